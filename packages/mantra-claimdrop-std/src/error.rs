@@ -0,0 +1,170 @@
+use cosmwasm_std::{ConversionOverflowError, Decimal, OverflowError, StdError};
+use cw_migrate_error_derive::cw_migrate_invalid_version_error;
+use cw_ownable::OwnershipError;
+use cw_utils::PaymentError;
+use thiserror::Error;
+
+use crate::msg::ClaimFailureReason;
+
+#[cw_migrate_invalid_version_error]
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Semver parsing error: {0}")]
+    SemVer(String),
+
+    #[error("{0}")]
+    OwnershipError(#[from] OwnershipError),
+
+    #[error("{0}")]
+    OverflowError(#[from] OverflowError),
+
+    #[error("{0}")]
+    ConversionOverflowError(#[from] ConversionOverflowError),
+
+    #[error("{0}")]
+    PaymentError(#[from] PaymentError),
+
+    #[error("Invalid distribution percentage, expected: {expected}, actual: {actual}")]
+    InvalidDistributionPercentage { expected: Decimal, actual: Decimal },
+
+    #[error("Invalid distribution percentage, cannot be zero")]
+    ZeroDistributionPercentage,
+
+    #[error("Invalid campaign parameter: {param} - {reason}")]
+    InvalidCampaignParam { param: String, reason: String },
+
+    #[error("Claim amount exceeds the maximum claimable amount")]
+    ExceededMaxClaimAmount,
+
+    #[error("Campaign error: {reason}")]
+    CampaignError { reason: String },
+
+    #[error("Invalid distribution times, start time: {start_time}, end time: {end_time}")]
+    InvalidDistributionTimes { start_time: u64, end_time: u64 },
+
+    #[error("Invalid start distribution time, start time: {start_time}, campaign start time: {campaign_start_time}. The start time needs to be in the future.")]
+    InvalidStartDistributionTime {
+        start_time: u64,
+        campaign_start_time: u64,
+    },
+
+    #[error("Invalid end distribution time, end time: {end_time}, campaign_end_time: {campaign_end_time}. The distribution end time needs to be less or equal to the campaign's end time.")]
+    InvalidEndDistributionTime {
+        end_time: u64,
+        campaign_end_time: u64,
+    },
+
+    #[error("There's nothing to claim for the given address")]
+    NothingToClaim,
+
+    #[error("No allocation found for address: {address}")]
+    NoAllocationFound { address: String },
+
+    #[error("The current address already has an allocation: {address}")]
+    AllocationAlreadyExists { address: String },
+
+    #[error("Address is blacklisted")]
+    AddressBlacklisted,
+
+    #[error("Address {address} has already exited early")]
+    AlreadyExitedEarly { address: String },
+
+    #[error("The allocation of address {address} has been forfeited")]
+    AllocationForfeited { address: String },
+
+    #[error("Address {address} failed the compliance check")]
+    ComplianceCheckFailed { address: String },
+
+    #[error("Invalid claim amount: {reason}")]
+    InvalidClaimAmount { reason: String },
+
+    #[error("Invalid input: {reason}")]
+    InvalidInput { reason: String },
+
+    #[error("Batch size limit exceeded: {actual}, maximum allowed: {max}")]
+    BatchSizeLimitExceeded { actual: usize, max: usize },
+
+    #[error("Unauthorized")]
+    Unauthorized,
+
+    #[error("No claim approval found for operator {operator}")]
+    ClaimApprovalNotFound { operator: String },
+
+    #[error("Invalid IBC channel: {reason}")]
+    InvalidIbcChannel { reason: String },
+
+    #[error("IBC channel {channel_id} is not allowed to send claims")]
+    IbcChannelNotAllowed { channel_id: String },
+
+    #[error("Invalid IBC transfer: {reason}")]
+    InvalidIbcTransfer { reason: String },
+
+    #[error("The contract is paused")]
+    ContractPaused,
+
+    #[error("The contract is not paused")]
+    ContractNotPaused,
+
+    #[error("A data migration is in progress, it has to complete before the contract can be used")]
+    MigrationInProgress,
+
+    #[error("There's no data migration in progress")]
+    NoMigrationInProgress,
+
+    #[error("The uploaded allocations don't match the expected {field}: expected {expected}, got {actual}")]
+    AllocationsMismatch {
+        field: String,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("The allocations have been finalized, they can no longer be changed")]
+    AllocationsFinalized,
+
+    #[error("The allocations have to be finalized first")]
+    AllocationsNotFinalized,
+}
+
+impl ContractError {
+    /// Converts a claim failure reason into the error returned by the Claim message
+    pub fn from_claim_failure(reason: ClaimFailureReason, address: &str) -> Self {
+        match reason {
+            ClaimFailureReason::NoActiveCampaign => Self::CampaignError {
+                reason: "there's not an active campaign".to_string(),
+            },
+            ClaimFailureReason::Paused => Self::ContractPaused,
+            ClaimFailureReason::NotStarted => Self::CampaignError {
+                reason: "not started".to_string(),
+            },
+            ClaimFailureReason::Closed => Self::CampaignError {
+                reason: "has been closed, cannot claim".to_string(),
+            },
+            ClaimFailureReason::Unauthorized => Self::Unauthorized,
+            ClaimFailureReason::Blacklisted => Self::AddressBlacklisted,
+            ClaimFailureReason::NotCompliant => Self::ComplianceCheckFailed {
+                address: address.to_string(),
+            },
+            ClaimFailureReason::NoAllocation => Self::NoAllocationFound {
+                address: address.to_string(),
+            },
+            ClaimFailureReason::InvalidAmount { reason } => Self::InvalidClaimAmount { reason },
+            ClaimFailureReason::NothingToClaim => Self::NothingToClaim,
+            ClaimFailureReason::Forfeited => Self::AllocationForfeited {
+                address: address.to_string(),
+            },
+            ClaimFailureReason::InsufficientFunds { .. } => Self::CampaignError {
+                reason: "no funds available to claim".to_string(),
+            },
+            ClaimFailureReason::AllocationsNotFinalized => Self::AllocationsNotFinalized,
+        }
+    }
+}
+
+impl From<semver::Error> for ContractError {
+    fn from(err: semver::Error) -> Self {
+        Self::SemVer(err.to_string())
+    }
+}
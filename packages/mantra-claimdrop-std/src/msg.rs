@@ -0,0 +1,1901 @@
+use std::fmt::{Display, Formatter};
+
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{
+    ensure, Binary, BlockInfo, Coin, Decimal, Decimal256, HexBinary, Timestamp, Uint128,
+};
+use cw_ownable::{cw_ownable_execute, cw_ownable_query};
+use cw_utils::Expiration;
+
+use crate::error::ContractError;
+
+/// Maximum length for campaign name
+const MAX_NAME_LENGTH: usize = 200;
+/// Maximum length for campaign description
+const MAX_DESCRIPTION_LENGTH: usize = 2000;
+/// Maximum length for campaign type
+const MAX_TYPE_LENGTH: usize = 200;
+/// Maximum number of schedules a campaign can define
+const MAX_SCHEDULES: usize = 10;
+/// Maximum length for schedule ids
+const MAX_SCHEDULE_ID_LENGTH: usize = 64;
+/// Maximum length for milestone ids
+const MAX_MILESTONE_ID_LENGTH: usize = 64;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// Owner of the contract. If not set, it is the sender of the Instantiate message.
+    pub owner: Option<String>,
+}
+
+#[cw_ownable_execute]
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Manages campaigns based on the action, defined by [CampaignAction].
+    ManageCampaign { action: CampaignAction },
+    /// Claims rewards from a campaign
+    Claim {
+        /// The receiver address of the claimed rewards. If not set, the sender of the message will be the receiver.
+        /// This is useful for allowing a contract to do the claim operation on behalf of a user.
+        receiver: Option<String>,
+        /// The amount to claim. If not set, all available tokens will be claimed.
+        amount: Option<Uint128>,
+        /// If set, the claimed tokens are sent to an address on another chain over ICS-20 instead
+        /// of the receiver. If the transfer fails, the claim is restored.
+        ibc_destination: Option<IbcDestination>,
+    },
+    /// Adds a batch of addresses and their allocations. This can only be done before the campaign has started,
+    /// unless the allocations only vest on relative distribution types.
+    AddAllocations {
+        /// Vector of (address, amount) pairs, or (address, amount, schedule id) triples for the
+        /// allocations vesting on one of the schedules of the campaign
+        allocations: Vec<AllocationRow>,
+    },
+    /// Adds a batch of addresses and their allocations, encoded in a compact binary format to fit
+    /// larger batches in a transaction. Behaves like AddAllocations with (address, amount) pairs.
+    ///
+    /// The data is a sequence of allocations, each encoded as an unsigned LEB128 varint header,
+    /// followed by the address and the amount as an unsigned LEB128 varint. A header of zero
+    /// means the address is a canonical 20-byte address, otherwise the header is the length in
+    /// bytes of the UTF-8 encoded address.
+    AddAllocationsPacked {
+        /// The encoded allocations
+        data: Binary,
+    },
+    /// Records the amounts already distributed to addresses outside of this contract, e.g. by a
+    /// previous campaign, so they are subtracted from what the addresses can claim. This can only be
+    /// done after the campaign has been created and before it has started, for addresses that
+    /// have an allocation. Seeding a distribution slot again replaces its previous amount.
+    SeedClaims {
+        /// Vector of (address, [(distribution slot, amount)]) pairs
+        claims: Vec<(String, Vec<(u64, Uint128)>)>,
+    },
+    /// Replaces an address in the allocation list. This can only be done before the campaign has started.
+    /// It can't be done once the allocations are finalized.
+    ReplaceAddress {
+        /// The old address to replace
+        old_address: String,
+        /// The new address to use
+        new_address: String,
+    },
+    /// Removes an address in the allocation list. This can only be done before the campaign has started.
+    /// It can't be done once the allocations are finalized.
+    RemoveAddress {
+        /// The address to remove
+        address: String,
+    },
+    /// Blacklists or unblacklists an address. This can be done at any time.
+    BlacklistAddress {
+        /// The address to blacklist/unblacklist
+        address: String,
+        /// Whether to blacklist or unblacklist
+        blacklist: bool,
+        /// The reason for blacklisting the address. Ignored when unblacklisting.
+        reason: Option<BlacklistReason>,
+        /// A free-form description of the reason, e.g. a reference to an incident report.
+        /// Ignored when unblacklisting.
+        note: Option<String>,
+        /// When the blacklisting expires. If not set, the address stays blacklisted until it's
+        /// unblacklisted. Ignored when unblacklisting.
+        expiration: Option<Expiration>,
+        /// Whether the vesting clock of the address is frozen while it's blacklisted. Defaults to
+        /// false. Ignored when unblacklisting.
+        freeze_vesting: Option<bool>,
+    },
+    /// Blacklists or unblacklists a batch of addresses, applying the same entry to all of them.
+    BlacklistAddresses {
+        /// The addresses to blacklist/unblacklist
+        addresses: Vec<String>,
+        /// Whether to blacklist or unblacklist the addresses
+        blacklist: bool,
+        /// The reason for blacklisting the addresses. Ignored when unblacklisting.
+        reason: Option<BlacklistReason>,
+        /// A free-form description of the reason. Ignored when unblacklisting.
+        note: Option<String>,
+        /// When the blacklisting expires. Ignored when unblacklisting.
+        expiration: Option<Expiration>,
+        /// Whether the vesting clock of the addresses is frozen while they're blacklisted.
+        /// Ignored when unblacklisting.
+        freeze_vesting: Option<bool>,
+    },
+    /// Manages authorized wallets that can perform admin actions. Only the owner can manage authorized wallets.
+    ManageAuthorizedWallets {
+        /// Vector of addresses to authorize/unauthorize
+        addresses: Vec<String>,
+        /// Whether to authorize or unauthorize the addresses
+        authorized: bool,
+    },
+    /// Restores the allocations, claims and blacklist entries exported from another contract with
+    /// the ExportState query, so users keep their progress. This can only be done after the campaign
+    /// has been created and before it has started.
+    ImportState {
+        /// The entries to import, as returned by the ExportState query
+        entries: Vec<StateEntry>,
+    },
+    /// Allows an IBC channel to send claim packets, or updates its configuration. Only the owner can
+    /// manage the IBC channels.
+    ManageIbcChannel {
+        /// The IBC channel on this chain
+        channel_id: String,
+        /// The configuration of the channel. If not set, the channel is disallowed.
+        config: Option<IbcChannelConfig>,
+    },
+    /// Allows an operator, e.g. a bot or a custody provider, to claim the sender's allocation on their
+    /// behalf. The claimed tokens are still sent to the sender. Approving an operator again replaces
+    /// the previous approval.
+    ApproveClaimer {
+        /// The address allowed to claim on behalf of the sender
+        operator: String,
+        /// When the approval expires. If not set, the approval doesn't expire.
+        expires: Option<Expiration>,
+        /// The maximum amount the operator can claim. If not set, the amount is unlimited.
+        max_amount: Option<Uint128>,
+    },
+    /// Revokes the approval of an operator to claim on behalf of the sender.
+    RevokeClaimer {
+        /// The operator to revoke
+        operator: String,
+    },
+    /// Subscribes the sender to auto-claims, or unsubscribes it. The rewards of subscribed addresses
+    /// are claimed for them by the Crank message, minus the crank tip.
+    AutoClaim {
+        /// Whether the sender is subscribed
+        enabled: bool,
+    },
+    /// Claims the rewards available for the next subscribed addresses, resuming after the last
+    /// address processed by the previous crank. Anyone can crank, and the caller is rewarded with
+    /// the crank tip, paid from the crank tip pool while it has funds.
+    Crank {
+        /// The maximum number of addresses to process. If not set, the default value is used.
+        limit: Option<u32>,
+    },
+    /// Updates the share of the auto-claimed rewards paid to crank callers.
+    UpdateCrankTip {
+        /// The share of the auto-claimed rewards paid to the crank caller
+        tip: Decimal,
+    },
+    /// Adds the reward tokens sent with the message to the crank tip pool. The crank tips are paid
+    /// from the pool, so the auto-claimed rewards are paid out in full. The pool is refunded when
+    /// the campaign is closed.
+    FundCrankTips {},
+    /// Forfeits the remaining allocations of the given addresses, which can no longer claim. Only
+    /// the allocations of blacklisted addresses can be forfeited before the campaign ends. The
+    /// forfeited allocations are returned to the owner or redistributed, depending on the
+    /// forfeiture mode of the campaign.
+    ForfeitAllocations {
+        /// The addresses whose allocations are forfeited
+        addresses: Vec<String>,
+    },
+    /// Exits the linear vesting distributions with an early exit penalty, claiming the vested
+    /// amount plus the unvested amount minus the penalty.
+    ExitEarly {},
+    /// Unlocks the milestone distributions with the given id, which start at the current time.
+    /// Only the owner or authorized wallets can trigger milestones.
+    TriggerMilestone {
+        /// The id of the milestone
+        id: String,
+    },
+    /// Checks the allocations uploaded so far against the expected checksum, count and total, and
+    /// locks further uploads, imports and removals. Addresses can still be replaced, to recover
+    /// lost keys, which moves their allocation without changing the count and total. Only the
+    /// owner or authorized wallets can finalize the allocations.
+    ///
+    /// The checksum is the sum, modulo 2^256, of `sha256(address || amount)` over the current
+    /// (address, amount) pairs, as 32 big-endian bytes, so it doesn't depend on the upload order.
+    /// The address is the validated address as stored by the contract and the amount is encoded
+    /// as 16 big-endian bytes.
+    FinalizeAllocations {
+        /// The expected checksum of the uploaded allocations
+        expected_hash: HexBinary,
+        /// The expected number of uploaded allocations
+        expected_count: u64,
+        /// The expected sum of the uploaded allocations
+        expected_total: Uint128,
+    },
+    /// Continues a data migration that didn't complete within the Migrate message. Other messages
+    /// are rejected until the migration completes.
+    ContinueMigration {
+        /// The maximum number of entries to process. If not set, the default value is used.
+        batch_size: Option<u32>,
+    },
+}
+
+#[cw_ownable_query]
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(CampaignResponse)]
+    /// Get the airdrop campaign
+    Campaign {},
+    #[returns(RewardsResponse)]
+    /// Get the rewards for a specific campaign and receiver address.
+    Rewards {
+        /// The address to get the rewards for.
+        receiver: String,
+    },
+    #[returns(RewardsBreakdownResponse)]
+    /// Get the rewards for a specific receiver address, broken down by distribution slot.
+    RewardsBreakdown {
+        /// The address to get the rewards for.
+        receiver: String,
+    },
+    #[returns(RewardsAtResponse)]
+    /// Get the projected rewards for an address at the given timestamp.
+    RewardsAt {
+        /// The address to get the rewards for.
+        address: String,
+        /// The unix timestamp to evaluate the rewards at, in seconds.
+        timestamp: u64,
+    },
+    #[returns(VestingScheduleResponse)]
+    /// Get the projected rewards for an address at each of the given timestamps. Useful to render
+    /// unlock curves.
+    VestingSchedule {
+        /// The address to get the rewards for.
+        address: String,
+        /// The unix timestamps to evaluate the rewards at, in seconds.
+        points: Vec<u64>,
+    },
+    #[returns(SimulateClaimResponse)]
+    /// Simulates a claim without executing it, running the same checks as the Claim message.
+    SimulateClaim {
+        /// The address that would send the Claim message.
+        sender: String,
+        /// The receiver address of the claimed rewards. If not set, the sender is the receiver.
+        receiver: Option<String>,
+        /// The amount to claim. If not set, all available tokens would be claimed.
+        amount: Option<Uint128>,
+    },
+    #[returns(ClaimedResponse)]
+    /// Get the total amount of tokens claimed on the campaign.
+    Claimed {
+        /// If provided, it will return the tokens claimed by the specified address.
+        address: Option<String>,
+        /// The address to start querying from. Used for paginating results.
+        start_from: Option<String>,
+        /// The maximum number of items to return. If not set, the default value is used. Used for paginating results.
+        limit: Option<u16>,
+    },
+    #[returns(AllocationsResponse)]
+    /// Get the allocation for an address
+    Allocations {
+        /// The address to get the allocation for, if provided
+        address: Option<String>,
+        /// The address to start querying from. Used for paginating results.
+        start_after: Option<String>,
+        /// The maximum number of items to return. If not set, the default value is used. Used for paginating results.
+        limit: Option<u16>,
+    },
+    #[returns(BlacklistResponse)]
+    /// Check if an address is blacklisted
+    IsBlacklisted {
+        /// The address to check
+        address: String,
+    },
+    #[returns(BlacklistedAddressesResponse)]
+    /// Get blacklisted addresses with pagination, including the ones whose blacklisting has expired
+    Blacklist {
+        /// The address to start querying from. Used for paginating results.
+        start_after: Option<String>,
+        /// The maximum number of items to return. Used for paginating results.
+        limit: Option<u32>,
+    },
+    #[returns(AuthorizedResponse)]
+    /// Check if an address is authorized (owner or authorized wallet)
+    IsAuthorized {
+        /// The address to check
+        address: String,
+    },
+    #[returns(AuthorizedWalletsResponse)]
+    /// Get authorized wallets with pagination
+    AuthorizedWallets {
+        /// The address to start querying from. Used for paginating results.
+        start_after: Option<String>,
+        /// The maximum number of items to return. Used for paginating results.
+        limit: Option<u32>,
+    },
+    #[returns(AuditLogResponse)]
+    /// Get the administrative audit log with pagination
+    AuditLog {
+        /// If provided, only entries with the given action are returned, e.g. "blacklist_address".
+        action: Option<String>,
+        /// If provided, only entries affecting the given address are returned.
+        subject: Option<String>,
+        /// The sequence number to start querying from. Used for paginating results.
+        start_after: Option<u64>,
+        /// The maximum number of items to return. If not set, the default value is used. Used for paginating results.
+        limit: Option<u16>,
+    },
+    #[returns(ClaimHistoryResponse)]
+    /// Get the individual claims made by an address, with pagination
+    ClaimHistory {
+        /// The address to get the claim history for
+        address: String,
+        /// The sequence number to start querying from. Used for paginating results.
+        start_after: Option<u64>,
+        /// The maximum number of items to return. If not set, the default value is used. Used for paginating results.
+        limit: Option<u16>,
+    },
+    #[returns(ExportStateResponse)]
+    /// Get the allocations joined with the claims and blacklist entries of each address, with
+    /// pagination. Used to hand the state of the campaign over to another contract.
+    ExportState {
+        /// The address to start querying from. Used for paginating results.
+        start_after: Option<String>,
+        /// The maximum number of items to return. If not set, the default value is used. Used for paginating results.
+        limit: Option<u16>,
+    },
+    #[returns(AllocationChecksumResponse)]
+    /// Get the checksum, count and total of the uploaded allocations, and whether they have been
+    /// finalized
+    AllocationChecksum {},
+    #[returns(IbcChannelsResponse)]
+    /// Get the IBC channels allowed to send claim packets, with pagination
+    IbcChannels {
+        /// The channel to start querying from. Used for paginating results.
+        start_after: Option<String>,
+        /// The maximum number of items to return. Used for paginating results.
+        limit: Option<u32>,
+    },
+    #[returns(ClaimApprovalsResponse)]
+    /// Get the operators approved to claim on behalf of an address, with pagination
+    ClaimApprovals {
+        /// The address that approved the operators
+        owner: String,
+        /// The operator to start querying from. Used for paginating results.
+        start_after: Option<String>,
+        /// The maximum number of items to return. Used for paginating results.
+        limit: Option<u32>,
+    },
+    #[returns(AutoClaimResponse)]
+    /// Get the auto-claim configuration and the addresses subscribed to auto-claims, with pagination
+    AutoClaim {
+        /// The address to start querying from. Used for paginating results.
+        start_after: Option<String>,
+        /// The maximum number of items to return. Used for paginating results.
+        limit: Option<u32>,
+    },
+    #[returns(PausedResponse)]
+    /// Check if the contract is paused
+    IsPaused {},
+    #[returns(MigrationStatusResponse)]
+    /// Get the version of the contract and the progress of the data migration, if any
+    MigrationStatus {},
+}
+
+/// The messages the chain can send to the contract, e.g. through governance proposals. They don't
+/// require authorization, so they can be used if the owner key is lost or compromised.
+#[cw_serde]
+pub enum SudoMsg {
+    /// Closes the campaign, refunding the remaining funds to the given recipient. Rejected while a
+    /// data migration is in progress.
+    ForceCloseCampaign {
+        /// The address the remaining funds of the campaign are sent to
+        refund_recipient: String,
+    },
+    /// Sets the owner of the contract, cancelling any pending ownership transfer. Allowed while a
+    /// data migration is in progress, so a new owner can continue it.
+    ResetOwner {
+        /// The new owner of the contract
+        owner: String,
+    },
+    /// Pauses the contract. While paused, all the execute messages are rejected. Allowed while a
+    /// data migration is in progress.
+    Pause {},
+    /// Resumes the contract after it was paused. Allowed while a data migration is in progress.
+    Resume {},
+    /// Blacklists or unblacklists a batch of addresses, applying the same entry to all of them.
+    /// Rejected while a data migration is in progress.
+    BlacklistAddresses {
+        /// The addresses to blacklist/unblacklist
+        addresses: Vec<String>,
+        /// Whether to blacklist or unblacklist the addresses
+        blacklist: bool,
+        /// The reason for blacklisting the addresses. Ignored when unblacklisting.
+        reason: Option<BlacklistReason>,
+        /// A free-form description of the reason. Ignored when unblacklisting.
+        note: Option<String>,
+        /// When the blacklisting expires. Ignored when unblacklisting.
+        expiration: Option<Expiration>,
+        /// Whether the vesting clock of the addresses is frozen while they're blacklisted.
+        /// Ignored when unblacklisting.
+        freeze_vesting: Option<bool>,
+    },
+}
+
+#[cw_serde]
+pub struct MigrateMsg {
+    /// The maximum number of entries the data migration processes within the Migrate message. If
+    /// not set, the default value is used. The rest are processed with ContinueMigration messages.
+    pub batch_size: Option<u32>,
+}
+
+/// Response to the MigrationStatus query.
+#[cw_serde]
+pub struct MigrationStatusResponse {
+    /// The version of the contract
+    pub version: String,
+    /// The data migration in progress, if any
+    pub migration: Option<MigrationProgress>,
+}
+
+/// The progress of a data migration.
+#[cw_serde]
+pub struct MigrationProgress {
+    /// The version of the contract the migration started from
+    pub from_version: String,
+    /// The version of the contract the migration goes to
+    pub to_version: String,
+    /// The migration steps left to run, the first one being the one in progress
+    pub pending_steps: Vec<String>,
+    /// The last key processed by the step in progress
+    pub cursor: Option<String>,
+    /// The number of entries processed by the step in progress so far
+    pub processed: u64,
+}
+
+pub type CampaignResponse = Campaign;
+
+/// Response to the Rewards query.
+#[cw_serde]
+pub struct RewardsResponse {
+    /// The tokens that have been claimed by the address.
+    pub claimed: Vec<Coin>,
+    /// The total amount of tokens that is pending to be claimed by the address.
+    pub pending: Vec<Coin>,
+    /// The tokens that are available to be claimed by the address.
+    pub available_to_claim: Vec<Coin>,
+    /// The share of the redistributed forfeited allocations included in the allocation of the
+    /// address.
+    pub redistributed: Vec<Coin>,
+}
+
+/// Response to the RewardsBreakdown query.
+#[cw_serde]
+pub struct RewardsBreakdownResponse {
+    /// The aggregated rewards, same as returned by the Rewards query.
+    pub rewards: RewardsResponse,
+    /// The rewards for each distribution slot of the campaign, in the order they are defined.
+    pub slots: Vec<SlotRewards>,
+}
+
+/// The rewards of an address for a single distribution slot.
+#[cw_serde]
+pub struct SlotRewards {
+    /// The index of the distribution type on the campaign
+    pub slot: u64,
+    /// The distribution type of the slot
+    pub distribution_type: DistributionType,
+    /// The total amount of tokens the address is entitled to in this slot
+    pub entitlement: Coin,
+    /// The amount of tokens vested so far, regardless of what has been claimed
+    pub vested: Coin,
+    /// The amount of tokens claimed so far
+    pub claimed: Coin,
+    /// The amount of tokens that can be claimed now
+    pub claimable: Coin,
+    /// The unix timestamp when the slot starts, in seconds. Not set for milestones that haven't
+    /// been triggered yet.
+    pub start_time: Option<u64>,
+    /// The unix timestamp when the slot ends, in seconds. Lump sum slots end when they start. Not
+    /// set for milestones that haven't been triggered yet.
+    pub end_time: Option<u64>,
+    /// The unix timestamp when the cliff period ends, in seconds, if any
+    pub cliff_end_time: Option<u64>,
+}
+
+/// Response to the RewardsAt query.
+pub type RewardsAtResponse = ProjectedRewards;
+
+/// Response to the VestingSchedule query.
+#[cw_serde]
+pub struct VestingScheduleResponse {
+    /// The projected rewards at each of the requested timestamps, in the order they were requested.
+    pub schedule: Vec<ProjectedRewards>,
+}
+
+/// The rewards of an address projected at a given point in time.
+#[cw_serde]
+pub struct ProjectedRewards {
+    /// The unix timestamp the rewards were evaluated at, in seconds
+    pub timestamp: u64,
+    /// The total amount of tokens vested at the timestamp, ignoring previous claims
+    pub vested: Coin,
+    /// The amount of tokens claimed so far
+    pub claimed: Coin,
+    /// The amount of tokens that can be claimed at the timestamp, taking previous claims into account
+    pub claimable: Coin,
+}
+
+/// Response to the SimulateClaim query.
+#[cw_serde]
+pub enum SimulateClaimResponse {
+    /// The claim would succeed
+    Success {
+        /// The amount that would be claimed
+        amount: Coin,
+        /// A vector with a tuple with (distribution slot, amount) breaking down the amount that
+        /// would be recorded
+        slots: Vec<(u64, Uint128)>,
+    },
+    /// The claim would fail
+    Failure {
+        /// The reason the claim would fail
+        reason: ClaimFailureReason,
+    },
+}
+
+/// The reasons a claim can fail for.
+#[cw_serde]
+pub enum ClaimFailureReason {
+    /// There's no campaign
+    NoActiveCampaign,
+    /// The contract has been paused
+    Paused,
+    /// The campaign has not started yet
+    NotStarted,
+    /// The campaign has been closed
+    Closed,
+    /// The sender is not allowed to claim on behalf of the receiver
+    Unauthorized,
+    /// The receiver is blacklisted
+    Blacklisted,
+    /// The receiver didn't pass the compliance check
+    NotCompliant,
+    /// The receiver has no allocation
+    NoAllocation,
+    /// The requested amount is invalid
+    InvalidAmount {
+        /// Why the amount is invalid
+        reason: String,
+    },
+    /// There's nothing to claim at this point in time
+    NothingToClaim,
+    /// The allocation of the receiver has been forfeited
+    Forfeited,
+    /// The contract doesn't hold enough funds to pay out the claim
+    InsufficientFunds {
+        /// The funds available in the contract
+        available: Coin,
+    },
+    /// The campaign requires the allocations to be finalized, and they haven't been
+    AllocationsNotFinalized,
+}
+
+/// Response to the Claimed query.
+#[cw_serde]
+pub struct ClaimedResponse {
+    /// Contains a vector with a tuple with (address, coin) that have been claimed
+    pub claimed: Vec<(String, Coin)>,
+    /// Contains a vector with a tuple with (address, [(distribution slot, coin)]), breaking down the
+    /// claimed amounts by distribution slot. Entries are in the same order as `claimed`.
+    pub claimed_by_slot: Vec<(String, Vec<(u64, Coin)>)>,
+}
+
+/// Response to the ClaimHistory query.
+#[cw_serde]
+pub struct ClaimHistoryResponse {
+    /// The claims made by the address, sorted by sequence number
+    pub history: Vec<ClaimRecord>,
+}
+
+/// Response to the Allocation query.
+#[cw_serde]
+pub struct AllocationsResponse {
+    /// A vector with a tuple with (address, coin) that have been allocated.
+    pub allocations: Vec<(String, Coin)>,
+}
+
+/// Response to the AllocationChecksum query.
+#[cw_serde]
+pub struct AllocationChecksumResponse {
+    /// The checksum of the allocations, see ExecuteMsg::FinalizeAllocations
+    pub hash: HexBinary,
+    /// The number of allocations
+    pub count: u64,
+    /// The sum of the allocations
+    pub total: Uint128,
+    /// The time the allocations were finalized (unix timestamp), in seconds, if they have been
+    pub finalized_at: Option<u64>,
+}
+
+/// Response to the ExportState query.
+#[cw_serde]
+pub struct ExportStateResponse {
+    /// The state of each address with an allocation, sorted by address
+    pub entries: Vec<StateEntry>,
+}
+
+/// The state of an address in the campaign, as exported by the ExportState query and restored by
+/// the ImportState message.
+#[cw_serde]
+pub struct StateEntry {
+    /// The address
+    pub address: String,
+    /// The allocation of the address
+    pub allocation: Uint128,
+    /// The amounts claimed by the address, sorted by distribution slot
+    pub claims: Vec<SlotClaimEntry>,
+    /// The blacklist entry of the address, if it's blacklisted
+    pub blacklist: Option<BlacklistEntry>,
+    /// The schedule the allocation vests on, if not the distribution types of the campaign
+    #[serde(default)]
+    pub schedule: Option<String>,
+    /// The time the allocation was added, if it was added after the campaign started
+    #[serde(default)]
+    pub registered_at: Option<u64>,
+    /// The time of the first claim of the address, if its relative distribution types are
+    /// anchored to it
+    #[serde(default)]
+    pub first_claimed_at: Option<u64>,
+}
+
+/// The amount an address has claimed from a distribution slot.
+#[cw_serde]
+pub struct SlotClaimEntry {
+    /// The index of the distribution type on the campaign
+    pub slot: u64,
+    /// The total amount claimed from the slot
+    pub amount: Uint128,
+    /// The timestamp of the last claim from the slot, in seconds
+    pub timestamp: u64,
+}
+
+/// Response to the Blacklist query.
+#[cw_serde]
+pub struct BlacklistResponse {
+    /// Whether the address is blacklisted
+    pub is_blacklisted: bool,
+}
+
+/// Response to the Blacklist query.
+#[cw_serde]
+pub struct BlacklistedAddressesResponse {
+    /// A vector with a tuple with (address, blacklist entry)
+    pub entries: Vec<(String, BlacklistEntry)>,
+}
+
+/// Represents a blacklisted address.
+#[cw_serde]
+pub struct BlacklistEntry {
+    /// The reason the address was blacklisted
+    pub reason: BlacklistReason,
+    /// The time at which the address was blacklisted (unix timestamp), in seconds
+    pub blacklisted_at: u64,
+    /// The address that blacklisted the address
+    pub blacklisted_by: String,
+    /// A free-form description of the reason
+    pub note: Option<String>,
+    /// When the blacklisting expires, if ever. Expired entries are kept until the address is
+    /// unblacklisted, but don't prevent the address from claiming.
+    pub expiration: Option<Expiration>,
+    /// Whether the vesting clock of the address is frozen while it's blacklisted
+    #[serde(default)]
+    pub freeze_vesting: bool,
+}
+
+impl BlacklistEntry {
+    /// Checks if the blacklisting is in effect, i.e. it hasn't expired
+    pub fn is_active(&self, block: &BlockInfo) -> bool {
+        self.expiration
+            .as_ref()
+            .is_none_or(|expiration| !expiration.is_expired(block))
+    }
+
+    /// Returns the period, as (start, end) timestamps in seconds, during which the vesting clock
+    /// was frozen by this blacklisting up until the given time, if it freezes vesting at all.
+    pub fn frozen_period(&self, until: u64) -> Option<(u64, u64)> {
+        if !self.freeze_vesting {
+            return None;
+        }
+
+        let end = match self.expiration {
+            Some(Expiration::AtTime(expiration)) => std::cmp::min(expiration.seconds(), until),
+            _ => until,
+        };
+
+        Some((self.blacklisted_at, std::cmp::max(self.blacklisted_at, end)))
+    }
+}
+
+/// The reason codes for blacklisting an address.
+#[cw_serde]
+#[derive(Default)]
+pub enum BlacklistReason {
+    /// No reason was given
+    #[default]
+    Unspecified,
+    /// The wallet was compromised
+    Compromised,
+    /// The address is subject to sanctions
+    Sanctioned,
+    /// The address is under investigation
+    Investigation,
+    /// The address is not eligible for the campaign
+    Ineligible,
+}
+
+/// Response to the IbcChannels query.
+#[cw_serde]
+pub struct IbcChannelsResponse {
+    /// A vector with a tuple with (channel id, channel configuration)
+    pub channels: Vec<(String, IbcChannelConfig)>,
+}
+
+/// The approval of an operator to claim on behalf of an allocation owner.
+#[cw_serde]
+pub struct ClaimApproval {
+    /// When the approval expires. If not set, the approval doesn't expire.
+    pub expires: Option<Expiration>,
+    /// The amount the operator can still claim. If not set, the amount is unlimited.
+    pub max_amount: Option<Uint128>,
+}
+
+/// Response to the ClaimApprovals query.
+#[cw_serde]
+pub struct ClaimApprovalsResponse {
+    /// A vector with a tuple with (operator, approval)
+    pub approvals: Vec<(String, ClaimApproval)>,
+}
+
+/// The version of the IBC channels claim packets are sent over.
+pub const IBC_APP_VERSION: &str = "claimdrop-1";
+
+/// The configuration of an IBC channel allowed to send claim packets.
+#[cw_serde]
+pub struct IbcChannelConfig {
+    /// The bech32 prefix of the addresses the channel can claim for, i.e. the prefix of the
+    /// remote chain. Packets for addresses with a different prefix are rejected.
+    pub address_prefix: String,
+    /// The ICS-20 transfer channel on this chain used to send the claimed tokens back to the
+    /// remote chain. If not set, the claimed tokens can only be sent to addresses on this chain.
+    pub transfer_channel: Option<String>,
+}
+
+/// Where the claimed tokens are sent when they're transferred to another chain over ICS-20.
+#[cw_serde]
+pub struct IbcDestination {
+    /// The ICS-20 transfer channel on this chain
+    pub channel_id: String,
+    /// The address on the remote chain receiving the tokens
+    pub remote_address: String,
+    /// The number of seconds after which the transfer times out, up to a week. If not set, the
+    /// default timeout is used.
+    pub timeout: Option<u64>,
+}
+
+/// The packet a companion contract on a remote chain sends over IBC to claim the rewards of an
+/// address. The companion contract is responsible for authenticating the address.
+#[cw_serde]
+pub struct IbcClaimPacket {
+    /// The address whose allocation is claimed, as it was added to the allocations
+    pub address: String,
+    /// The amount to claim. If not set, all available tokens will be claimed.
+    pub amount: Option<Uint128>,
+    /// Where the claimed tokens are sent to
+    pub payout: IbcClaimPayout,
+}
+
+/// Where the tokens claimed over IBC are sent to.
+#[cw_serde]
+pub enum IbcClaimPayout {
+    /// The tokens are sent to an address on this chain
+    Local {
+        /// The address on this chain receiving the tokens. Must be the claimed address or an
+        /// operator it approved to claim on its behalf, within the limits of the approval.
+        receiver: String,
+    },
+    /// The tokens are sent to an address on the remote chain over ICS-20, through the transfer
+    /// channel configured for the channel the packet was received on
+    Remote {
+        /// The address on the remote chain receiving the tokens
+        remote_address: String,
+    },
+}
+
+/// The acknowledgement data of a successful claim packet.
+#[cw_serde]
+pub struct IbcClaimAck {
+    /// The amount claimed
+    pub amount: Coin,
+}
+
+/// Response to the AutoClaim query.
+#[cw_serde]
+pub struct AutoClaimResponse {
+    /// The share of the auto-claimed rewards paid to the crank caller
+    pub tip: Decimal,
+    /// The funds left in the crank tip pool
+    pub tip_pool: Uint128,
+    /// The last address processed by the previous crank, if the crank hasn't gone through all the
+    /// subscribed addresses yet
+    pub cursor: Option<String>,
+    /// The addresses subscribed to auto-claims
+    pub subscriptions: Vec<String>,
+}
+
+/// Response to the IsPaused query.
+#[cw_serde]
+pub struct PausedResponse {
+    /// Whether the contract is paused
+    pub is_paused: bool,
+}
+
+/// Response to the IsAuthorized query.
+#[cw_serde]
+pub struct AuthorizedResponse {
+    /// Whether the address is authorized (owner or authorized wallet)
+    pub is_authorized: bool,
+}
+
+/// Response to the AuthorizedWallets query.
+#[cw_serde]
+pub struct AuthorizedWalletsResponse {
+    /// List of authorized wallet addresses
+    pub wallets: Vec<String>,
+}
+
+/// Response to the AuditLog query.
+#[cw_serde]
+pub struct AuditLogResponse {
+    /// The audit log entries, sorted by sequence number
+    pub entries: Vec<AuditEntry>,
+}
+
+/// The campaign action that can be executed with the [ExecuteMsg::ManageCampaign] message.
+#[cw_serde]
+pub enum CampaignAction {
+    /// Creates a new campaign
+    CreateCampaign {
+        /// The parameters to create a campaign with
+        params: Box<CampaignParams>,
+    },
+    /// Closes the campaign
+    CloseCampaign {},
+    /// Updates how claimers are checked against the compliance registry
+    UpdateCompliance {
+        /// The compliance registry contract. If not set, the current one is kept.
+        compliance_contract: Option<String>,
+        /// How the compliance registry is used
+        mode: ComplianceMode,
+    },
+    /// Updates what happens to the allocations forfeited by addresses
+    UpdateForfeitureMode {
+        /// What happens to the forfeited allocations
+        mode: ForfeitureMode,
+    },
+}
+
+/// An allocation uploaded with the AddAllocations message.
+#[cw_serde]
+#[serde(untagged)]
+pub enum AllocationRow {
+    /// (address, amount), vesting on the distribution types of the campaign
+    Default(String, Uint128),
+    /// (address, amount, schedule id), vesting on the given schedule of the campaign
+    Scheduled(String, Uint128, String),
+}
+
+impl AllocationRow {
+    /// Returns the address, amount and schedule id of the allocation
+    pub fn into_parts(self) -> (String, Uint128, Option<String>) {
+        match self {
+            AllocationRow::Default(address, amount) => (address, amount, None),
+            AllocationRow::Scheduled(address, amount, schedule) => {
+                (address, amount, Some(schedule))
+            }
+        }
+    }
+}
+
+impl From<(String, Uint128)> for AllocationRow {
+    fn from((address, amount): (String, Uint128)) -> Self {
+        AllocationRow::Default(address, amount)
+    }
+}
+
+/// A named set of distribution types, which allocations can vest on instead of the distribution
+/// types of the campaign, e.g. to vest the allocations of the team differently from the community.
+#[cw_serde]
+pub struct Schedule {
+    /// The schedule id, referenced by the allocations vesting on it
+    pub id: String,
+    /// The ways the allocations on this schedule are distributed. The sum of the percentages
+    /// must be 100.
+    pub distribution_type: Vec<DistributionType>,
+}
+
+/// Represents a campaign.
+#[cw_serde]
+pub struct Campaign {
+    /// The campaign name
+    pub name: String,
+    /// The campaign description
+    pub description: String,
+    /// Campaign type. Value used by front ends.
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// The denom to be distributed as reward by the campaign
+    pub reward_denom: String,
+    /// The total amount of the reward asset that is intended to be allocated to the campaign
+    pub total_reward: Coin,
+    /// The amount of the reward asset that has been claimed
+    pub claimed: Coin,
+    /// The amount of the reward asset that has been forfeited, either by blacklisted addresses,
+    /// unclaimed after the campaign ended, or as early exit penalties
+    #[serde(default)]
+    pub forfeited: Uint128,
+    /// The ways the reward is distributed, which are defined by the [DistributionType].
+    /// The sum of the percentages must be 100.
+    pub distribution_type: Vec<DistributionType>,
+    /// The named schedules allocations can vest on instead of the distribution types above
+    #[serde(default)]
+    pub schedules: Vec<Schedule>,
+    /// The campaign start time (unix timestamp), in seconds
+    pub start_time: u64,
+    /// The campaign end time (unix timestamp), in seconds
+    pub end_time: u64,
+    /// The timestamp at which the campaign was closed, in seconds
+    pub closed: Option<u64>,
+    /// The compliance registry contract queried before paying out claims, if any
+    pub compliance_contract: Option<String>,
+    /// How the compliance registry is used to check the receivers of claims
+    #[serde(default)]
+    pub compliance_mode: ComplianceMode,
+    /// The amount redistributed from forfeited allocations per allocated token, added to the
+    /// allocations of the addresses that haven't exited early nor forfeited their allocation
+    #[serde(default)]
+    pub reward_per_share: Decimal256,
+    /// What happens to the allocations forfeited by blacklisted or unclaimed addresses
+    #[serde(default)]
+    pub forfeiture_mode: ForfeitureMode,
+    /// The milestones that have been triggered
+    #[serde(default)]
+    pub milestones: Vec<TriggeredMilestone>,
+    /// Whether the allocations have to be finalized before the campaign can be claimed from
+    #[serde(default)]
+    pub require_finalized_allocations: bool,
+}
+
+/// A milestone that has been triggered, unlocking the milestone distributions with its id.
+#[cw_serde]
+pub struct TriggeredMilestone {
+    /// The id of the milestone
+    pub id: String,
+    /// The time the milestone was triggered (unix timestamp), in seconds
+    pub time: u64,
+}
+
+impl Display for Campaign {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Campaign {{ name: {}, description: {}, type: {}, reward_denom: {}, total_reward: {}, claimed: {}, forfeited: {}, distribution_type: {:?}, schedules: {:?}, start_time: {}, end_time: {}, closed: {:?}, compliance_contract: {:?}, compliance_mode: {:?}, reward_per_share: {}, forfeiture_mode: {:?}, milestones: {:?}, require_finalized_allocations: {} }}",
+            self.name,
+            self.description,
+            self.ty,
+            self.reward_denom,
+            self.total_reward,
+            self.claimed,
+            self.forfeited,
+            self.distribution_type,
+            self.schedules,
+            self.start_time,
+            self.end_time,
+            self.closed,
+            self.compliance_contract,
+            self.compliance_mode,
+            self.reward_per_share,
+            self.forfeiture_mode,
+            self.milestones,
+            self.require_finalized_allocations
+        )
+    }
+}
+
+impl Campaign {
+    /// Creates a new campaign from the given parameters
+    pub fn from_params(params: CampaignParams) -> Self {
+        let reward_denom = params.reward_denom.clone();
+
+        Campaign {
+            name: params.name,
+            description: params.description,
+            ty: params.ty,
+            reward_denom: params.reward_denom,
+            total_reward: params.total_reward,
+            claimed: Coin {
+                denom: reward_denom,
+                amount: Uint128::zero(),
+            },
+            forfeited: Uint128::zero(),
+            distribution_type: params.distribution_type,
+            schedules: params.schedules,
+            start_time: params.start_time,
+            end_time: params.end_time,
+            closed: None,
+            compliance_contract: None,
+            compliance_mode: ComplianceMode::Disabled,
+            reward_per_share: Decimal256::zero(),
+            forfeiture_mode: ForfeitureMode::Return,
+            milestones: vec![],
+            require_finalized_allocations: params.require_finalized_allocations,
+        }
+    }
+
+    /// Checks if the campaign has started
+    pub fn has_started(&self, current_time: &Timestamp) -> bool {
+        current_time.seconds() >= self.start_time
+    }
+
+    /// Checks if the campaign has ended
+    pub fn has_ended(&self, current_time: &Timestamp) -> bool {
+        current_time.seconds() >= self.end_time
+    }
+
+    /// Checks if any distribution type of the campaign or its schedules is the milestone with the
+    /// given id
+    pub fn has_milestone(&self, id: &str) -> bool {
+        self.distribution_type
+            .iter()
+            .chain(
+                self.schedules
+                    .iter()
+                    .flat_map(|schedule| schedule.distribution_type.iter()),
+            )
+            .any(|dist| dist.milestone_id() == Some(id))
+    }
+
+    /// Returns the distribution types of the given schedule, or the ones of the campaign if no
+    /// schedule is given
+    pub fn distribution_type_for(
+        &self,
+        schedule: Option<&str>,
+    ) -> Result<&[DistributionType], ContractError> {
+        let Some(schedule) = schedule else {
+            return Ok(&self.distribution_type);
+        };
+
+        self.schedules
+            .iter()
+            .find(|candidate| candidate.id == schedule)
+            .map(|schedule| schedule.distribution_type.as_slice())
+            .ok_or(ContractError::InvalidInput {
+                reason: format!("unknown schedule {schedule}"),
+            })
+    }
+}
+
+/// What happens to the allocations forfeited by blacklisted or unclaimed addresses.
+#[cw_serde]
+#[derive(Default)]
+pub enum ForfeitureMode {
+    /// The forfeited allocations are returned to the owner
+    #[default]
+    Return,
+    /// The forfeited allocations are shared pro rata among the addresses that haven't exited
+    /// early nor forfeited their allocation. They're returned to the owner if there are no such
+    /// addresses left.
+    Redistribute,
+}
+
+/// Why an allocation was forfeited.
+#[cw_serde]
+#[derive(Eq)]
+pub enum ForfeitureReason {
+    /// The address was blacklisted
+    Blacklisted,
+    /// The allocation was left unclaimed after the campaign ended
+    Unclaimed,
+}
+
+/// How the compliance registry is used to check the receivers of claims.
+#[cw_serde]
+#[derive(Default)]
+pub enum ComplianceMode {
+    /// The compliance registry is not queried
+    #[default]
+    Disabled,
+    /// Only addresses explicitly allowed by the compliance registry can claim
+    AllowlistRequired,
+    /// All addresses can claim, except the ones denied by the compliance registry
+    DenylistOnly,
+}
+
+impl ComplianceMode {
+    /// Checks if an address with the given status in the compliance registry can claim
+    pub fn permits(&self, status: &ComplianceStatus) -> bool {
+        match self {
+            ComplianceMode::Disabled => true,
+            ComplianceMode::AllowlistRequired => *status == ComplianceStatus::Allowed,
+            ComplianceMode::DenylistOnly => *status != ComplianceStatus::Denied,
+        }
+    }
+}
+
+/// The query interface a compliance registry contract has to implement.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum ComplianceQueryMsg {
+    #[returns(IsAllowedResponse)]
+    /// Get the compliance status of an address
+    IsAllowed {
+        /// The address to check
+        address: String,
+    },
+}
+
+/// Response to the IsAllowed query of a compliance registry.
+#[cw_serde]
+pub struct IsAllowedResponse {
+    /// The compliance status of the address
+    pub status: ComplianceStatus,
+}
+
+/// The compliance status of an address in a compliance registry.
+#[cw_serde]
+pub enum ComplianceStatus {
+    /// The address passed the compliance checks
+    Allowed,
+    /// The address failed the compliance checks
+    Denied,
+    /// The address is not known to the registry
+    Unlisted,
+}
+
+/// Represents the parameters to create a campaign with.
+#[cw_serde]
+pub struct CampaignParams {
+    /// The campaign name
+    pub name: String,
+    /// The campaign description
+    pub description: String,
+    /// Campaign type. Value used by front ends.
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// The denom to be distributed as reward by the campaign
+    pub reward_denom: String,
+    /// The total amount of the reward asset that is intended to be allocated to the campaign
+    pub total_reward: Coin,
+    /// The ways the reward is distributed, which are defined by the [DistributionType].
+    /// The sum of the percentages must be 100.
+    pub distribution_type: Vec<DistributionType>,
+    /// The named schedules allocations can vest on instead of the distribution types above
+    #[serde(default)]
+    pub schedules: Vec<Schedule>,
+    /// The campaign start time (unix timestamp), in seconds
+    pub start_time: u64,
+    /// The campaign end timestamp (unix timestamp), in seconds
+    pub end_time: u64,
+    /// Whether the allocations have to be finalized with the FinalizeAllocations message before
+    /// the campaign can be claimed from
+    #[serde(default)]
+    pub require_finalized_allocations: bool,
+}
+
+impl CampaignParams {
+    /// Validates the campaign name and description
+    pub fn validate_campaign_name_description(&self) -> Result<(), ContractError> {
+        ensure!(
+            !self.name.is_empty(),
+            ContractError::InvalidCampaignParam {
+                param: "name".to_string(),
+                reason: "cannot be empty".to_string(),
+            }
+        );
+
+        ensure!(
+            self.name.len() <= MAX_NAME_LENGTH,
+            ContractError::InvalidCampaignParam {
+                param: "name".to_string(),
+                reason: format!("cannot be longer than {} characters", MAX_NAME_LENGTH),
+            }
+        );
+
+        ensure!(
+            !self.description.is_empty(),
+            ContractError::InvalidCampaignParam {
+                param: "description".to_string(),
+                reason: "cannot be empty".to_string(),
+            }
+        );
+
+        ensure!(
+            self.description.len() <= MAX_DESCRIPTION_LENGTH,
+            ContractError::InvalidCampaignParam {
+                param: "description".to_string(),
+                reason: format!(
+                    "cannot be longer than {} characters",
+                    MAX_DESCRIPTION_LENGTH
+                ),
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Validates the campaign type
+    pub fn validate_campaign_type(&self) -> Result<(), ContractError> {
+        ensure!(
+            !self.ty.is_empty(),
+            ContractError::InvalidCampaignParam {
+                param: "type".to_string(),
+                reason: "cannot be empty".to_string(),
+            }
+        );
+
+        ensure!(
+            self.ty.len() <= MAX_TYPE_LENGTH,
+            ContractError::InvalidCampaignParam {
+                param: "type".to_string(),
+                reason: format!("cannot be longer than {} characters", MAX_TYPE_LENGTH),
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Validates the start and end times of a campaign
+    pub fn validate_campaign_times(&self, current_time: Timestamp) -> Result<(), ContractError> {
+        ensure!(
+            self.start_time < self.end_time,
+            ContractError::InvalidCampaignParam {
+                param: "start_time".to_string(),
+                reason: "cannot be greater or equal than end_time".to_string(),
+            }
+        );
+        ensure!(
+            self.start_time >= current_time.seconds(),
+            ContractError::InvalidCampaignParam {
+                param: "start_time".to_string(),
+                reason: "cannot be less than the current time".to_string(),
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Ensures the distribution type parameters are correct, for the campaign and its schedules
+    pub fn validate_campaign_distribution(&self) -> Result<(), ContractError> {
+        self.validate_distribution(&self.distribution_type)?;
+
+        ensure!(
+            self.schedules.len() <= MAX_SCHEDULES,
+            ContractError::InvalidCampaignParam {
+                param: "schedules".to_string(),
+                reason: format!("cannot define more than {MAX_SCHEDULES} schedules"),
+            }
+        );
+
+        for (i, schedule) in self.schedules.iter().enumerate() {
+            ensure!(
+                !schedule.id.is_empty() && schedule.id.len() <= MAX_SCHEDULE_ID_LENGTH,
+                ContractError::InvalidCampaignParam {
+                    param: "schedules".to_string(),
+                    reason: format!(
+                        "schedule ids must be between 1 and {MAX_SCHEDULE_ID_LENGTH} characters"
+                    ),
+                }
+            );
+            ensure!(
+                !self.schedules[..i]
+                    .iter()
+                    .any(|other| other.id == schedule.id),
+                ContractError::InvalidCampaignParam {
+                    param: "schedules".to_string(),
+                    reason: format!("duplicated schedule id {}", schedule.id),
+                }
+            );
+
+            self.validate_distribution(&schedule.distribution_type)?;
+        }
+
+        Ok(())
+    }
+
+    /// Ensures the parameters of a list of distribution types are correct
+    fn validate_distribution(
+        &self,
+        distribution_type: &[DistributionType],
+    ) -> Result<(), ContractError> {
+        let mut total_percentage = Decimal::zero();
+
+        ensure!(
+            !distribution_type.is_empty() && distribution_type.len() <= 2,
+            ContractError::InvalidCampaignParam {
+                param: "distribution_type".to_string(),
+                reason: "invalid number of distribution types, should be at least 1, maximum 2"
+                    .to_string(),
+            }
+        );
+
+        for dist in distribution_type.iter() {
+            let percentage = dist.percentage();
+
+            ensure!(
+                percentage != Decimal::zero(),
+                ContractError::ZeroDistributionPercentage
+            );
+
+            total_percentage = total_percentage.checked_add(percentage)?;
+
+            let (start_time, end_time, cliff_duration) = match dist {
+                DistributionType::LinearVesting {
+                    start_time,
+                    end_time,
+                    cliff_duration,
+                    ..
+                } => (start_time, Some(end_time), cliff_duration),
+                DistributionType::LumpSum { start_time, .. } => (start_time, None, &None),
+                // relative distribution types start once anchored, and can end after the campaign
+                DistributionType::RelativeLinearVesting {
+                    duration,
+                    cliff_duration,
+                    ..
+                } => {
+                    Self::validate_relative_distribution(*duration, cliff_duration)?;
+                    continue;
+                }
+                DistributionType::RelativeLumpSum { .. } => continue,
+                DistributionType::Milestone {
+                    id,
+                    vesting_duration,
+                    ..
+                } => {
+                    Self::validate_milestone(id, vesting_duration)?;
+                    ensure!(
+                        distribution_type
+                            .iter()
+                            .filter(|other| other.milestone_id() == Some(id.as_str()))
+                            .count()
+                            == 1,
+                        ContractError::InvalidCampaignParam {
+                            param: "milestone".to_string(),
+                            reason: format!("duplicated milestone id {id}"),
+                        }
+                    );
+                    continue;
+                }
+            };
+
+            ensure!(
+                *start_time >= self.start_time,
+                ContractError::InvalidStartDistributionTime {
+                    start_time: *start_time,
+                    campaign_start_time: self.start_time,
+                }
+            );
+
+            // validate the end time. Applies for the linear vesting distribution type only
+            if let Some(end_time) = end_time {
+                ensure!(
+                    end_time > start_time,
+                    ContractError::InvalidDistributionTimes {
+                        start_time: *start_time,
+                        end_time: *end_time,
+                    }
+                );
+
+                ensure!(
+                    *end_time <= self.end_time,
+                    ContractError::InvalidEndDistributionTime {
+                        end_time: *end_time,
+                        campaign_end_time: self.end_time,
+                    }
+                );
+            }
+
+            // validate the cliff duration
+            if let Some(cliff_duration) = cliff_duration {
+                ensure!(
+                    *cliff_duration > 0u64,
+                    ContractError::InvalidCampaignParam {
+                        param: "cliff_duration".to_string(),
+                        reason: "cannot be zero".to_string(),
+                    }
+                );
+
+                ensure!(
+                    // it is safe to unwrap because this cliff validation only applies for linear vesting,
+                    // which contains an end_time
+                    *cliff_duration < end_time.unwrap() - start_time,
+                    ContractError::InvalidCampaignParam {
+                        param: "cliff_duration".to_string(),
+                        reason: "cannot be greater or equal than the distribution duration"
+                            .to_string(),
+                    }
+                );
+            }
+
+            // validate the early exit penalty
+            if let Some(penalty) = dist.early_exit_penalty() {
+                ensure!(
+                    penalty.percentage <= Decimal::one(),
+                    ContractError::InvalidCampaignParam {
+                        param: "early_exit_penalty".to_string(),
+                        reason: "percentage cannot be greater than 100%".to_string(),
+                    }
+                );
+            }
+        }
+
+        ensure!(
+            total_percentage == Decimal::percent(100),
+            ContractError::InvalidDistributionPercentage {
+                expected: Decimal::percent(100),
+                actual: total_percentage,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Ensures the duration and cliff of a relative linear vesting distribution are correct
+    fn validate_relative_distribution(
+        duration: u64,
+        cliff_duration: &Option<u64>,
+    ) -> Result<(), ContractError> {
+        ensure!(
+            duration > 0u64,
+            ContractError::InvalidCampaignParam {
+                param: "duration".to_string(),
+                reason: "cannot be zero".to_string(),
+            }
+        );
+
+        if let Some(cliff_duration) = cliff_duration {
+            ensure!(
+                *cliff_duration > 0u64 && *cliff_duration < duration,
+                ContractError::InvalidCampaignParam {
+                    param: "cliff_duration".to_string(),
+                    reason: "must be greater than zero and lower than the distribution duration"
+                        .to_string(),
+                }
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Ensures the id and vesting duration of a milestone distribution are correct
+    fn validate_milestone(id: &str, vesting_duration: &Option<u64>) -> Result<(), ContractError> {
+        ensure!(
+            !id.is_empty() && id.len() <= MAX_MILESTONE_ID_LENGTH,
+            ContractError::InvalidCampaignParam {
+                param: "milestone".to_string(),
+                reason: format!(
+                    "milestone ids must be between 1 and {MAX_MILESTONE_ID_LENGTH} characters"
+                ),
+            }
+        );
+
+        ensure!(
+            *vesting_duration != Some(0),
+            ContractError::InvalidCampaignParam {
+                param: "vesting_duration".to_string(),
+                reason: "cannot be zero".to_string(),
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Validates the total reward amount and denom
+    pub fn validate_rewards(&self) -> Result<(), ContractError> {
+        ensure!(
+            self.total_reward.amount > Uint128::zero(),
+            ContractError::InvalidCampaignParam {
+                param: "total_reward".to_string(),
+                reason: "cannot be zero".to_string()
+            }
+        );
+
+        ensure!(
+            self.total_reward.denom == self.reward_denom,
+            ContractError::InvalidCampaignParam {
+                param: "reward_denom".to_string(),
+                reason: "reward denom mismatch".to_string()
+            }
+        );
+
+        Ok(())
+    }
+}
+
+#[cw_serde]
+pub enum DistributionType {
+    /// The distribution is done in a linear vesting schedule
+    LinearVesting {
+        /// The percentage of the total reward to be distributed with a linear vesting schedule
+        percentage: Decimal,
+        /// The unix timestamp when this distribution type starts, in seconds
+        start_time: u64,
+        /// The unix timestamp when this distribution type ends, in seconds
+        end_time: u64,
+        /// The duration of the cliff, in seconds
+        cliff_duration: Option<u64>,
+        /// The penalty applied to the unvested amount when exiting early. If not set, addresses
+        /// can't exit this distribution early.
+        early_exit_penalty: Option<EarlyExitPenalty>,
+    },
+    /// The distribution is done in a single lump sum, i.e. no vesting period
+    LumpSum {
+        percentage: Decimal,
+        /// The unix timestamp when this distribution type starts, in seconds
+        start_time: u64,
+    },
+    /// The distribution is done in a linear vesting schedule that starts relative to the anchor
+    /// time of each address
+    RelativeLinearVesting {
+        /// The percentage of the total reward to be distributed with a linear vesting schedule
+        percentage: Decimal,
+        /// The time of each address the vesting schedule is relative to
+        anchor: VestingAnchor,
+        /// The time between the anchor and the start of the vesting schedule, in seconds
+        start_offset: u64,
+        /// The duration of the vesting schedule, in seconds
+        duration: u64,
+        /// The duration of the cliff, in seconds
+        cliff_duration: Option<u64>,
+    },
+    /// The distribution is done in a single lump sum released relative to the anchor time of each
+    /// address
+    RelativeLumpSum {
+        percentage: Decimal,
+        /// The time of each address the lump sum is relative to
+        anchor: VestingAnchor,
+        /// The time between the anchor and the release of the lump sum, in seconds
+        start_offset: u64,
+    },
+    /// The distribution is locked until the milestone with the given id is triggered, e.g. on a
+    /// mainnet launch or an exchange listing. Once triggered, it's released in a lump sum or
+    /// vested linearly from the trigger time.
+    Milestone {
+        /// The id of the milestone, shared by the milestone distributions unlocked together
+        id: String,
+        percentage: Decimal,
+        /// The duration of the linear vesting starting at the trigger time, in seconds. If not
+        /// set, the distribution is released in a lump sum when triggered.
+        vesting_duration: Option<u64>,
+    },
+}
+
+/// The time of an address relative distribution types are anchored to.
+#[cw_serde]
+pub enum VestingAnchor {
+    /// The time the allocation of the address was added, or the start time of the campaign if it
+    /// was added before the campaign started
+    Registration,
+    /// The time of the first claim of the address
+    FirstClaim,
+}
+
+impl DistributionType {
+    /// Checks if the distribution type has started. Relative distribution types only start once
+    /// they're anchored, see [DistributionType::anchored], and milestones once they're triggered,
+    /// see [DistributionType::triggered].
+    pub fn has_started(&self, current_time: &Timestamp) -> bool {
+        let start_time = match self {
+            DistributionType::LinearVesting { start_time, .. } => start_time,
+            DistributionType::LumpSum { start_time, .. } => start_time,
+            DistributionType::RelativeLinearVesting { .. }
+            | DistributionType::RelativeLumpSum { .. }
+            | DistributionType::Milestone { .. } => return false,
+        };
+
+        current_time.seconds() >= *start_time
+    }
+
+    /// Returns the percentage of the total reward distributed by the distribution type
+    pub fn percentage(&self) -> Decimal {
+        match self {
+            DistributionType::LinearVesting { percentage, .. }
+            | DistributionType::LumpSum { percentage, .. }
+            | DistributionType::RelativeLinearVesting { percentage, .. }
+            | DistributionType::RelativeLumpSum { percentage, .. }
+            | DistributionType::Milestone { percentage, .. } => *percentage,
+        }
+    }
+
+    /// Returns the anchor of the distribution type, if it's relative
+    pub fn anchor(&self) -> Option<&VestingAnchor> {
+        match self {
+            DistributionType::RelativeLinearVesting { anchor, .. }
+            | DistributionType::RelativeLumpSum { anchor, .. } => Some(anchor),
+            DistributionType::LinearVesting { .. }
+            | DistributionType::LumpSum { .. }
+            | DistributionType::Milestone { .. } => None,
+        }
+    }
+
+    /// Returns the distribution type with absolute times, anchoring relative distribution types
+    /// at the given registration and first claim times. Absolute distribution types are returned
+    /// as they are.
+    pub fn anchored(&self, registered_at: u64, first_claimed_at: u64) -> DistributionType {
+        let anchor_time = |anchor: &VestingAnchor| match anchor {
+            VestingAnchor::Registration => registered_at,
+            VestingAnchor::FirstClaim => first_claimed_at,
+        };
+
+        match self {
+            DistributionType::RelativeLinearVesting {
+                percentage,
+                anchor,
+                start_offset,
+                duration,
+                cliff_duration,
+            } => {
+                let start_time = anchor_time(anchor).saturating_add(*start_offset);
+                DistributionType::LinearVesting {
+                    percentage: *percentage,
+                    start_time,
+                    end_time: start_time.saturating_add(*duration),
+                    cliff_duration: *cliff_duration,
+                    early_exit_penalty: None,
+                }
+            }
+            DistributionType::RelativeLumpSum {
+                percentage,
+                anchor,
+                start_offset,
+            } => DistributionType::LumpSum {
+                percentage: *percentage,
+                start_time: anchor_time(anchor).saturating_add(*start_offset),
+            },
+            DistributionType::LinearVesting { .. }
+            | DistributionType::LumpSum { .. }
+            | DistributionType::Milestone { .. } => self.clone(),
+        }
+    }
+
+    /// Returns the id of the milestone, if the distribution type is a milestone
+    pub fn milestone_id(&self) -> Option<&str> {
+        match self {
+            DistributionType::Milestone { id, .. } => Some(id),
+            _ => None,
+        }
+    }
+
+    /// Returns the distribution type with absolute times if it's a milestone that has been
+    /// triggered. Otherwise it's returned as it is, milestones staying locked until triggered.
+    pub fn triggered(&self, milestones: &[TriggeredMilestone]) -> DistributionType {
+        let DistributionType::Milestone {
+            id,
+            percentage,
+            vesting_duration,
+        } = self
+        else {
+            return self.clone();
+        };
+
+        let Some(milestone) = milestones.iter().find(|milestone| milestone.id == *id) else {
+            return self.clone();
+        };
+
+        match vesting_duration {
+            Some(vesting_duration) => DistributionType::LinearVesting {
+                percentage: *percentage,
+                start_time: milestone.time,
+                end_time: milestone.time.saturating_add(*vesting_duration),
+                cliff_duration: None,
+                early_exit_penalty: None,
+            },
+            None => DistributionType::LumpSum {
+                percentage: *percentage,
+                start_time: milestone.time,
+            },
+        }
+    }
+
+    /// Returns the early exit penalty of the distribution, if any
+    pub fn early_exit_penalty(&self) -> Option<&EarlyExitPenalty> {
+        match self {
+            DistributionType::LinearVesting {
+                early_exit_penalty, ..
+            } => early_exit_penalty.as_ref(),
+            DistributionType::LumpSum { .. }
+            | DistributionType::RelativeLinearVesting { .. }
+            | DistributionType::RelativeLumpSum { .. }
+            | DistributionType::Milestone { .. } => None,
+        }
+    }
+}
+
+/// The penalty applied to the unvested amount of a linear vesting distribution when exiting it
+/// early.
+#[cw_serde]
+pub struct EarlyExitPenalty {
+    /// The share of the unvested amount forfeited when exiting early
+    pub percentage: Decimal,
+    /// Where the forfeited amount goes
+    pub destination: PenaltyDestination,
+}
+
+/// Where the amount forfeited by early exits goes.
+#[cw_serde]
+pub enum PenaltyDestination {
+    /// The forfeited amount is sent to the treasury address
+    Treasury { address: String },
+    /// The forfeited amount is shared pro rata among the addresses that haven't exited early nor
+    /// forfeited their allocation
+    Redistribute {},
+}
+
+/// Represents an individual claim made by an address.
+#[cw_serde]
+pub struct ClaimRecord {
+    /// The sequence number of the claim, unique across all addresses
+    pub sequence: u64,
+    /// The time at which the claim was made (unix timestamp), in seconds
+    pub timestamp: u64,
+    /// The total amount claimed
+    pub amount: Coin,
+    /// A vector with a tuple with (distribution slot, amount) breaking down the claimed amount
+    pub slots: Vec<(u64, Uint128)>,
+    /// The address the claimed tokens were sent to
+    pub destination: String,
+    /// The address that initiated the claim
+    pub sender: String,
+}
+
+/// Represents an entry in the administrative audit log.
+#[cw_serde]
+pub struct AuditEntry {
+    /// The sequence number of the entry, starting at 1
+    pub sequence: u64,
+    /// The block height at which the action was performed
+    pub height: u64,
+    /// The time at which the action was performed (unix timestamp), in seconds
+    pub time: u64,
+    /// The address that performed the action
+    pub sender: String,
+    /// The action that was performed
+    pub action: AuditAction,
+    /// The address affected by the action, if any
+    pub subject: Option<String>,
+}
+
+/// The administrative actions recorded in the audit log.
+#[cw_serde]
+pub enum AuditAction {
+    /// A campaign was created
+    CreateCampaign,
+    /// The campaign was closed
+    CloseCampaign,
+    /// A batch of allocations was uploaded
+    AddAllocations {
+        /// The number of allocations in the batch
+        count: u64,
+    },
+    /// A batch of claims distributed outside of the contract was seeded
+    SeedClaims {
+        /// The number of addresses in the batch
+        count: u64,
+    },
+    /// A batch of state entries exported from another contract was imported
+    ImportState {
+        /// The number of entries in the batch
+        count: u64,
+    },
+    /// The subject address was replaced in the allocation list
+    ReplaceAddress {
+        /// The address that replaced the subject
+        new_address: String,
+    },
+    /// The subject address was removed from the allocation list
+    RemoveAddress,
+    /// The subject address was blacklisted
+    BlacklistAddress,
+    /// The subject address was removed from the blacklist
+    UnblacklistAddress,
+    /// The subject address was authorized to perform admin actions
+    AuthorizeWallet,
+    /// The subject address was unauthorized to perform admin actions
+    UnauthorizeWallet,
+    /// The subject address was made the owner of the contract
+    ResetOwner,
+    /// The contract was paused
+    Pause,
+    /// The contract was resumed
+    Resume,
+    /// The subject IBC channel was allowed, updated or disallowed to send claim packets
+    ManageIbcChannel {
+        /// The configuration of the channel, if allowed
+        config: Option<IbcChannelConfig>,
+    },
+    /// The compliance settings of the campaign were updated
+    UpdateCompliance {
+        /// The compliance registry contract
+        compliance_contract: Option<String>,
+        /// The compliance mode
+        mode: ComplianceMode,
+    },
+    /// The share of the auto-claimed rewards paid to crank callers was updated
+    UpdateCrankTip {
+        /// The new tip
+        tip: Decimal,
+    },
+    /// The crank tip pool was funded
+    FundCrankTips {
+        /// The amount added to the pool
+        amount: Coin,
+    },
+    /// The forfeiture mode of the campaign was updated
+    UpdateForfeitureMode {
+        /// The new forfeiture mode
+        mode: ForfeitureMode,
+    },
+    /// The allocation of the subject address was forfeited
+    ForfeitAllocation {
+        /// Why the allocation was forfeited
+        reason: ForfeitureReason,
+        /// The amount forfeited
+        amount: Uint128,
+    },
+    /// A milestone was triggered
+    TriggerMilestone {
+        /// The id of the milestone
+        id: String,
+    },
+    /// A batch of the data migration was run
+    ContinueMigration {
+        /// The number of entries processed
+        processed: u64,
+    },
+    /// The uploaded allocations were finalized
+    FinalizeAllocations {
+        /// The number of uploaded allocations
+        count: u64,
+    },
+}
+
+impl AuditAction {
+    /// Returns the name of the action, used to filter the audit log by action type
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AuditAction::CreateCampaign => "create_campaign",
+            AuditAction::CloseCampaign => "close_campaign",
+            AuditAction::AddAllocations { .. } => "add_allocations",
+            AuditAction::SeedClaims { .. } => "seed_claims",
+            AuditAction::ImportState { .. } => "import_state",
+            AuditAction::ReplaceAddress { .. } => "replace_address",
+            AuditAction::RemoveAddress => "remove_address",
+            AuditAction::BlacklistAddress => "blacklist_address",
+            AuditAction::UnblacklistAddress => "unblacklist_address",
+            AuditAction::AuthorizeWallet => "authorize_wallet",
+            AuditAction::UnauthorizeWallet => "unauthorize_wallet",
+            AuditAction::ResetOwner => "reset_owner",
+            AuditAction::Pause => "pause",
+            AuditAction::Resume => "resume",
+            AuditAction::ManageIbcChannel { .. } => "manage_ibc_channel",
+            AuditAction::UpdateCompliance { .. } => "update_compliance",
+            AuditAction::UpdateCrankTip { .. } => "update_crank_tip",
+            AuditAction::FundCrankTips { .. } => "fund_crank_tips",
+            AuditAction::UpdateForfeitureMode { .. } => "update_forfeiture_mode",
+            AuditAction::ForfeitAllocation { .. } => "forfeit_allocation",
+            AuditAction::TriggerMilestone { .. } => "trigger_milestone",
+            AuditAction::ContinueMigration { .. } => "continue_migration",
+            AuditAction::FinalizeAllocations { .. } => "finalize_allocations",
+        }
+    }
+}
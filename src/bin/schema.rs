@@ -1,11 +1,12 @@
 use cosmwasm_schema::write_api;
 
-use mantra_claimdrop_std::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use mantra_claimdrop_std::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, SudoMsg};
 
 fn main() {
     write_api! {
         instantiate: InstantiateMsg,
         execute: ExecuteMsg,
         query: QueryMsg,
+        sudo: SudoMsg,
     }
 }
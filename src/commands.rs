@@ -1,22 +1,58 @@
 use std::collections::HashMap;
 
-use cosmwasm_std::{ensure, BankMsg, Coin, DepsMut, Env, MessageInfo, Response, Uint128};
+use cosmwasm_std::{
+    coin, coins, ensure, Addr, BankMsg, Binary, Coin, Decimal, Deps, DepsMut, Env, Event,
+    HexBinary, MessageInfo, Order, Response, StdResult, Storage, SubMsg, Uint128,
+};
+use cw_storage_plus::Bound;
+use cw_utils::Expiration;
 
 use crate::helpers::{self, validate_raw_address};
 use crate::state::{
-    assert_authorized, get_allocation, get_claims_for_address, get_total_claims_amount_for_address,
-    is_authorized, is_blacklisted, Claim, DistributionSlot, ALLOCATIONS, AUTHORIZED_WALLETS,
-    BLACKLIST, CAMPAIGN, CLAIMS,
+    anchor_first_claim, assert_authorized, get_allocation, get_claims_for_address,
+    get_distribution_type, get_effective_allocation, get_total_claims_amount_for_address,
+    get_vesting_time, is_authorized, is_blacklisted, is_paused, lift_blacklist,
+    move_claim_approvals, move_claim_history, move_claims, move_pending_ibc_transfers,
+    record_audit_entry, record_claim, redistribute, retire_shares, save_claims, AllocationChecksum,
+    Claim, DistributionSlot, EarlyExit, Forfeiture, PendingIbcTransfer, VestingAnchors,
+    ALLOCATIONS, ALLOCATION_CHECKSUM, ALLOCATION_SCHEDULES, AUTHORIZED_WALLETS, AUTO_CLAIM_CURSOR,
+    AUTO_CLAIM_SUBSCRIPTIONS, BLACKLIST, CAMPAIGN, CLAIM_APPROVALS, CLAIM_HISTORY_SEQUENCE,
+    CRANK_TIP, CRANK_TIP_POOL, EARLY_EXITS, FORFEITURES, IBC_CHANNELS, PAUSED, TOTAL_ALLOCATED,
+    VESTING_ANCHORS, VESTING_FREEZES,
 };
+use crate::{ibc, migrations};
 use mantra_claimdrop_std::error::ContractError;
-use mantra_claimdrop_std::msg::{Campaign, CampaignAction, CampaignParams, DistributionType};
+use mantra_claimdrop_std::msg::{
+    AllocationRow, AuditAction, BlacklistEntry, BlacklistReason, Campaign, CampaignAction,
+    CampaignParams, ClaimApproval, ClaimFailureReason, ComplianceMode, DistributionType,
+    EarlyExitPenalty, ForfeitureMode, ForfeitureReason, IbcChannelConfig, IbcDestination,
+    PenaltyDestination, StateEntry, TriggeredMilestone,
+};
 
 /// Maximum number of allocations that can be added in a single batch
 pub const MAX_ALLOCATION_BATCH_SIZE: usize = 3000;
 
+/// Maximum number of allocations that can be forfeited in a single batch
+pub const MAX_FORFEITURE_BATCH_SIZE: usize = 1000;
+
 /// Maximum number of authorized wallets that can be managed in a single batch operation
 pub const MAX_AUTHORIZED_WALLETS_BATCH_SIZE: usize = 1000;
 
+/// Maximum number of addresses that can be blacklisted or unblacklisted in a single batch operation
+pub const MAX_BLACKLIST_BATCH_SIZE: usize = 1000;
+
+/// The sender recorded for the actions performed through sudo messages, e.g. in the audit log
+pub const SUDO_SENDER: &str = "governance";
+
+/// Default number of addresses processed by a crank
+pub const DEFAULT_CRANK_LIMIT: u32 = 30;
+
+/// Maximum number of addresses processed by a crank
+pub const MAX_CRANK_LIMIT: u32 = 100;
+
+/// Maximum share of the auto-claimed rewards that can be paid to crank callers
+pub const MAX_CRANK_TIP: Decimal = Decimal::percent(5);
+
 /// Manages a campaign
 pub(crate) fn manage_campaign(
     deps: DepsMut,
@@ -30,7 +66,18 @@ pub(crate) fn manage_campaign(
         CampaignAction::CreateCampaign { params } => create_campaign(deps, env, info, *params),
         CampaignAction::CloseCampaign {} => {
             cw_utils::nonpayable(&info)?;
-            close_campaign(deps, env)
+            close_campaign(deps, env, &info.sender, None)
+        }
+        CampaignAction::UpdateCompliance {
+            compliance_contract,
+            mode,
+        } => {
+            cw_utils::nonpayable(&info)?;
+            update_compliance(deps, env, info, compliance_contract, mode)
+        }
+        CampaignAction::UpdateForfeitureMode { mode } => {
+            cw_utils::nonpayable(&info)?;
+            update_forfeiture_mode(deps, env, info, mode)
         }
     }
 }
@@ -54,18 +101,145 @@ fn create_campaign(
 
     helpers::validate_campaign_params(env.block.time, &campaign_params)?;
 
+    for distribution in campaign_params.distribution_type.iter().chain(
+        campaign_params
+            .schedules
+            .iter()
+            .flat_map(|schedule| schedule.distribution_type.iter()),
+    ) {
+        if let Some(EarlyExitPenalty {
+            destination: PenaltyDestination::Treasury { address },
+            ..
+        }) = distribution.early_exit_penalty()
+        {
+            deps.api.addr_validate(address)?;
+        }
+    }
+
     let campaign = Campaign::from_params(campaign_params);
     CAMPAIGN.save(deps.storage, &campaign)?;
 
+    record_audit_entry(
+        deps.storage,
+        &env,
+        &info.sender,
+        AuditAction::CreateCampaign,
+        None,
+    )?;
+
     Ok(Response::default().add_attributes(vec![
         ("action", "create_campaign".to_string()),
         ("campaign", campaign.to_string()),
     ]))
 }
 
-/// Closes the existing airdrop campaign. Only the owner can end the campaign.
-/// The remaining funds in the campaign are refunded to the owner.
-fn close_campaign(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+/// Updates the compliance registry contract and the way it's used to check the receivers of
+/// claims. Can be done at any time while the campaign is not closed.
+fn update_compliance(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    compliance_contract: Option<String>,
+    mode: ComplianceMode,
+) -> Result<Response, ContractError> {
+    let mut campaign = CAMPAIGN
+        .may_load(deps.storage)?
+        .ok_or(ContractError::CampaignError {
+            reason: "there's not an active campaign".to_string(),
+        })?;
+
+    ensure!(
+        campaign.closed.is_none(),
+        ContractError::CampaignError {
+            reason: "campaign has already been closed".to_string()
+        }
+    );
+
+    if let Some(compliance_contract) = compliance_contract {
+        campaign.compliance_contract =
+            Some(deps.api.addr_validate(&compliance_contract)?.to_string());
+    }
+
+    ensure!(
+        mode == ComplianceMode::Disabled || campaign.compliance_contract.is_some(),
+        ContractError::InvalidInput {
+            reason: "a compliance contract is required to enable compliance checks".to_string(),
+        }
+    );
+
+    campaign.compliance_mode = mode;
+    CAMPAIGN.save(deps.storage, &campaign)?;
+
+    record_audit_entry(
+        deps.storage,
+        &env,
+        &info.sender,
+        AuditAction::UpdateCompliance {
+            compliance_contract: campaign.compliance_contract.clone(),
+            mode: campaign.compliance_mode.clone(),
+        },
+        None,
+    )?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "update_compliance".to_string()),
+        (
+            "compliance_contract",
+            campaign.compliance_contract.unwrap_or_default(),
+        ),
+        ("compliance_mode", format!("{:?}", campaign.compliance_mode)),
+    ]))
+}
+
+/// Updates what happens to the allocations forfeited by addresses. Can be done at any time while
+/// the campaign is not closed, and applies to the allocations forfeited from then on.
+fn update_forfeiture_mode(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    mode: ForfeitureMode,
+) -> Result<Response, ContractError> {
+    let mut campaign = CAMPAIGN
+        .may_load(deps.storage)?
+        .ok_or(ContractError::CampaignError {
+            reason: "there's not an active campaign".to_string(),
+        })?;
+
+    ensure!(
+        campaign.closed.is_none(),
+        ContractError::CampaignError {
+            reason: "campaign has already been closed".to_string()
+        }
+    );
+
+    campaign.forfeiture_mode = mode;
+    CAMPAIGN.save(deps.storage, &campaign)?;
+
+    record_audit_entry(
+        deps.storage,
+        &env,
+        &info.sender,
+        AuditAction::UpdateForfeitureMode {
+            mode: campaign.forfeiture_mode.clone(),
+        },
+        None,
+    )?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "update_forfeiture_mode".to_string()),
+        ("forfeiture_mode", format!("{:?}", campaign.forfeiture_mode)),
+    ]))
+}
+
+/// Closes the existing airdrop campaign. Only the owner can end the campaign, unless it's closed
+/// through a sudo message. The remaining funds in the campaign are refunded to the given recipient,
+/// or the owner if not set.
+pub(crate) fn close_campaign(
+    deps: DepsMut,
+    env: Env,
+    sender: &Addr,
+    refund_recipient: Option<Addr>,
+) -> Result<Response, ContractError> {
     let mut campaign = CAMPAIGN
         .may_load(deps.storage)?
         .ok_or(ContractError::CampaignError {
@@ -81,15 +255,18 @@ fn close_campaign(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
 
     let refund: Coin = deps
         .querier
-        .query_balance(env.contract.address, &campaign.reward_denom)?;
+        .query_balance(env.contract.address.clone(), &campaign.reward_denom)?;
 
     let mut messages = vec![];
 
     if !refund.amount.is_zero() {
-        let owner = cw_ownable::get_ownership(deps.storage)?.owner.unwrap();
+        let refund_recipient = match refund_recipient {
+            Some(refund_recipient) => refund_recipient,
+            None => cw_ownable::get_ownership(deps.storage)?.owner.unwrap(),
+        };
 
         messages.push(BankMsg::Send {
-            to_address: owner.to_string(),
+            to_address: refund_recipient.to_string(),
             amount: vec![refund.clone()],
         });
     }
@@ -97,6 +274,10 @@ fn close_campaign(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
     campaign.closed = Some(env.block.time.seconds());
 
     CAMPAIGN.save(deps.storage, &campaign)?;
+    // the crank tip pool was refunded along with the rest of the balance
+    CRANK_TIP_POOL.remove(deps.storage);
+
+    record_audit_entry(deps.storage, &env, sender, AuditAction::CloseCampaign, None)?;
 
     Ok(Response::default()
         .add_messages(messages)
@@ -107,89 +288,223 @@ fn close_campaign(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
         ]))
 }
 
-pub(crate) fn claim(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
+/// The outcome of the checks performed before a claim, shared by the Claim message, the
+/// SimulateClaim query and the claims received over IBC.
+pub(crate) struct ClaimPlan {
+    /// The campaign being claimed from
+    pub campaign: Campaign,
+    /// The address whose allocation is claimed
+    pub address: String,
+    /// The total allocation of the receiver
+    pub total_user_allocation: Uint128,
+    /// The amount to be claimed
+    pub amount: Coin,
+    /// The amount to be recorded per distribution slot
+    pub claims_to_record: HashMap<DistributionSlot, Claim>,
+    /// The operator claiming on behalf of the address through a claim approval, if any
+    pub approved_operator: Option<Addr>,
+}
+
+/// A claim recorded by [apply_claim].
+pub(crate) struct AppliedClaim {
+    /// The amount claimed
+    pub amount: Coin,
+    /// The operator that claimed on behalf of the address through a claim approval, if any
+    pub approved_operator: Option<Addr>,
+    /// Whether the claim anchored the distribution types relative to the first claim
+    pub anchored_first_claim: bool,
+}
+
+/// Runs all the checks of a claim without mutating state. Returns the [ClaimPlan] if the claim
+/// would succeed, or the reason why it would fail otherwise.
+pub(crate) fn prepare_claim(
+    deps: Deps,
+    env: &Env,
+    sender: &Addr,
     receiver: Option<String>,
     amount: Option<Uint128>,
-) -> Result<Response, ContractError> {
-    let mut campaign = CAMPAIGN
-        .may_load(deps.storage)?
-        .ok_or(ContractError::CampaignError {
-            reason: "there's not an active campaign".to_string(),
-        })?;
+) -> Result<Result<ClaimPlan, ClaimFailureReason>, ContractError> {
+    let campaign = match claimable_campaign(deps, env)? {
+        Ok(campaign) => campaign,
+        Err(reason) => return Ok(Err(reason)),
+    };
 
-    ensure!(
-        campaign.has_started(&env.block.time),
-        ContractError::CampaignError {
-            reason: "not started".to_string()
+    let receiver = receiver
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?
+        .unwrap_or_else(|| sender.clone());
+
+    // Check if the caller is authorized to claim:
+    // Owner, authorized wallet, the wallet with the allocation OR an operator approved by it can claim
+    let is_authorized_user = is_authorized(deps, sender)?;
+
+    let approval = if is_authorized_user || *sender == receiver {
+        None
+    } else {
+        match active_claim_approval(deps, env, &receiver, sender)? {
+            Some(approval) => Some((sender.clone(), approval)),
+            None => return Ok(Err(ClaimFailureReason::Unauthorized)),
         }
-    );
+    };
 
-    ensure!(
-        campaign.closed.is_none(),
-        ContractError::CampaignError {
-            reason: "has been closed, cannot claim".to_string()
+    plan_approved_claim(deps, env, campaign, receiver.as_str(), approval, amount)
+}
+
+/// Loads the claim approval granted by an owner to an operator, if it exists and hasn't expired.
+pub(crate) fn active_claim_approval(
+    deps: Deps,
+    env: &Env,
+    owner: &Addr,
+    operator: &Addr,
+) -> Result<Option<ClaimApproval>, ContractError> {
+    Ok(CLAIM_APPROVALS
+        .may_load(deps.storage, (owner.as_str(), operator.as_str()))?
+        .filter(|approval| {
+            !approval
+                .expires
+                .is_some_and(|expires| expires.is_expired(&env.block))
+        }))
+}
+
+/// Plans a claim of the allocation of an address, made by an operator through a claim approval if
+/// given. Operators with a limited approval claim up to the approved amount.
+pub(crate) fn plan_approved_claim(
+    deps: Deps,
+    env: &Env,
+    campaign: Campaign,
+    address: &str,
+    approval: Option<(Addr, ClaimApproval)>,
+    amount: Option<Uint128>,
+) -> Result<Result<ClaimPlan, ClaimFailureReason>, ContractError> {
+    let Some((operator, max_amount)) = approval
+        .as_ref()
+        .and_then(|(operator, approval)| approval.max_amount.map(|max| (operator.clone(), max)))
+    else {
+        return Ok(plan_claim(deps, env, campaign, address, amount)?
+            .map(|plan| with_operator(plan, approval.map(|(operator, _)| operator))));
+    };
+
+    if max_amount.is_zero() {
+        return Ok(Err(ClaimFailureReason::InvalidAmount {
+            reason: "the approved amount has already been claimed".to_string(),
+        }));
+    }
+
+    if let Some(requested_amount) = amount {
+        if requested_amount > max_amount {
+            return Ok(Err(ClaimFailureReason::InvalidAmount {
+                reason: format!(
+                    "requested amount {requested_amount} exceeds approved amount {max_amount}"
+                ),
+            }));
         }
-    );
+    }
+
+    let plan = match plan_claim(deps, env, campaign.clone(), address, amount)? {
+        Ok(plan) if plan.amount.amount > max_amount => {
+            plan_claim(deps, env, campaign, address, Some(max_amount))?
+        }
+        plan => plan,
+    };
+
+    Ok(plan.map(|plan| with_operator(plan, Some(operator))))
+}
+
+/// Sets the operator claiming through a claim approval on a plan.
+fn with_operator(plan: ClaimPlan, approved_operator: Option<Addr>) -> ClaimPlan {
+    ClaimPlan {
+        approved_operator,
+        ..plan
+    }
+}
+
+/// Loads the campaign if it can be claimed from. Returns the reason why it can't otherwise.
+pub(crate) fn claimable_campaign(
+    deps: Deps,
+    env: &Env,
+) -> Result<Result<Campaign, ClaimFailureReason>, ContractError> {
+    if is_paused(deps.storage)? {
+        return Ok(Err(ClaimFailureReason::Paused));
+    }
+
+    let Some(campaign) = CAMPAIGN.may_load(deps.storage)? else {
+        return Ok(Err(ClaimFailureReason::NoActiveCampaign));
+    };
+
+    if !campaign.has_started(&env.block.time) {
+        return Ok(Err(ClaimFailureReason::NotStarted));
+    }
+
+    if campaign.closed.is_some() {
+        return Ok(Err(ClaimFailureReason::Closed));
+    }
+
+    if campaign.require_finalized_allocations
+        && AllocationChecksum::load(deps.storage)?
+            .finalized_at
+            .is_none()
+    {
+        return Ok(Err(ClaimFailureReason::AllocationsNotFinalized));
+    }
 
     // Note: Campaign end_time is intentionally not checked here.
     // Users should be able to claim their allocated tokens even after the campaign end_time has passed,
     // as long as the campaign has not been manually closed by the owner.
 
-    let receiver = receiver
-        .map(|addr| deps.api.addr_validate(&addr))
-        .transpose()?
-        .unwrap_or_else(|| info.sender.clone());
-
-    // Check if the caller is authorized to claim:
-    // Owner, authorized wallet, OR the wallet with the allocation can claim
-    let is_authorized_user = is_authorized(deps.as_ref(), &info.sender)?;
+    Ok(Ok(campaign))
+}
 
-    ensure!(
-        is_authorized_user || info.sender == receiver,
-        ContractError::Unauthorized
-    );
+/// Runs the checks of a claim that depend on the address whose allocation is claimed, once the
+/// claim has been authorized, and works out the amounts to record per distribution slot.
+pub(crate) fn plan_claim(
+    deps: Deps,
+    env: &Env,
+    campaign: Campaign,
+    address: &str,
+    amount: Option<Uint128>,
+) -> Result<Result<ClaimPlan, ClaimFailureReason>, ContractError> {
+    if is_blacklisted(deps, env, address)? {
+        return Ok(Err(ClaimFailureReason::Blacklisted));
+    }
 
-    ensure!(
-        !is_blacklisted(deps.as_ref(), receiver.as_ref())?,
-        ContractError::AddressBlacklisted
-    );
+    if FORFEITURES.has(deps.storage, address) {
+        return Ok(Err(ClaimFailureReason::Forfeited));
+    }
 
     // Get allocation for the address
-    let total_user_allocation = get_allocation(deps.as_ref(), receiver.as_ref())?.ok_or(
-        ContractError::NoAllocationFound {
-            address: receiver.to_string(),
-        },
-    )?;
+    let Some(total_user_allocation) = get_effective_allocation(deps, &campaign, address)? else {
+        return Ok(Err(ClaimFailureReason::NoAllocation));
+    };
+
+    // the vesting clock of the address lags behind if it was frozen while blacklisted
+    let vesting_time = get_vesting_time(deps, &campaign, address, &env.block.time)?;
+    let distribution_type = get_distribution_type(deps, &campaign, address, &vesting_time)?;
 
     // new_claims is HashMap<DistributionSlot, Claim=(amount, timestamp)> representing newly available amounts per slot
     let (max_claimable_amount_coin, new_claims) = helpers::compute_claimable_amount(
-        deps.as_ref(),
+        deps,
         &campaign,
-        &env.block.time,
-        receiver.as_ref(),
+        &distribution_type,
+        &vesting_time,
+        address,
         total_user_allocation,
     )?;
 
     let actual_claim_amount_coin = match amount {
         Some(requested_amount) => {
-            ensure!(
-                requested_amount > Uint128::zero(),
-                ContractError::InvalidClaimAmount {
-                    reason: "amount must be greater than zero".to_string()
-                }
-            );
-            ensure!(
-                requested_amount <= max_claimable_amount_coin.amount,
-                ContractError::InvalidClaimAmount {
+            if requested_amount.is_zero() {
+                return Ok(Err(ClaimFailureReason::InvalidAmount {
+                    reason: "amount must be greater than zero".to_string(),
+                }));
+            }
+            if requested_amount > max_claimable_amount_coin.amount {
+                return Ok(Err(ClaimFailureReason::InvalidAmount {
                     reason: format!(
                         "requested amount {} exceeds available claimable amount {}",
                         requested_amount, max_claimable_amount_coin.amount
-                    )
-                }
-            );
+                    ),
+                }));
+            }
             Coin {
                 denom: campaign.reward_denom.clone(),
                 amount: requested_amount,
@@ -198,23 +513,22 @@ pub(crate) fn claim(
         None => max_claimable_amount_coin,
     };
 
-    ensure!(
-        actual_claim_amount_coin.amount > Uint128::zero(),
-        ContractError::NothingToClaim
-    );
+    if actual_claim_amount_coin.amount.is_zero() {
+        return Ok(Err(ClaimFailureReason::NothingToClaim));
+    }
 
-    let available_funds = deps
-        .querier
-        .query_balance(env.contract.address, &campaign.reward_denom)?;
+    if !helpers::is_compliant(deps, &campaign, address)? {
+        return Ok(Err(ClaimFailureReason::NotCompliant));
+    }
 
-    ensure!(
-        actual_claim_amount_coin.amount <= available_funds.amount,
-        ContractError::CampaignError {
-            reason: "no funds available to claim".to_string()
-        }
-    );
+    let available_funds = helpers::available_funds(deps, env, &campaign)?;
+
+    if actual_claim_amount_coin.amount > available_funds.amount {
+        return Ok(Err(ClaimFailureReason::InsufficientFunds {
+            available: available_funds,
+        }));
+    }
 
-    let previous_claims = get_claims_for_address(deps.as_ref(), receiver.to_string())?;
     let mut claims_to_record: HashMap<DistributionSlot, Claim> = HashMap::new();
     let mut remaining_to_distribute = actual_claim_amount_coin.amount;
 
@@ -222,12 +536,17 @@ pub(crate) fn claim(
         let mut lump_sum_slots_with_new_claims: Vec<DistributionSlot> = vec![];
         let mut linear_vesting_slots_with_new_claims: Vec<DistributionSlot> = vec![];
 
-        for (idx, dist_type) in campaign.distribution_type.iter().enumerate() {
+        for (idx, dist_type) in distribution_type.iter().enumerate() {
             if new_claims.contains_key(&idx) {
                 // Only consider slots that have new claimable amounts
                 match dist_type {
-                    DistributionType::LumpSum { .. } => lump_sum_slots_with_new_claims.push(idx),
-                    DistributionType::LinearVesting { .. } => {
+                    DistributionType::LumpSum { .. }
+                    | DistributionType::RelativeLumpSum { .. }
+                    | DistributionType::Milestone { .. } => {
+                        lump_sum_slots_with_new_claims.push(idx)
+                    }
+                    DistributionType::LinearVesting { .. }
+                    | DistributionType::RelativeLinearVesting { .. } => {
                         linear_vesting_slots_with_new_claims.push(idx)
                     }
                 }
@@ -276,7 +595,181 @@ pub(crate) fn claim(
     // At this point, if initial checks were correct (actual_claim_amount_coin.amount <= sum of new_claims),
     // remaining_to_distribute should be zero.
 
-    let updated_claims = helpers::aggregate_claims(&previous_claims, &claims_to_record)?;
+    Ok(Ok(ClaimPlan {
+        campaign,
+        address: address.to_string(),
+        total_user_allocation,
+        amount: actual_claim_amount_coin,
+        claims_to_record,
+        approved_operator: None,
+    }))
+}
+
+/// Claims the rewards available for the receiver, or the sender if no receiver is provided. The
+/// claimed tokens are sent to the receiver, or to the IBC destination if provided.
+pub(crate) fn claim(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    receiver: Option<String>,
+    amount: Option<Uint128>,
+    ibc_destination: Option<IbcDestination>,
+) -> Result<Response, ContractError> {
+    if let Some(ibc_destination) = &ibc_destination {
+        helpers::validate_ibc_destination(ibc_destination)?;
+    }
+
+    let error_address = receiver.clone().unwrap_or_else(|| info.sender.to_string());
+
+    let plan = match prepare_claim(deps.as_ref(), &env, &info.sender, receiver, amount)? {
+        Ok(plan) => plan,
+        // the first claim anchors the distribution types relative to it, even if nothing has
+        // vested yet
+        Err(ClaimFailureReason::NothingToClaim) => {
+            let campaign = CAMPAIGN.load(deps.storage)?;
+            let receiver = deps.api.addr_validate(&error_address)?.to_string();
+            let vesting_time =
+                get_vesting_time(deps.as_ref(), &campaign, &receiver, &env.block.time)?;
+
+            if helpers::is_compliant(deps.as_ref(), &campaign, &receiver)?
+                && anchor_first_claim(deps.branch(), &campaign, &receiver, &vesting_time)?
+            {
+                return Ok(Response::default().add_attributes(vec![
+                    ("action", "claim".to_string()),
+                    ("receiver", receiver),
+                    ("claimed_amount", coin(0, campaign.reward_denom).to_string()),
+                    ("first_claimed_at", vesting_time.seconds().to_string()),
+                ]));
+            }
+
+            return Err(ContractError::from_claim_failure(
+                ClaimFailureReason::NothingToClaim,
+                &error_address,
+            ));
+        }
+        Err(reason) => return Err(ContractError::from_claim_failure(reason, &error_address)),
+    };
+    // approved operators can only send the claimed tokens to the receiver
+    ensure!(
+        plan.approved_operator.is_none() || ibc_destination.is_none(),
+        ContractError::Unauthorized
+    );
+
+    let receiver = plan.address.clone();
+    let slots = helpers::slot_amounts(&plan.claims_to_record);
+
+    let destination = ibc_destination
+        .as_ref()
+        .map_or(receiver.as_str(), |ibc_destination| {
+            ibc_destination.remote_address.as_str()
+        })
+        .to_string();
+    let applied = apply_claim(deps.branch(), &env, &info.sender, plan, &destination)?;
+    let actual_claim_amount_coin = applied.amount.clone();
+
+    let mut attributes = vec![
+        ("action", "claim".to_string()),
+        ("receiver", receiver.clone()),
+        ("claimed_amount", actual_claim_amount_coin.to_string()),
+    ];
+
+    let message = match ibc_destination {
+        Some(IbcDestination {
+            channel_id,
+            remote_address,
+            timeout,
+        }) => {
+            attributes.push(("channel_id", channel_id.clone()));
+            attributes.push(("remote_address", remote_address.clone()));
+
+            ibc::claim_transfer_msg(
+                &env,
+                PendingIbcTransfer {
+                    channel_id,
+                    address: receiver,
+                    amount: actual_claim_amount_coin,
+                    slots,
+                    // the claim was just appended to the claim history
+                    history_sequence: CLAIM_HISTORY_SEQUENCE.load(deps.storage)?,
+                    approved_operator: applied.approved_operator.map(Addr::into_string),
+                    anchored_first_claim: applied.anchored_first_claim,
+                },
+                remote_address,
+                timeout.unwrap_or(ibc::IBC_TRANSFER_TIMEOUT_SECONDS),
+            )?
+        }
+        None => SubMsg::new(BankMsg::Send {
+            to_address: receiver,
+            amount: vec![actual_claim_amount_coin],
+        }),
+    };
+
+    Ok(Response::default()
+        .add_submessage(message)
+        .add_attributes(attributes))
+}
+
+/// Records a planned claim, updating the claimed amounts and the claim history. Sending the
+/// claimed tokens to the destination is up to the caller. All the checks run before anything is
+/// written, so a claim that fails leaves no partial writes behind.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `env`  - The env context
+/// * `sender` - The address that initiated the claim
+/// * `plan` - The claim, as planned by [prepare_claim] or [plan_claim]
+/// * `destination` - The address the claimed tokens are sent to
+///
+/// # Returns
+/// * `Result<AppliedClaim, ContractError>` - The claim recorded
+pub(crate) fn apply_claim(
+    mut deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    plan: ClaimPlan,
+    destination: &str,
+) -> Result<AppliedClaim, ContractError> {
+    let ClaimPlan {
+        mut campaign,
+        address,
+        total_user_allocation,
+        amount: actual_claim_amount_coin,
+        claims_to_record,
+        approved_operator,
+    } = plan;
+
+    let previous_claims = get_claims_for_address(deps.as_ref(), address.clone())?;
+    // only the slots claimed from need to be written
+    let updated_claims: HashMap<DistributionSlot, Claim> =
+        helpers::aggregate_claims(&previous_claims, &claims_to_record)?
+            .into_iter()
+            .filter(|(slot, _)| claims_to_record.contains_key(slot))
+            .collect();
+
+    let total_claimed = get_total_claims_amount_for_address(deps.as_ref(), &address)?
+        .checked_add(actual_claim_amount_coin.amount)?;
+    ensure!(
+        total_user_allocation >= total_claimed,
+        ContractError::ExceededMaxClaimAmount
+    );
+
+    let approval = match approved_operator.clone() {
+        Some(operator) => {
+            let mut approval =
+                CLAIM_APPROVALS.load(deps.storage, (address.as_str(), operator.as_str()))?;
+            if let Some(max_amount) = approval.max_amount {
+                approval.max_amount =
+                    Some(max_amount.checked_sub(actual_claim_amount_coin.amount)?);
+            }
+            Some((operator, approval))
+        }
+        None => None,
+    };
+
+    // the relative distribution types were planned as if anchored at this claim
+    let vesting_time = get_vesting_time(deps.as_ref(), &campaign, &address, &env.block.time)?;
+    let anchored_first_claim =
+        anchor_first_claim(deps.branch(), &campaign, &address, &vesting_time)?;
 
     campaign.claimed.amount = campaign
         .claimed
@@ -284,33 +777,42 @@ pub(crate) fn claim(
         .checked_add(actual_claim_amount_coin.amount)?;
 
     CAMPAIGN.save(deps.storage, &campaign)?;
-    CLAIMS.save(deps.storage, receiver.to_string(), &updated_claims)?;
+    save_claims(deps.storage, address.as_str(), &updated_claims)?;
+    record_claim(
+        deps.storage,
+        env,
+        sender,
+        address.as_str(),
+        destination,
+        &actual_claim_amount_coin,
+        &claims_to_record,
+    )?;
 
-    ensure!(
-        total_user_allocation
-            >= get_total_claims_amount_for_address(deps.as_ref(), receiver.as_ref())?,
-        ContractError::ExceededMaxClaimAmount
-    );
+    if let Some((operator, approval)) = approval {
+        CLAIM_APPROVALS.save(
+            deps.storage,
+            (address.as_str(), operator.as_str()),
+            &approval,
+        )?;
+    }
 
-    Ok(Response::default()
-        .add_message(BankMsg::Send {
-            to_address: receiver.to_string(),
-            amount: vec![actual_claim_amount_coin.clone()],
-        })
-        .add_attributes(vec![
-            ("action", "claim".to_string()),
-            ("receiver", receiver.to_string()),
-            ("claimed_amount", actual_claim_amount_coin.to_string()),
-        ]))
+    Ok(AppliedClaim {
+        amount: actual_claim_amount_coin,
+        approved_operator,
+        anchored_first_claim,
+    })
 }
 
 /// Adds a batch of addresses and their allocations. This can only be done before the campaign has started.
+/// Allocations vesting on one of the schedules of the campaign can only be added once the campaign
+/// has been created. Allocations vesting only on relative distribution types can also be added
+/// while the campaign is running, and are anchored at the time they're added.
 ///
 /// # Arguments
 /// * `deps` - The dependencies
 /// * `env`  - The env context
 /// * `info` - The message info
-/// * `allocations` - Vector of (address, amount) pairs
+/// * `allocations` - Vector of (address, amount) pairs, or (address, amount, schedule id) triples
 ///
 /// # Returns
 /// * `Result<Response, ContractError>` - The response with attributes
@@ -318,7 +820,7 @@ pub fn add_allocations(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    allocations: Vec<(String, Uint128)>,
+    allocations: Vec<AllocationRow>,
 ) -> Result<Response, ContractError> {
     assert_authorized(deps.as_ref(), &info.sender)?;
 
@@ -333,19 +835,30 @@ pub fn add_allocations(
 
     // Check if campaign has started
     let campaign = CAMPAIGN.may_load(deps.storage)?;
+    let started = campaign
+        .as_ref()
+        .is_some_and(|campaign| campaign.has_started(&env.block.time));
 
-    if let Some(campaign) = campaign {
+    if let Some(campaign) = campaign.as_ref().filter(|_| started) {
         ensure!(
-            !campaign.has_started(&env.block.time),
+            campaign.closed.is_none(),
             ContractError::CampaignError {
-                reason: "cannot upload allocations after campaign has started".to_string(),
+                reason: "cannot upload allocations after campaign has been closed".to_string(),
             }
         );
     }
 
-    let allocations_len = allocations.len().to_string();
+    let mut checksum = AllocationChecksum::load(deps.storage)?;
+    ensure!(
+        checksum.finalized_at.is_none(),
+        ContractError::AllocationsFinalized
+    );
 
-    for (address_raw, amount) in allocations.into_iter() {
+    let allocations_len = allocations.len();
+    let mut total_allocated = TOTAL_ALLOCATED.may_load(deps.storage)?.unwrap_or_default();
+
+    for row in allocations.into_iter() {
+        let (address_raw, amount, schedule) = row.into_parts();
         let validated_receiver_string = validate_raw_address(deps.as_ref(), &address_raw)?;
 
         let allocation: Option<Uint128> =
@@ -357,28 +870,385 @@ pub fn add_allocations(
             }
         );
         ALLOCATIONS.save(deps.storage, validated_receiver_string.as_str(), &amount)?;
+        total_allocated = total_allocated.checked_add(amount)?;
+        checksum.record(&validated_receiver_string, amount)?;
+
+        if schedule.is_some() || started {
+            // the schedules are defined on the campaign
+            let distribution_type = campaign
+                .as_ref()
+                .ok_or(ContractError::CampaignError {
+                    reason: "there's not an active campaign".to_string(),
+                })?
+                .distribution_type_for(schedule.as_deref())?;
+
+            // allocations that vest on absolute times can't join a running campaign
+            if started {
+                ensure!(
+                    distribution_type.iter().all(|dist| dist.anchor().is_some()),
+                    ContractError::CampaignError {
+                        reason: "cannot upload allocations after campaign has started".to_string(),
+                    }
+                );
+
+                VESTING_ANCHORS.save(
+                    deps.storage,
+                    validated_receiver_string.as_str(),
+                    &VestingAnchors {
+                        registered_at: Some(env.block.time.seconds()),
+                        first_claimed_at: None,
+                        registered_reward_per_share: campaign
+                            .as_ref()
+                            .map(|campaign| campaign.reward_per_share)
+                            .unwrap_or_default(),
+                    },
+                )?;
+            }
+        }
+
+        if let Some(schedule) = schedule {
+            ALLOCATION_SCHEDULES.save(
+                deps.storage,
+                validated_receiver_string.as_str(),
+                &schedule,
+            )?;
+        }
     }
 
+    TOTAL_ALLOCATED.save(deps.storage, &total_allocated)?;
+    ALLOCATION_CHECKSUM.save(deps.storage, &checksum)?;
+
+    record_audit_entry(
+        deps.storage,
+        &env,
+        &info.sender,
+        AuditAction::AddAllocations {
+            count: allocations_len as u64,
+        },
+        None,
+    )?;
+
     Ok(Response::default()
         .add_attribute("action", "add_allocations")
-        .add_attribute("count", allocations_len))
+        .add_attribute("count", allocations_len.to_string()))
 }
 
-/// Replaces an address in the allocation list. This can be done at any time during the campaign.
+/// Adds a batch of addresses and their allocations, encoded in the packed binary format. The
+/// decoded allocations are added as with [add_allocations].
 ///
 /// # Arguments
 /// * `deps` - The dependencies
+/// * `env`  - The env context
 /// * `info` - The message info
-/// * `old_address` - The old address to replace
-/// * `new_address` - The new address to use
+/// * `data` - The encoded (address, amount) pairs
 ///
 /// # Returns
 /// * `Result<Response, ContractError>` - The response with attributes
-pub fn replace_address(
+pub fn add_allocations_packed(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    old_address_raw: String,
-    new_address_raw: String,
+    data: Binary,
+) -> Result<Response, ContractError> {
+    let allocations = helpers::decode_packed_allocations(deps.as_ref(), &data)?;
+    add_allocations(deps, env, info, allocations)
+}
+
+/// Imports the state exported from another contract with the ExportState query, restoring the
+/// allocations, claims and blacklist entries of the given addresses. This can only be done after the
+/// campaign has been created, so the claims can be checked against its distribution slots, and
+/// before it has started.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `env`  - The env context
+/// * `info` - The message info
+/// * `entries` - The state entries to import
+///
+/// # Returns
+/// * `Result<Response, ContractError>` - The response with attributes
+pub fn import_state(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    entries: Vec<StateEntry>,
+) -> Result<Response, ContractError> {
+    assert_authorized(deps.as_ref(), &info.sender)?;
+
+    ensure!(
+        entries.len() <= MAX_ALLOCATION_BATCH_SIZE,
+        ContractError::BatchSizeLimitExceeded {
+            actual: entries.len(),
+            max: MAX_ALLOCATION_BATCH_SIZE,
+        }
+    );
+
+    let mut campaign = CAMPAIGN
+        .may_load(deps.storage)?
+        .ok_or(ContractError::CampaignError {
+            reason: "there's not an active campaign".to_string(),
+        })?;
+
+    ensure!(
+        !campaign.has_started(&env.block.time),
+        ContractError::CampaignError {
+            reason: "cannot import state after campaign has started".to_string(),
+        }
+    );
+
+    let mut checksum = AllocationChecksum::load(deps.storage)?;
+    ensure!(
+        checksum.finalized_at.is_none(),
+        ContractError::AllocationsFinalized
+    );
+
+    let entries_len = entries.len();
+    let mut imported_claims = Uint128::zero();
+    let mut total_allocated = TOTAL_ALLOCATED.may_load(deps.storage)?.unwrap_or_default();
+
+    for entry in entries.into_iter() {
+        let address = validate_raw_address(deps.as_ref(), &entry.address)?;
+
+        ensure!(
+            !ALLOCATIONS.has(deps.storage, address.as_str()),
+            ContractError::AllocationAlreadyExists {
+                address: address.clone(),
+            }
+        );
+
+        let claims = collect_slot_claims(
+            campaign.distribution_type_for(entry.schedule.as_deref())?,
+            &address,
+            entry.allocation,
+            entry
+                .claims
+                .into_iter()
+                .map(|claim| (claim.slot, (claim.amount, claim.timestamp))),
+        )?;
+        let total_claimed = helpers::total_claimed(&claims)?;
+
+        ensure!(
+            total_claimed <= entry.allocation,
+            ContractError::ExceededMaxClaimAmount
+        );
+
+        ALLOCATIONS.save(deps.storage, address.as_str(), &entry.allocation)?;
+        total_allocated = total_allocated.checked_add(entry.allocation)?;
+        checksum.record(&address, entry.allocation)?;
+        save_claims(deps.storage, address.as_str(), &claims)?;
+
+        if let Some(blacklist_entry) = entry.blacklist {
+            BLACKLIST.save(deps.storage, address.as_str(), &blacklist_entry)?;
+        }
+
+        if let Some(schedule) = entry.schedule {
+            ALLOCATION_SCHEDULES.save(deps.storage, address.as_str(), &schedule)?;
+        }
+
+        if entry.registered_at.is_some() || entry.first_claimed_at.is_some() {
+            VESTING_ANCHORS.save(
+                deps.storage,
+                address.as_str(),
+                &VestingAnchors {
+                    registered_at: entry.registered_at,
+                    first_claimed_at: entry.first_claimed_at,
+                    ..VestingAnchors::default()
+                },
+            )?;
+        }
+
+        imported_claims = imported_claims.checked_add(total_claimed)?;
+    }
+
+    // the claims made on the previous contract count towards the claimed amount of the campaign
+    campaign.claimed.amount = campaign.claimed.amount.checked_add(imported_claims)?;
+    CAMPAIGN.save(deps.storage, &campaign)?;
+    TOTAL_ALLOCATED.save(deps.storage, &total_allocated)?;
+    ALLOCATION_CHECKSUM.save(deps.storage, &checksum)?;
+
+    record_audit_entry(
+        deps.storage,
+        &env,
+        &info.sender,
+        AuditAction::ImportState {
+            count: entries_len as u64,
+        },
+        None,
+    )?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "import_state".to_string()),
+        ("count", entries_len.to_string()),
+        (
+            "claimed",
+            Coin {
+                denom: campaign.reward_denom,
+                amount: imported_claims,
+            }
+            .to_string(),
+        ),
+    ]))
+}
+
+/// Records the amounts already distributed to addresses outside of this contract, e.g. by a
+/// previous campaign, so they are subtracted from what the addresses can claim. This can only be
+/// done after the campaign has been created and before it has started, for addresses that have an
+/// allocation. Seeding a distribution slot again replaces the amount previously seeded for it.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `env`  - The env context
+/// * `info` - The message info
+/// * `claims` - Vector of (address, [(distribution slot, amount)]) pairs
+///
+/// # Returns
+/// * `Result<Response, ContractError>` - The response with attributes
+pub fn seed_claims(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    claims: Vec<(String, Vec<(u64, Uint128)>)>,
+) -> Result<Response, ContractError> {
+    assert_authorized(deps.as_ref(), &info.sender)?;
+
+    ensure!(
+        claims.len() <= MAX_ALLOCATION_BATCH_SIZE,
+        ContractError::BatchSizeLimitExceeded {
+            actual: claims.len(),
+            max: MAX_ALLOCATION_BATCH_SIZE,
+        }
+    );
+
+    let mut campaign = CAMPAIGN
+        .may_load(deps.storage)?
+        .ok_or(ContractError::CampaignError {
+            reason: "there's not an active campaign".to_string(),
+        })?;
+
+    ensure!(
+        !campaign.has_started(&env.block.time),
+        ContractError::CampaignError {
+            reason: "cannot seed claims after campaign has started".to_string(),
+        }
+    );
+
+    let claims_len = claims.len();
+
+    for (address_raw, slot_amounts) in claims.into_iter() {
+        let address = validate_raw_address(deps.as_ref(), &address_raw)?;
+        let allocation =
+            get_allocation(deps.as_ref(), &address)?.ok_or(ContractError::NoAllocationFound {
+                address: address.clone(),
+            })?;
+
+        let seeded_claims = collect_slot_claims(
+            &get_distribution_type(deps.as_ref(), &campaign, &address, &env.block.time)?,
+            &address,
+            allocation,
+            slot_amounts
+                .into_iter()
+                .map(|(slot, amount)| (slot, (amount, env.block.time.seconds()))),
+        )?;
+
+        let mut claims = get_claims_for_address(deps.as_ref(), address.clone())?;
+        let replaced_claims: HashMap<DistributionSlot, Claim> = seeded_claims
+            .keys()
+            .filter_map(|slot| claims.get(slot).map(|claim| (*slot, *claim)))
+            .collect();
+        claims.extend(seeded_claims.iter());
+
+        ensure!(
+            helpers::total_claimed(&claims)? <= allocation,
+            ContractError::ExceededMaxClaimAmount
+        );
+
+        campaign.claimed.amount = campaign
+            .claimed
+            .amount
+            .checked_sub(helpers::total_claimed(&replaced_claims)?)?
+            .checked_add(helpers::total_claimed(&seeded_claims)?)?;
+
+        save_claims(deps.storage, address.as_str(), &seeded_claims)?;
+    }
+
+    CAMPAIGN.save(deps.storage, &campaign)?;
+
+    record_audit_entry(
+        deps.storage,
+        &env,
+        &info.sender,
+        AuditAction::SeedClaims {
+            count: claims_len as u64,
+        },
+        None,
+    )?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "seed_claims".to_string()),
+        ("count", claims_len.to_string()),
+        ("claimed", campaign.claimed.to_string()),
+    ]))
+}
+
+/// Collects the claims of an address per distribution slot, making sure the slots exist in the
+/// distribution types the address vests on, aren't repeated and don't exceed the entitlement of
+/// their distribution. A slot may also hold the dust left by rounding down the entitlements, as
+/// it's paid out on the last claim of a campaign.
+fn collect_slot_claims(
+    distribution_type: &[DistributionType],
+    address: &str,
+    allocation: Uint128,
+    slot_claims: impl Iterator<Item = (u64, Claim)>,
+) -> Result<HashMap<DistributionSlot, Claim>, ContractError> {
+    let entitlements = distribution_type
+        .iter()
+        .map(|distribution| helpers::compute_slot_entitlement(allocation, distribution))
+        .collect::<Result<Vec<Uint128>, ContractError>>()?;
+    let dust = allocation.saturating_sub(entitlements.iter().sum());
+
+    let mut claims: HashMap<DistributionSlot, Claim> = HashMap::new();
+
+    for (slot, claim) in slot_claims {
+        let entitlement = entitlements
+            .get(slot as usize)
+            .ok_or(ContractError::InvalidInput {
+                reason: format!("invalid distribution slot {slot} for {address}"),
+            })?;
+        ensure!(
+            claim.0 <= entitlement.checked_add(dust)?,
+            ContractError::ExceededMaxClaimAmount
+        );
+        ensure!(
+            claims.insert(slot as DistributionSlot, claim).is_none(),
+            ContractError::InvalidInput {
+                reason: format!("duplicate distribution slot {slot} for {address}"),
+            }
+        );
+    }
+
+    Ok(claims)
+}
+
+/// Replaces an address in the allocation list. This can be done at any time during the campaign,
+/// even after the allocations are finalized, to recover lost keys.
+/// Everything recorded for the old address, like its claims, the claim approvals it granted and its
+/// auto-claim subscription, moves to the new address.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `env`  - The env context
+/// * `info` - The message info
+/// * `old_address` - The old address to replace
+/// * `new_address` - The new address to use
+///
+/// # Returns
+/// * `Result<Response, ContractError>` - The response with attributes
+pub fn replace_address(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    old_address_raw: String,
+    new_address_raw: String,
 ) -> Result<Response, ContractError> {
     assert_authorized(deps.as_ref(), &info.sender)?;
 
@@ -408,18 +1278,98 @@ pub fn replace_address(
         &old_allocation,
     )?;
 
+    // the allocation moves to the new address, so the count and total of the checksum don't
+    // change. Replacements are allowed after the allocations are finalized to recover lost keys.
+    let mut checksum = AllocationChecksum::load(deps.storage)?;
+    checksum.unrecord(old_address_canonical.as_str(), old_allocation)?;
+    checksum.record(new_address_validated.as_str(), old_allocation)?;
+    ALLOCATION_CHECKSUM.save(deps.storage, &checksum)?;
+
     // Update claims and blacklist if the address has claimed rewards or is blacklisted
     let claims = get_claims_for_address(deps.as_ref(), old_address_canonical.clone())?;
     if !claims.is_empty() {
-        CLAIMS.remove(deps.storage, old_address_canonical.clone());
-        CLAIMS.save(deps.storage, new_address_validated.to_string(), &claims)?;
+        move_claims(
+            deps.storage,
+            old_address_canonical.as_str(),
+            new_address_validated.as_str(),
+        )?;
+        move_claim_history(
+            deps.storage,
+            old_address_canonical.as_str(),
+            new_address_validated.as_str(),
+        )?;
+        move_pending_ibc_transfers(
+            deps.storage,
+            old_address_canonical.as_str(),
+            new_address_validated.as_str(),
+        )?;
     }
 
-    if is_blacklisted(deps.as_ref(), old_address_canonical.as_str())? {
+    if let Some(blacklist_entry) =
+        BLACKLIST.may_load(deps.storage, old_address_canonical.as_str())?
+    {
         BLACKLIST.remove(deps.storage, old_address_canonical.as_str());
-        BLACKLIST.save(deps.storage, new_address_validated.as_str(), &true)?;
+        BLACKLIST.save(
+            deps.storage,
+            new_address_validated.as_str(),
+            &blacklist_entry,
+        )?;
+    }
+
+    move_claim_approvals(
+        deps.storage,
+        old_address_canonical.as_str(),
+        new_address_validated.as_str(),
+    )?;
+
+    if AUTO_CLAIM_SUBSCRIPTIONS.has(deps.storage, old_address_canonical.as_str()) {
+        AUTO_CLAIM_SUBSCRIPTIONS.remove(deps.storage, old_address_canonical.as_str());
+        AUTO_CLAIM_SUBSCRIPTIONS.save(deps.storage, new_address_validated.as_str(), &())?;
+    }
+
+    if let Some(schedule) =
+        ALLOCATION_SCHEDULES.may_load(deps.storage, old_address_canonical.as_str())?
+    {
+        ALLOCATION_SCHEDULES.remove(deps.storage, old_address_canonical.as_str());
+        ALLOCATION_SCHEDULES.save(deps.storage, new_address_validated.as_str(), &schedule)?;
+    }
+
+    if let Some(anchors) = VESTING_ANCHORS.may_load(deps.storage, old_address_canonical.as_str())? {
+        VESTING_ANCHORS.remove(deps.storage, old_address_canonical.as_str());
+        VESTING_ANCHORS.save(deps.storage, new_address_validated.as_str(), &anchors)?;
+    }
+
+    if let Some(early_exit) = EARLY_EXITS.may_load(deps.storage, old_address_canonical.as_str())? {
+        EARLY_EXITS.remove(deps.storage, old_address_canonical.as_str());
+        EARLY_EXITS.save(deps.storage, new_address_validated.as_str(), &early_exit)?;
+    }
+
+    if let Some(forfeiture) = FORFEITURES.may_load(deps.storage, old_address_canonical.as_str())? {
+        FORFEITURES.remove(deps.storage, old_address_canonical.as_str());
+        FORFEITURES.save(deps.storage, new_address_validated.as_str(), &forfeiture)?;
+    }
+
+    if let Some(vesting_freezes) =
+        VESTING_FREEZES.may_load(deps.storage, old_address_canonical.as_str())?
+    {
+        VESTING_FREEZES.remove(deps.storage, old_address_canonical.as_str());
+        VESTING_FREEZES.save(
+            deps.storage,
+            new_address_validated.as_str(),
+            &vesting_freezes,
+        )?;
     }
 
+    record_audit_entry(
+        deps.storage,
+        &env,
+        &info.sender,
+        AuditAction::ReplaceAddress {
+            new_address: new_address_validated.to_string(),
+        },
+        Some(old_address_canonical),
+    )?;
+
     Ok(Response::default().add_attributes(vec![
         ("action", "replace_address".to_string()),
         ("old_address", old_address_raw),
@@ -427,7 +1377,8 @@ pub fn replace_address(
     ]))
 }
 
-/// Removes an address from the allocation list. This can only be done before the campaign has started.
+/// Removes an address from the allocation list. This can only be done before the campaign has
+/// started and the allocations are finalized.
 /// Trying to remove an address that doesn't exist in the list won't result in an error.
 ///
 /// # Arguments
@@ -446,6 +1397,12 @@ pub fn remove_address(
 ) -> Result<Response, ContractError> {
     assert_authorized(deps.as_ref(), &info.sender)?;
 
+    let mut checksum = AllocationChecksum::load(deps.storage)?;
+    ensure!(
+        checksum.finalized_at.is_none(),
+        ContractError::AllocationsFinalized
+    );
+
     // Check if campaign has started
     let campaign = CAMPAIGN.may_load(deps.storage)?;
 
@@ -461,7 +1418,23 @@ pub fn remove_address(
 
     let address = validate_raw_address(deps.as_ref(), &address)?;
 
-    ALLOCATIONS.remove(deps.storage, address.as_str());
+    if let Some(allocation) = ALLOCATIONS.may_load(deps.storage, address.as_str())? {
+        ALLOCATIONS.remove(deps.storage, address.as_str());
+        ALLOCATION_SCHEDULES.remove(deps.storage, address.as_str());
+        VESTING_ANCHORS.remove(deps.storage, address.as_str());
+        let total_allocated = TOTAL_ALLOCATED.may_load(deps.storage)?.unwrap_or_default();
+        TOTAL_ALLOCATED.save(deps.storage, &total_allocated.checked_sub(allocation)?)?;
+        checksum.unrecord(address.as_str(), allocation)?;
+        ALLOCATION_CHECKSUM.save(deps.storage, &checksum)?;
+    }
+
+    record_audit_entry(
+        deps.storage,
+        &env,
+        &info.sender,
+        AuditAction::RemoveAddress,
+        Some(address.clone()),
+    )?;
 
     Ok(Response::default()
         .add_attribute("action", "remove_address")
@@ -470,29 +1443,43 @@ pub fn remove_address(
 
 /// Blacklists or unblacklists an address. This can be done at any time.
 ///
+/// Blacklisting an address that is already blacklisted replaces its entry. If the vesting clock of
+/// the address was frozen, the period it was frozen for is kept when the entry is lifted, so the
+/// vesting of the address resumes where it stopped.
+///
 /// # Arguments
 /// * `deps` - The dependencies
+/// * `env`  - The env context
 /// * `info` - The message info
 /// * `address` - The address to blacklist/unblacklist
 /// * `blacklist` - Whether to blacklist or unblacklist
+/// * `reason` - The reason for blacklisting, ignored when unblacklisting
+/// * `note` - A free-form description of the reason, ignored when unblacklisting
+/// * `expiration` - When the blacklisting expires, ignored when unblacklisting
+/// * `freeze_vesting` - Whether to freeze the vesting clock, ignored when unblacklisting
 ///
 /// # Returns
 /// * `Result<Response, ContractError>` - The response with attributes
+#[allow(clippy::too_many_arguments)]
 pub fn blacklist_address(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     address: String,
     blacklist: bool,
+    reason: Option<BlacklistReason>,
+    note: Option<String>,
+    expiration: Option<Expiration>,
+    freeze_vesting: Option<bool>,
 ) -> Result<Response, ContractError> {
     assert_authorized(deps.as_ref(), &info.sender)?;
 
     let address = validate_raw_address(deps.as_ref(), &address)?;
+    let entry = blacklist
+        .then(|| new_blacklist_entry(&env, &info.sender, reason, note, expiration, freeze_vesting))
+        .transpose()?;
 
-    if blacklist {
-        BLACKLIST.save(deps.storage, address.as_str(), &true)?;
-    } else {
-        BLACKLIST.remove(deps.storage, address.as_str());
-    }
+    update_blacklist(deps.storage, &env, &info.sender, &address, entry.as_ref())?;
 
     Ok(Response::default()
         .add_attribute("action", "blacklist_address".to_string())
@@ -500,10 +1487,154 @@ pub fn blacklist_address(
         .add_attribute("blacklisted", blacklist.to_string()))
 }
 
+/// Blacklists or unblacklists a batch of addresses at once, applying the same entry to all of
+/// them. Emits a `blacklist_address` event per address.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `env`  - The env context
+/// * `info` - The message info
+/// * `addresses` - The addresses to blacklist/unblacklist
+/// * `blacklist` - Whether to blacklist or unblacklist
+/// * `reason` - The reason for blacklisting, ignored when unblacklisting
+/// * `note` - A free-form description of the reason, ignored when unblacklisting
+/// * `expiration` - When the blacklisting expires, ignored when unblacklisting
+/// * `freeze_vesting` - Whether to freeze the vesting clock, ignored when unblacklisting
+///
+/// # Returns
+/// * `Result<Response, ContractError>` - The response with attributes and events
+#[allow(clippy::too_many_arguments)]
+pub fn blacklist_addresses(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    addresses: Vec<String>,
+    blacklist: bool,
+    reason: Option<BlacklistReason>,
+    note: Option<String>,
+    expiration: Option<Expiration>,
+    freeze_vesting: Option<bool>,
+) -> Result<Response, ContractError> {
+    assert_authorized(deps.as_ref(), &info.sender)?;
+
+    blacklist_addresses_unchecked(
+        deps,
+        env,
+        &info.sender,
+        addresses,
+        blacklist,
+        reason,
+        note,
+        expiration,
+        freeze_vesting,
+    )
+}
+
+/// Blacklists or unblacklists a batch of addresses on behalf of the given sender, without checking
+/// whether it's authorized. Used by [blacklist_addresses] and the BlacklistAddresses sudo message.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn blacklist_addresses_unchecked(
+    deps: DepsMut,
+    env: Env,
+    sender: &Addr,
+    addresses: Vec<String>,
+    blacklist: bool,
+    reason: Option<BlacklistReason>,
+    note: Option<String>,
+    expiration: Option<Expiration>,
+    freeze_vesting: Option<bool>,
+) -> Result<Response, ContractError> {
+    ensure!(
+        addresses.len() <= MAX_BLACKLIST_BATCH_SIZE,
+        ContractError::BatchSizeLimitExceeded {
+            actual: addresses.len(),
+            max: MAX_BLACKLIST_BATCH_SIZE,
+        }
+    );
+
+    ensure!(
+        !addresses.is_empty(),
+        ContractError::InvalidInput {
+            reason: "addresses cannot be empty".to_string(),
+        }
+    );
+
+    let entry = blacklist
+        .then(|| new_blacklist_entry(&env, sender, reason, note, expiration, freeze_vesting))
+        .transpose()?;
+
+    let mut events = Vec::with_capacity(addresses.len());
+
+    for address in addresses.iter() {
+        let address = validate_raw_address(deps.as_ref(), address)?;
+
+        update_blacklist(deps.storage, &env, sender, &address, entry.as_ref())?;
+
+        events.push(
+            Event::new("blacklist_address")
+                .add_attribute("address", address)
+                .add_attribute("blacklisted", blacklist.to_string()),
+        );
+    }
+
+    Ok(Response::default()
+        .add_attributes(vec![
+            ("action", "blacklist_addresses".to_string()),
+            ("count", addresses.len().to_string()),
+            ("blacklisted", blacklist.to_string()),
+        ])
+        .add_events(events))
+}
+
+/// Builds a validated blacklist entry, recording the current time and the sender.
+fn new_blacklist_entry(
+    env: &Env,
+    sender: &Addr,
+    reason: Option<BlacklistReason>,
+    note: Option<String>,
+    expiration: Option<Expiration>,
+    freeze_vesting: Option<bool>,
+) -> Result<BlacklistEntry, ContractError> {
+    let freeze_vesting = freeze_vesting.unwrap_or(false);
+    helpers::validate_blacklist_params(env, note.as_deref(), expiration.as_ref(), freeze_vesting)?;
+
+    Ok(BlacklistEntry {
+        reason: reason.unwrap_or_default(),
+        blacklisted_at: env.block.time.seconds(),
+        blacklisted_by: sender.to_string(),
+        note,
+        expiration,
+        freeze_vesting,
+    })
+}
+
+/// Blacklists the given address with the given entry, replacing its previous entry if any, or
+/// unblacklists it if no entry is given. The change is recorded in the audit log.
+fn update_blacklist(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: &Addr,
+    address: &str,
+    entry: Option<&BlacklistEntry>,
+) -> Result<(), ContractError> {
+    lift_blacklist(storage, env, address)?;
+
+    let action = match entry {
+        Some(entry) => {
+            BLACKLIST.save(storage, address, entry)?;
+            AuditAction::BlacklistAddress
+        }
+        None => AuditAction::UnblacklistAddress,
+    };
+
+    record_audit_entry(storage, env, sender, action, Some(address.to_string()))
+}
+
 /// Manages authorized wallets that can perform admin actions. Only the owner can manage the authorized wallets list.
 ///
 /// # Arguments
 /// * `deps` - The dependencies
+/// * `env`  - The env context
 /// * `info` - The message info
 /// * `addresses` - Vector of addresses to authorize/unauthorize
 /// * `authorized` - Whether to authorize or unauthorize the addresses
@@ -512,6 +1643,7 @@ pub fn blacklist_address(
 /// * `Result<Response, ContractError>` - The response with attributes
 pub fn manage_authorized_wallets(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     addresses: Vec<String>,
     authorized: bool,
@@ -538,11 +1670,21 @@ pub fn manage_authorized_wallets(
     for address in addresses.iter() {
         let validated_address = deps.api.addr_validate(address)?;
 
-        if authorized {
+        let action = if authorized {
             AUTHORIZED_WALLETS.save(deps.storage, validated_address.as_str(), &())?;
+            AuditAction::AuthorizeWallet
         } else {
             AUTHORIZED_WALLETS.remove(deps.storage, validated_address.as_str());
-        }
+            AuditAction::UnauthorizeWallet
+        };
+
+        record_audit_entry(
+            deps.storage,
+            &env,
+            &info.sender,
+            action,
+            Some(validated_address.to_string()),
+        )?;
     }
 
     Ok(Response::default().add_attributes(vec![
@@ -551,3 +1693,1066 @@ pub fn manage_authorized_wallets(
         ("authorized", authorized.to_string()),
     ]))
 }
+
+/// Continues the data migration in progress, processing up to `batch_size` entries.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `env`  - The env context
+/// * `info` - The message info
+/// * `batch_size` - The maximum number of entries to process
+///
+/// # Returns
+/// * `Result<Response, ContractError>` - The response with attributes
+pub fn continue_migration(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    batch_size: Option<u32>,
+) -> Result<Response, ContractError> {
+    assert_authorized(deps.as_ref(), &info.sender)?;
+
+    let (processed, progress) = migrations::run_migration(
+        deps.storage,
+        &env,
+        batch_size.unwrap_or(migrations::DEFAULT_MIGRATION_BATCH_SIZE),
+    )?;
+
+    record_audit_entry(
+        deps.storage,
+        &env,
+        &info.sender,
+        AuditAction::ContinueMigration { processed },
+        None,
+    )?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "continue_migration".to_string()),
+        ("processed", processed.to_string()),
+        ("completed", progress.is_none().to_string()),
+    ]))
+}
+
+/// Sets the owner of the contract through a sudo message, cancelling any pending ownership
+/// transfer.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `env`  - The env context
+/// * `owner` - The new owner of the contract
+///
+/// # Returns
+/// * `Result<Response, ContractError>` - The response with attributes
+pub fn reset_owner(deps: DepsMut, env: Env, owner: String) -> Result<Response, ContractError> {
+    let owner = deps.api.addr_validate(&owner)?;
+    cw_ownable::initialize_owner(deps.storage, deps.api, Some(owner.as_str()))?;
+
+    record_audit_entry(
+        deps.storage,
+        &env,
+        &Addr::unchecked(SUDO_SENDER),
+        AuditAction::ResetOwner,
+        Some(owner.to_string()),
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("action", "reset_owner")
+        .add_attribute("owner", owner))
+}
+
+/// Pauses or resumes the contract through a sudo message. While paused, all the execute messages
+/// are rejected.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `env`  - The env context
+/// * `paused` - Whether to pause or resume the contract
+///
+/// # Returns
+/// * `Result<Response, ContractError>` - The response with attributes
+pub fn set_paused(deps: DepsMut, env: Env, paused: bool) -> Result<Response, ContractError> {
+    if paused {
+        ensure!(!is_paused(deps.storage)?, ContractError::ContractPaused);
+        PAUSED.save(deps.storage, &true)?;
+    } else {
+        ensure!(is_paused(deps.storage)?, ContractError::ContractNotPaused);
+        PAUSED.remove(deps.storage);
+    }
+
+    let (action, audit_action) = if paused {
+        ("pause", AuditAction::Pause)
+    } else {
+        ("resume", AuditAction::Resume)
+    };
+
+    record_audit_entry(
+        deps.storage,
+        &env,
+        &Addr::unchecked(SUDO_SENDER),
+        audit_action,
+        None,
+    )?;
+
+    Ok(Response::default().add_attribute("action", action))
+}
+
+/// Allows an IBC channel to send claim packets, updates its configuration, or disallows it if no
+/// configuration is given. Only the owner can manage the IBC channels.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `env`  - The env context
+/// * `info` - The message info
+/// * `channel_id` - The IBC channel on this chain
+/// * `config` - The configuration of the channel, if allowed
+///
+/// # Returns
+/// * `Result<Response, ContractError>` - The response with attributes
+pub fn manage_ibc_channel(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    channel_id: String,
+    config: Option<IbcChannelConfig>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    ensure!(
+        !channel_id.is_empty(),
+        ContractError::InvalidIbcChannel {
+            reason: "channel_id cannot be empty".to_string(),
+        }
+    );
+
+    match &config {
+        Some(config) => {
+            ensure!(
+                !config.address_prefix.is_empty(),
+                ContractError::InvalidIbcChannel {
+                    reason: "address_prefix cannot be empty".to_string(),
+                }
+            );
+
+            if let Some(transfer_channel) = &config.transfer_channel {
+                ensure!(
+                    !transfer_channel.is_empty(),
+                    ContractError::InvalidIbcChannel {
+                        reason: "transfer_channel cannot be empty".to_string(),
+                    }
+                );
+            }
+
+            IBC_CHANNELS.save(deps.storage, channel_id.as_str(), config)?;
+        }
+        None => IBC_CHANNELS.remove(deps.storage, channel_id.as_str()),
+    }
+
+    let allowed = config.is_some();
+
+    record_audit_entry(
+        deps.storage,
+        &env,
+        &info.sender,
+        AuditAction::ManageIbcChannel { config },
+        Some(channel_id.clone()),
+    )?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "manage_ibc_channel".to_string()),
+        ("channel_id", channel_id),
+        ("allowed", allowed.to_string()),
+    ]))
+}
+
+/// Approves an operator to claim on behalf of the sender, replacing any previous approval.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `env`  - The env context
+/// * `info` - The message info
+/// * `operator` - The address allowed to claim on behalf of the sender
+/// * `expires` - When the approval expires, if ever
+/// * `max_amount` - The maximum amount the operator can claim, if limited
+///
+/// # Returns
+/// * `Result<Response, ContractError>` - The response with attributes
+pub(crate) fn approve_claimer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    operator: String,
+    expires: Option<Expiration>,
+    max_amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let operator = deps.api.addr_validate(&operator)?;
+
+    ensure!(
+        get_allocation(deps.as_ref(), info.sender.as_str())?.is_some(),
+        ContractError::NoAllocationFound {
+            address: info.sender.to_string(),
+        }
+    );
+
+    ensure!(
+        operator != info.sender,
+        ContractError::InvalidInput {
+            reason: "can't approve yourself as claimer".to_string(),
+        }
+    );
+
+    if let Some(expires) = &expires {
+        ensure!(
+            !expires.is_expired(&env.block),
+            ContractError::InvalidInput {
+                reason: format!("expiration {expires} is already expired"),
+            }
+        );
+    }
+
+    ensure!(
+        max_amount != Some(Uint128::zero()),
+        ContractError::InvalidInput {
+            reason: "max_amount must be greater than zero".to_string(),
+        }
+    );
+
+    CLAIM_APPROVALS.save(
+        deps.storage,
+        (info.sender.as_str(), operator.as_str()),
+        &ClaimApproval {
+            expires,
+            max_amount,
+        },
+    )?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "approve_claimer".to_string()),
+        ("owner", info.sender.to_string()),
+        ("operator", operator.to_string()),
+        (
+            "max_amount",
+            max_amount.map_or("unlimited".to_string(), |amount| amount.to_string()),
+        ),
+    ]))
+}
+
+/// Revokes the approval of an operator to claim on behalf of the sender.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `info` - The message info
+/// * `operator` - The operator to revoke
+///
+/// # Returns
+/// * `Result<Response, ContractError>` - The response with attributes
+pub(crate) fn revoke_claimer(
+    deps: DepsMut,
+    info: MessageInfo,
+    operator: String,
+) -> Result<Response, ContractError> {
+    let key = (info.sender.as_str(), operator.as_str());
+
+    ensure!(
+        CLAIM_APPROVALS.has(deps.storage, key),
+        ContractError::ClaimApprovalNotFound { operator }
+    );
+
+    CLAIM_APPROVALS.remove(deps.storage, key);
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "revoke_claimer".to_string()),
+        ("owner", info.sender.to_string()),
+        ("operator", operator),
+    ]))
+}
+
+/// Subscribes the sender to auto-claims, or unsubscribes it.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `info` - The message info
+/// * `enabled` - Whether the sender is subscribed
+///
+/// # Returns
+/// * `Result<Response, ContractError>` - The response with attributes
+pub(crate) fn auto_claim(
+    deps: DepsMut,
+    info: MessageInfo,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    if enabled {
+        ensure!(
+            get_allocation(deps.as_ref(), info.sender.as_str())?.is_some(),
+            ContractError::NoAllocationFound {
+                address: info.sender.to_string(),
+            }
+        );
+
+        AUTO_CLAIM_SUBSCRIPTIONS.save(deps.storage, info.sender.as_str(), &())?;
+    } else {
+        AUTO_CLAIM_SUBSCRIPTIONS.remove(deps.storage, info.sender.as_str());
+    }
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "auto_claim".to_string()),
+        ("address", info.sender.to_string()),
+        ("enabled", enabled.to_string()),
+    ]))
+}
+
+/// Claims the rewards available for the next subscribed addresses, resuming after the cursor left
+/// by the previous crank. Addresses that can't claim at the moment are skipped. The claims are paid
+/// out in full, and the crank tip on every claim is paid to the sender from the crank tip pool,
+/// as long as the pool has funds left.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `env`  - The env context
+/// * `info` - The message info
+/// * `limit` - The maximum number of addresses to process
+///
+/// # Returns
+/// * `Result<Response, ContractError>` - The response with attributes
+pub(crate) fn crank(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    ensure!(
+        limit != Some(0),
+        ContractError::InvalidInput {
+            reason: "limit must be greater than zero".to_string(),
+        }
+    );
+
+    let limit = limit.unwrap_or(DEFAULT_CRANK_LIMIT).min(MAX_CRANK_LIMIT) as usize;
+
+    let campaign = claimable_campaign(deps.as_ref(), &env)?
+        .map_err(|reason| ContractError::from_claim_failure(reason, info.sender.as_str()))?;
+
+    let cursor = AUTO_CLAIM_CURSOR.may_load(deps.storage)?;
+    let addresses = AUTO_CLAIM_SUBSCRIPTIONS
+        .keys(
+            deps.storage,
+            cursor.as_deref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<String>>>()?;
+
+    // the claims are paid out once the crank completes, so the funds they use are tracked here
+    let mut available_funds = helpers::available_funds(deps.as_ref(), &env, &campaign)?.amount;
+    let tip = CRANK_TIP.may_load(deps.storage)?.unwrap_or_default();
+    let mut tip_pool = CRANK_TIP_POOL.may_load(deps.storage)?.unwrap_or_default();
+
+    let mut messages = vec![];
+    let mut claimed = 0u32;
+    let mut total_claimed = Uint128::zero();
+    let mut total_tip = Uint128::zero();
+
+    for address in addresses.iter() {
+        // every claim updates the campaign, so it's reloaded for each address
+        let campaign = CAMPAIGN.load(deps.storage)?;
+
+        let plan = match plan_claim(deps.as_ref(), &env, campaign, address, None)? {
+            Ok(plan) if plan.amount.amount <= available_funds => plan,
+            _ => continue,
+        };
+
+        let amount = apply_claim(deps.branch(), &env, &info.sender, plan, address)?.amount;
+        available_funds = available_funds.checked_sub(amount.amount)?;
+
+        let tip_amount = amount.amount.mul_floor(tip).min(tip_pool);
+        tip_pool = tip_pool.checked_sub(tip_amount)?;
+
+        messages.push(BankMsg::Send {
+            to_address: address.clone(),
+            amount: vec![amount.clone()],
+        });
+
+        claimed += 1;
+        total_claimed = total_claimed.checked_add(amount.amount)?;
+        total_tip = total_tip.checked_add(tip_amount)?;
+    }
+
+    let completed = addresses.len() < limit;
+    match addresses.last() {
+        Some(last) if !completed => AUTO_CLAIM_CURSOR.save(deps.storage, last)?,
+        _ => AUTO_CLAIM_CURSOR.remove(deps.storage),
+    }
+
+    if !total_tip.is_zero() {
+        CRANK_TIP_POOL.save(deps.storage, &tip_pool)?;
+        messages.push(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: coins(total_tip.u128(), &campaign.reward_denom),
+        });
+    }
+
+    Ok(Response::default()
+        .add_messages(messages)
+        .add_attributes(vec![
+            ("action", "crank".to_string()),
+            ("processed", addresses.len().to_string()),
+            ("claimed", claimed.to_string()),
+            (
+                "claimed_amount",
+                coin(total_claimed.u128(), &campaign.reward_denom).to_string(),
+            ),
+            (
+                "tip",
+                coin(total_tip.u128(), &campaign.reward_denom).to_string(),
+            ),
+            ("completed", completed.to_string()),
+        ]))
+}
+
+/// Updates the share of the auto-claimed rewards paid to crank callers.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `env`  - The env context
+/// * `info` - The message info
+/// * `tip` - The share of the auto-claimed rewards paid to the crank caller
+///
+/// # Returns
+/// * `Result<Response, ContractError>` - The response with attributes
+pub(crate) fn update_crank_tip(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    tip: Decimal,
+) -> Result<Response, ContractError> {
+    assert_authorized(deps.as_ref(), &info.sender)?;
+
+    ensure!(
+        tip <= MAX_CRANK_TIP,
+        ContractError::InvalidInput {
+            reason: format!("tip can't be greater than {MAX_CRANK_TIP}"),
+        }
+    );
+
+    CRANK_TIP.save(deps.storage, &tip)?;
+
+    record_audit_entry(
+        deps.storage,
+        &env,
+        &info.sender,
+        AuditAction::UpdateCrankTip { tip },
+        None,
+    )?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "update_crank_tip".to_string()),
+        ("tip", tip.to_string()),
+    ]))
+}
+
+/// Adds the reward tokens sent with the message to the crank tip pool, which pays the crank tips.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `env`  - The env context
+/// * `info` - The message info
+///
+/// # Returns
+/// * `Result<Response, ContractError>` - The response with attributes
+pub(crate) fn fund_crank_tips(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    assert_authorized(deps.as_ref(), &info.sender)?;
+
+    let campaign = CAMPAIGN
+        .may_load(deps.storage)?
+        .ok_or(ContractError::CampaignError {
+            reason: "there's not an active campaign".to_string(),
+        })?;
+
+    ensure!(
+        campaign.closed.is_none(),
+        ContractError::CampaignError {
+            reason: "campaign has been closed".to_string()
+        }
+    );
+
+    let amount = cw_utils::must_pay(&info, &campaign.reward_denom)?;
+    let tip_pool = CRANK_TIP_POOL
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .checked_add(amount)?;
+    CRANK_TIP_POOL.save(deps.storage, &tip_pool)?;
+
+    record_audit_entry(
+        deps.storage,
+        &env,
+        &info.sender,
+        AuditAction::FundCrankTips {
+            amount: coin(amount.u128(), &campaign.reward_denom),
+        },
+        None,
+    )?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "fund_crank_tips".to_string()),
+        (
+            "amount",
+            coin(amount.u128(), &campaign.reward_denom).to_string(),
+        ),
+        ("tip_pool", tip_pool.to_string()),
+    ]))
+}
+
+/// Exits the distributions with an early exit penalty, claiming the amount vested so far plus the
+/// unvested amount of those distributions minus the penalty. The penalty is sent to the treasury
+/// or redistributed among the addresses that haven't exited early, as configured on each
+/// distribution. The distributions without an early exit penalty keep vesting as usual.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `env`  - The env context
+/// * `info` - The message info
+///
+/// # Returns
+/// * `Result<Response, ContractError>` - The response with attributes
+pub(crate) fn exit_early(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let address = info.sender.to_string();
+
+    let mut campaign = claimable_campaign(deps.as_ref(), &env)?
+        .map_err(|reason| ContractError::from_claim_failure(reason, &address))?;
+
+    ensure!(
+        !EARLY_EXITS.has(deps.storage, &address),
+        ContractError::AlreadyExitedEarly {
+            address: address.clone(),
+        }
+    );
+
+    let vesting_time = get_vesting_time(deps.as_ref(), &campaign, &address, &env.block.time)?;
+    let distribution_type =
+        get_distribution_type(deps.as_ref(), &campaign, &address, &vesting_time)?;
+    let penalty_slots = distribution_type
+        .iter()
+        .enumerate()
+        .filter_map(|(slot, distribution)| {
+            distribution
+                .early_exit_penalty()
+                .map(|penalty| (slot, distribution, penalty))
+        })
+        .collect::<Vec<_>>();
+
+    ensure!(
+        !penalty_slots.is_empty(),
+        ContractError::CampaignError {
+            reason: "early exits are not enabled".to_string(),
+        }
+    );
+
+    // the vested amount is claimed as usual, nothing being vested doesn't prevent exiting
+    let mut claims_to_record =
+        match plan_claim(deps.as_ref(), &env, campaign.clone(), &address, None)? {
+            Ok(plan) => plan.claims_to_record,
+            Err(ClaimFailureReason::NothingToClaim) => {
+                if !helpers::is_compliant(deps.as_ref(), &campaign, &address)? {
+                    return Err(ContractError::from_claim_failure(
+                        ClaimFailureReason::NotCompliant,
+                        &address,
+                    ));
+                }
+                HashMap::new()
+            }
+            Err(reason) => return Err(ContractError::from_claim_failure(reason, &address)),
+        };
+    let vested_amount = helpers::total_claimed(&claims_to_record)?;
+
+    let allocation =
+        get_allocation(deps.as_ref(), &address)?.ok_or(ContractError::NoAllocationFound {
+            address: address.clone(),
+        })?;
+    let effective_allocation =
+        get_effective_allocation(deps.as_ref(), &campaign, &address)?.unwrap_or(allocation);
+    let previous_claims = get_claims_for_address(deps.as_ref(), address.clone())?;
+    let claims = helpers::aggregate_claims(&previous_claims, &claims_to_record)?;
+
+    // the dust left by rounding errors is only paid out once all the distributions have ended, so
+    // it's part of the unvested amount until then
+    let mut dust = effective_allocation.checked_sub(helpers::total_claimed(&claims)?)?;
+    for (slot, distribution) in distribution_type.iter().enumerate() {
+        let (entitlement, _) = helpers::calculate_vested_amount_for_distribution(
+            &vesting_time,
+            effective_allocation,
+            distribution,
+        )?;
+        let claimed = claims
+            .get(&slot)
+            .map_or(Uint128::zero(), |(amount, _)| *amount);
+        dust = dust.saturating_sub(entitlement.saturating_sub(claimed));
+    }
+
+    let mut slot_payouts: HashMap<DistributionSlot, Claim> = claims_to_record.clone();
+    let mut exit_payout = Uint128::zero();
+    let mut treasury_penalties: Vec<(String, Uint128)> = vec![];
+    let mut redistributed_penalty = Uint128::zero();
+
+    for (i, (slot, distribution, penalty)) in penalty_slots.iter().enumerate() {
+        let (entitlement, _) = helpers::calculate_vested_amount_for_distribution(
+            &vesting_time,
+            effective_allocation,
+            distribution,
+        )?;
+        let claimed = claims
+            .get(slot)
+            .map_or(Uint128::zero(), |(amount, _)| *amount);
+        let mut unvested = entitlement.saturating_sub(claimed);
+
+        if i == penalty_slots.len() - 1 {
+            unvested = unvested.checked_add(dust)?;
+        }
+
+        if unvested.is_zero() {
+            continue;
+        }
+
+        let penalty_amount = unvested.mul_ceil(penalty.percentage);
+        let payout = unvested.checked_sub(penalty_amount)?;
+
+        // the whole unvested amount is consumed, including the penalty
+        let (consumed, _) = claims_to_record
+            .entry(*slot)
+            .or_insert((Uint128::zero(), env.block.time.seconds()));
+        *consumed = consumed.checked_add(unvested)?;
+
+        let (paid, _) = slot_payouts
+            .entry(*slot)
+            .or_insert((Uint128::zero(), env.block.time.seconds()));
+        *paid = paid.checked_add(payout)?;
+
+        exit_payout = exit_payout.checked_add(payout)?;
+
+        match &penalty.destination {
+            PenaltyDestination::Treasury { address } => {
+                match treasury_penalties
+                    .iter_mut()
+                    .find(|(treasury, _)| treasury == address)
+                {
+                    Some((_, amount)) => *amount = amount.checked_add(penalty_amount)?,
+                    None => treasury_penalties.push((address.clone(), penalty_amount)),
+                }
+            }
+            PenaltyDestination::Redistribute {} => {
+                redistributed_penalty = redistributed_penalty.checked_add(penalty_amount)?;
+            }
+        }
+    }
+
+    let claimed_amount = vested_amount.checked_add(exit_payout)?;
+    let treasury_penalty = treasury_penalties
+        .iter()
+        .try_fold(Uint128::zero(), |total, (_, amount)| {
+            total.checked_add(*amount)
+        })?;
+    let penalty = treasury_penalty.checked_add(redistributed_penalty)?;
+
+    ensure!(
+        !claimed_amount.is_zero() || !penalty.is_zero(),
+        ContractError::NothingToClaim
+    );
+
+    let available_funds = helpers::available_funds(deps.as_ref(), &env, &campaign)?;
+    if claimed_amount.checked_add(treasury_penalty)? > available_funds.amount {
+        return Err(ContractError::from_claim_failure(
+            ClaimFailureReason::InsufficientFunds {
+                available: available_funds,
+            },
+            &address,
+        ));
+    }
+
+    let updated_claims: HashMap<DistributionSlot, Claim> =
+        helpers::aggregate_claims(&previous_claims, &claims_to_record)?
+            .into_iter()
+            .filter(|(slot, _)| claims_to_record.contains_key(slot))
+            .collect();
+    save_claims(deps.storage, &address, &updated_claims)?;
+    anchor_first_claim(deps.branch(), &campaign, &address, &vesting_time)?;
+
+    ensure!(
+        effective_allocation >= get_total_claims_amount_for_address(deps.as_ref(), &address)?,
+        ContractError::ExceededMaxClaimAmount
+    );
+
+    let claimed_coin = coin(claimed_amount.u128(), &campaign.reward_denom);
+    if !claimed_amount.is_zero() {
+        record_claim(
+            deps.storage,
+            &env,
+            &info.sender,
+            &address,
+            &address,
+            &claimed_coin,
+            &slot_payouts,
+        )?;
+    }
+
+    EARLY_EXITS.save(
+        deps.storage,
+        &address,
+        &EarlyExit {
+            timestamp: env.block.time.seconds(),
+            reward_per_share: campaign.reward_per_share,
+            penalty: coin(penalty.u128(), &campaign.reward_denom),
+        },
+    )?;
+
+    // the address no longer shares in the redistributed forfeitures, including its own penalty
+    retire_shares(deps.storage, allocation)?;
+    redistribute(deps.storage, &mut campaign, redistributed_penalty)?;
+
+    campaign.forfeited = campaign.forfeited.checked_add(penalty)?;
+
+    campaign.claimed.amount = campaign.claimed.amount.checked_add(claimed_amount)?;
+    CAMPAIGN.save(deps.storage, &campaign)?;
+
+    let mut messages = vec![];
+    if !claimed_amount.is_zero() {
+        messages.push(BankMsg::Send {
+            to_address: address.clone(),
+            amount: vec![claimed_coin.clone()],
+        });
+    }
+    for (treasury, amount) in treasury_penalties {
+        if !amount.is_zero() {
+            messages.push(BankMsg::Send {
+                to_address: treasury,
+                amount: coins(amount.u128(), &campaign.reward_denom),
+            });
+        }
+    }
+
+    Ok(Response::default()
+        .add_messages(messages)
+        .add_attributes(vec![
+            ("action", "exit_early".to_string()),
+            ("address", address),
+            ("claimed_amount", claimed_coin.to_string()),
+            (
+                "penalty",
+                coin(penalty.u128(), &campaign.reward_denom).to_string(),
+            ),
+            (
+                "redistributed",
+                coin(redistributed_penalty.u128(), &campaign.reward_denom).to_string(),
+            ),
+        ]))
+}
+
+/// Forfeits the remaining allocations of the given addresses, which can no longer claim. Before
+/// the campaign ends, only the allocations of blacklisted addresses can be forfeited. Afterwards,
+/// the allocations left unclaimed by any address can be forfeited too. The forfeited allocations
+/// are returned to the owner or redistributed among the remaining addresses, depending on the
+/// forfeiture mode of the campaign. If there are no remaining addresses to redistribute them
+/// among, they're returned to the owner.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `env`  - The env context
+/// * `info` - The message info
+/// * `addresses` - The addresses whose allocations are forfeited
+///
+/// # Returns
+/// * `Result<Response, ContractError>` - The response with attributes
+pub fn forfeit_allocations(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    addresses: Vec<String>,
+) -> Result<Response, ContractError> {
+    assert_authorized(deps.as_ref(), &info.sender)?;
+
+    ensure!(
+        addresses.len() <= MAX_FORFEITURE_BATCH_SIZE,
+        ContractError::BatchSizeLimitExceeded {
+            actual: addresses.len(),
+            max: MAX_FORFEITURE_BATCH_SIZE,
+        }
+    );
+
+    let mut campaign = CAMPAIGN
+        .may_load(deps.storage)?
+        .ok_or(ContractError::CampaignError {
+            reason: "there's not an active campaign".to_string(),
+        })?;
+
+    ensure!(
+        campaign.closed.is_none(),
+        ContractError::CampaignError {
+            reason: "campaign has already been closed".to_string()
+        }
+    );
+
+    let mut forfeited = Uint128::zero();
+
+    for address_raw in addresses.iter() {
+        let address = validate_raw_address(deps.as_ref(), address_raw)?;
+
+        let allocation = ALLOCATIONS.may_load(deps.storage, &address)?.ok_or(
+            ContractError::NoAllocationFound {
+                address: address.clone(),
+            },
+        )?;
+
+        ensure!(
+            !FORFEITURES.has(deps.storage, &address),
+            ContractError::AllocationForfeited {
+                address: address.clone(),
+            }
+        );
+        ensure!(
+            !EARLY_EXITS.has(deps.storage, &address),
+            ContractError::AlreadyExitedEarly {
+                address: address.clone(),
+            }
+        );
+
+        let reason = if is_blacklisted(deps.as_ref(), &env, &address)? {
+            ForfeitureReason::Blacklisted
+        } else if campaign.has_ended(&env.block.time) {
+            ForfeitureReason::Unclaimed
+        } else {
+            return Err(ContractError::CampaignError {
+                reason: format!(
+                    "cannot forfeit the allocation of {address} before the campaign has ended"
+                ),
+            });
+        };
+
+        let effective_allocation =
+            get_effective_allocation(deps.as_ref(), &campaign, &address)?.unwrap_or(allocation);
+        let amount = effective_allocation.saturating_sub(get_total_claims_amount_for_address(
+            deps.as_ref(),
+            &address,
+        )?);
+
+        FORFEITURES.save(
+            deps.storage,
+            &address,
+            &Forfeiture {
+                timestamp: env.block.time.seconds(),
+                reason: reason.clone(),
+                reward_per_share: campaign.reward_per_share,
+                amount: coin(amount.u128(), &campaign.reward_denom),
+            },
+        )?;
+        retire_shares(deps.storage, allocation)?;
+
+        record_audit_entry(
+            deps.storage,
+            &env,
+            &info.sender,
+            AuditAction::ForfeitAllocation { reason, amount },
+            Some(address),
+        )?;
+
+        forfeited = forfeited.checked_add(amount)?;
+    }
+
+    campaign.forfeited = campaign.forfeited.checked_add(forfeited)?;
+
+    let mut messages = vec![];
+    let redistributed = match campaign.forfeiture_mode {
+        ForfeitureMode::Redistribute => redistribute(deps.storage, &mut campaign, forfeited)?,
+        ForfeitureMode::Return => false,
+    };
+
+    // if the ownership has been renounced, the forfeited allocations stay in the contract
+    if let Some(owner) = cw_ownable::get_ownership(deps.storage)?.owner {
+        if !redistributed && !forfeited.is_zero() {
+            let available_funds = helpers::available_funds(deps.as_ref(), &env, &campaign)?;
+
+            ensure!(
+                forfeited <= available_funds.amount,
+                ContractError::CampaignError {
+                    reason: "no funds available to return the forfeited allocations".to_string(),
+                }
+            );
+
+            messages.push(BankMsg::Send {
+                to_address: owner.to_string(),
+                amount: coins(forfeited.u128(), &campaign.reward_denom),
+            });
+        }
+    }
+
+    CAMPAIGN.save(deps.storage, &campaign)?;
+
+    Ok(Response::default()
+        .add_messages(messages)
+        .add_attributes(vec![
+            ("action", "forfeit_allocations".to_string()),
+            ("count", addresses.len().to_string()),
+            (
+                "forfeited",
+                coin(forfeited.u128(), &campaign.reward_denom).to_string(),
+            ),
+            ("redistributed", redistributed.to_string()),
+        ]))
+}
+
+/// Triggers a milestone, unlocking the milestone distributions with its id from the current time.
+/// Only the owner or authorized wallets can trigger milestones, and each milestone can only be
+/// triggered once.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `env`  - The env context
+/// * `info` - The message info
+/// * `id` - The id of the milestone
+///
+/// # Returns
+/// * `Result<Response, ContractError>` - The response with attributes
+pub(crate) fn trigger_milestone(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: String,
+) -> Result<Response, ContractError> {
+    assert_authorized(deps.as_ref(), &info.sender)?;
+
+    let mut campaign = CAMPAIGN
+        .may_load(deps.storage)?
+        .ok_or(ContractError::CampaignError {
+            reason: "there's not an active campaign".to_string(),
+        })?;
+
+    ensure!(
+        campaign.closed.is_none(),
+        ContractError::CampaignError {
+            reason: "campaign has already been closed".to_string()
+        }
+    );
+
+    ensure!(
+        campaign.has_milestone(&id),
+        ContractError::InvalidInput {
+            reason: format!("unknown milestone {id}"),
+        }
+    );
+
+    ensure!(
+        !campaign
+            .milestones
+            .iter()
+            .any(|milestone| milestone.id == id),
+        ContractError::CampaignError {
+            reason: format!("milestone {id} has already been triggered"),
+        }
+    );
+
+    campaign.milestones.push(TriggeredMilestone {
+        id: id.clone(),
+        time: env.block.time.seconds(),
+    });
+    CAMPAIGN.save(deps.storage, &campaign)?;
+
+    record_audit_entry(
+        deps.storage,
+        &env,
+        &info.sender,
+        AuditAction::TriggerMilestone { id: id.clone() },
+        None,
+    )?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "trigger_milestone".to_string()),
+        ("milestone", id),
+        ("time", env.block.time.seconds().to_string()),
+    ]))
+}
+
+/// Finalizes the uploaded allocations, checking them against the checksum, count and total the
+/// caller expects. Once finalized, no more allocations can be uploaded or removed, only replaced.
+/// Only the owner or authorized wallets can finalize the allocations, and they can only be
+/// finalized once.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `env`  - The env context
+/// * `info` - The message info
+/// * `expected_hash` - The expected checksum of the uploaded allocations
+/// * `expected_count` - The expected number of uploaded allocations
+/// * `expected_total` - The expected sum of the uploaded allocations
+///
+/// # Returns
+/// * `Result<Response, ContractError>` - The response with attributes
+pub(crate) fn finalize_allocations(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    expected_hash: HexBinary,
+    expected_count: u64,
+    expected_total: Uint128,
+) -> Result<Response, ContractError> {
+    assert_authorized(deps.as_ref(), &info.sender)?;
+
+    let mut checksum = AllocationChecksum::load(deps.storage)?;
+
+    ensure!(
+        checksum.finalized_at.is_none(),
+        ContractError::AllocationsFinalized
+    );
+
+    ensure!(
+        checksum.count == expected_count,
+        ContractError::AllocationsMismatch {
+            field: "count".to_string(),
+            expected: expected_count.to_string(),
+            actual: checksum.count.to_string(),
+        }
+    );
+
+    ensure!(
+        checksum.total == expected_total,
+        ContractError::AllocationsMismatch {
+            field: "total".to_string(),
+            expected: expected_total.to_string(),
+            actual: checksum.total.to_string(),
+        }
+    );
+
+    let hash = HexBinary::from(checksum.hash);
+    ensure!(
+        hash == expected_hash,
+        ContractError::AllocationsMismatch {
+            field: "hash".to_string(),
+            expected: expected_hash.to_hex(),
+            actual: hash.to_hex(),
+        }
+    );
+
+    checksum.finalized_at = Some(env.block.time.seconds());
+    ALLOCATION_CHECKSUM.save(deps.storage, &checksum)?;
+
+    record_audit_entry(
+        deps.storage,
+        &env,
+        &info.sender,
+        AuditAction::FinalizeAllocations {
+            count: checksum.count,
+        },
+        None,
+    )?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "finalize_allocations".to_string()),
+        ("hash", hash.to_hex()),
+        ("count", checksum.count.to_string()),
+        ("total", checksum.total.to_string()),
+    ]))
+}
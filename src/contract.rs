@@ -1,10 +1,10 @@
-use cosmwasm_std::{entry_point, to_json_binary};
-use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response};
+use cosmwasm_std::{ensure, entry_point, to_json_binary, Addr};
+use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response};
 use cw2::set_contract_version;
 
-use crate::{commands, queries, validate_contract};
+use crate::{commands, ibc, migrations, queries, state, validate_contract};
 use mantra_claimdrop_std::error::ContractError;
-use mantra_claimdrop_std::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use mantra_claimdrop_std::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SudoMsg};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "mantra_claimdrop-contract";
@@ -35,37 +35,164 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    // the contract can't be used until the data migration completes
+    if !matches!(
+        msg,
+        ExecuteMsg::ContinueMigration { .. } | ExecuteMsg::UpdateOwnership(_)
+    ) {
+        migrations::assert_no_migration_in_progress(deps.storage)?;
+    }
+
+    ensure!(
+        !state::is_paused(deps.storage)?,
+        ContractError::ContractPaused
+    );
+
     match msg {
         ExecuteMsg::ManageCampaign { action } => commands::manage_campaign(deps, env, info, action),
-        ExecuteMsg::Claim { receiver, amount } => {
+        ExecuteMsg::Claim {
+            receiver,
+            amount,
+            ibc_destination,
+        } => {
             cw_utils::nonpayable(&info)?;
-            commands::claim(deps, env, info, receiver, amount)
+            commands::claim(deps, env, info, receiver, amount, ibc_destination)
         }
         ExecuteMsg::AddAllocations { allocations } => {
             cw_utils::nonpayable(&info)?;
             commands::add_allocations(deps, env, info, allocations)
         }
+        ExecuteMsg::AddAllocationsPacked { data } => {
+            cw_utils::nonpayable(&info)?;
+            commands::add_allocations_packed(deps, env, info, data)
+        }
         ExecuteMsg::ReplaceAddress {
             old_address,
             new_address,
         } => {
             cw_utils::nonpayable(&info)?;
-            commands::replace_address(deps, info, old_address, new_address)
+            commands::replace_address(deps, env, info, old_address, new_address)
         }
         ExecuteMsg::RemoveAddress { address } => {
             cw_utils::nonpayable(&info)?;
             commands::remove_address(deps, env, info, address)
         }
-        ExecuteMsg::BlacklistAddress { address, blacklist } => {
+        ExecuteMsg::BlacklistAddress {
+            address,
+            blacklist,
+            reason,
+            note,
+            expiration,
+            freeze_vesting,
+        } => {
+            cw_utils::nonpayable(&info)?;
+            commands::blacklist_address(
+                deps,
+                env,
+                info,
+                address,
+                blacklist,
+                reason,
+                note,
+                expiration,
+                freeze_vesting,
+            )
+        }
+        ExecuteMsg::BlacklistAddresses {
+            addresses,
+            blacklist,
+            reason,
+            note,
+            expiration,
+            freeze_vesting,
+        } => {
             cw_utils::nonpayable(&info)?;
-            commands::blacklist_address(deps, info, address, blacklist)
+            commands::blacklist_addresses(
+                deps,
+                env,
+                info,
+                addresses,
+                blacklist,
+                reason,
+                note,
+                expiration,
+                freeze_vesting,
+            )
         }
         ExecuteMsg::ManageAuthorizedWallets {
             addresses,
             authorized,
         } => {
             cw_utils::nonpayable(&info)?;
-            commands::manage_authorized_wallets(deps, info, addresses, authorized)
+            commands::manage_authorized_wallets(deps, env, info, addresses, authorized)
+        }
+        ExecuteMsg::SeedClaims { claims } => {
+            cw_utils::nonpayable(&info)?;
+            commands::seed_claims(deps, env, info, claims)
+        }
+        ExecuteMsg::ImportState { entries } => {
+            cw_utils::nonpayable(&info)?;
+            commands::import_state(deps, env, info, entries)
+        }
+        ExecuteMsg::ManageIbcChannel { channel_id, config } => {
+            cw_utils::nonpayable(&info)?;
+            commands::manage_ibc_channel(deps, env, info, channel_id, config)
+        }
+        ExecuteMsg::ApproveClaimer {
+            operator,
+            expires,
+            max_amount,
+        } => {
+            cw_utils::nonpayable(&info)?;
+            commands::approve_claimer(deps, env, info, operator, expires, max_amount)
+        }
+        ExecuteMsg::RevokeClaimer { operator } => {
+            cw_utils::nonpayable(&info)?;
+            commands::revoke_claimer(deps, info, operator)
+        }
+        ExecuteMsg::AutoClaim { enabled } => {
+            cw_utils::nonpayable(&info)?;
+            commands::auto_claim(deps, info, enabled)
+        }
+        ExecuteMsg::Crank { limit } => {
+            cw_utils::nonpayable(&info)?;
+            commands::crank(deps, env, info, limit)
+        }
+        ExecuteMsg::UpdateCrankTip { tip } => {
+            cw_utils::nonpayable(&info)?;
+            commands::update_crank_tip(deps, env, info, tip)
+        }
+        ExecuteMsg::FundCrankTips {} => commands::fund_crank_tips(deps, env, info),
+        ExecuteMsg::ForfeitAllocations { addresses } => {
+            cw_utils::nonpayable(&info)?;
+            commands::forfeit_allocations(deps, env, info, addresses)
+        }
+        ExecuteMsg::ExitEarly {} => {
+            cw_utils::nonpayable(&info)?;
+            commands::exit_early(deps, env, info)
+        }
+        ExecuteMsg::TriggerMilestone { id } => {
+            cw_utils::nonpayable(&info)?;
+            commands::trigger_milestone(deps, env, info, id)
+        }
+        ExecuteMsg::FinalizeAllocations {
+            expected_hash,
+            expected_count,
+            expected_total,
+        } => {
+            cw_utils::nonpayable(&info)?;
+            commands::finalize_allocations(
+                deps,
+                env,
+                info,
+                expected_hash,
+                expected_count,
+                expected_total,
+            )
+        }
+        ExecuteMsg::ContinueMigration { batch_size } => {
+            cw_utils::nonpayable(&info)?;
+            commands::continue_migration(deps, env, info, batch_size)
         }
         ExecuteMsg::UpdateOwnership(action) => {
             cw_utils::nonpayable(&info)?;
@@ -84,11 +211,44 @@ pub fn execute(
 
 #[entry_point]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    // the claims, blacklist entries and allocation totals can't be read until their migration
+    // completes
+    if matches!(
+        msg,
+        QueryMsg::Rewards { .. }
+            | QueryMsg::RewardsBreakdown { .. }
+            | QueryMsg::RewardsAt { .. }
+            | QueryMsg::VestingSchedule { .. }
+            | QueryMsg::SimulateClaim { .. }
+            | QueryMsg::Claimed { .. }
+            | QueryMsg::IsBlacklisted { .. }
+            | QueryMsg::Blacklist { .. }
+            | QueryMsg::ExportState { .. }
+    ) {
+        migrations::assert_no_migration_in_progress(deps.storage)?;
+    }
+
     match msg {
         QueryMsg::Campaign {} => Ok(to_json_binary(&queries::query_campaign(deps)?)?),
         QueryMsg::Rewards { receiver } => Ok(to_json_binary(&queries::query_rewards(
             deps, env, receiver,
         )?)?),
+        QueryMsg::RewardsBreakdown { receiver } => Ok(to_json_binary(
+            &queries::query_rewards_breakdown(deps, env, receiver)?,
+        )?),
+        QueryMsg::RewardsAt { address, timestamp } => Ok(to_json_binary(
+            &queries::query_rewards_at(deps, env, address, timestamp)?,
+        )?),
+        QueryMsg::VestingSchedule { address, points } => Ok(to_json_binary(
+            &queries::query_vesting_schedule(deps, env, address, points)?,
+        )?),
+        QueryMsg::SimulateClaim {
+            sender,
+            receiver,
+            amount,
+        } => Ok(to_json_binary(&queries::query_simulate_claim(
+            deps, env, sender, receiver, amount,
+        )?)?),
         QueryMsg::Ownership {} => Ok(to_json_binary(&cw_ownable::get_ownership(deps.storage)?)?),
         QueryMsg::Claimed {
             address,
@@ -108,20 +268,139 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractErro
             limit,
         )?)?),
         QueryMsg::IsBlacklisted { address } => Ok(to_json_binary(&queries::query_is_blacklisted(
-            deps, address,
+            deps, env, address,
         )?)?),
+        QueryMsg::Blacklist { start_after, limit } => Ok(to_json_binary(
+            &queries::query_blacklist(deps, start_after, limit)?,
+        )?),
         QueryMsg::IsAuthorized { address } => Ok(to_json_binary(&queries::query_is_authorized(
             deps, address,
         )?)?),
         QueryMsg::AuthorizedWallets { start_after, limit } => Ok(to_json_binary(
             &queries::query_authorized_wallets(deps, start_after, limit)?,
         )?),
+        QueryMsg::AuditLog {
+            action,
+            subject,
+            start_after,
+            limit,
+        } => Ok(to_json_binary(&queries::query_audit_log(
+            deps,
+            action,
+            subject,
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::ClaimHistory {
+            address,
+            start_after,
+            limit,
+        } => Ok(to_json_binary(&queries::query_claim_history(
+            deps,
+            address,
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::ExportState { start_after, limit } => Ok(to_json_binary(
+            &queries::query_export_state(deps, start_after, limit)?,
+        )?),
+        QueryMsg::AllocationChecksum {} => {
+            Ok(to_json_binary(&queries::query_allocation_checksum(deps)?)?)
+        }
+        QueryMsg::IbcChannels { start_after, limit } => Ok(to_json_binary(
+            &queries::query_ibc_channels(deps, start_after, limit)?,
+        )?),
+        QueryMsg::ClaimApprovals {
+            owner,
+            start_after,
+            limit,
+        } => Ok(to_json_binary(&queries::query_claim_approvals(
+            deps,
+            owner,
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::AutoClaim { start_after, limit } => Ok(to_json_binary(
+            &queries::query_auto_claim(deps, start_after, limit)?,
+        )?),
+        QueryMsg::IsPaused {} => Ok(to_json_binary(&queries::query_is_paused(deps)?)?),
+        QueryMsg::MigrationStatus {} => {
+            Ok(to_json_binary(&queries::query_migration_status(deps)?)?)
+        }
+    }
+}
+
+#[entry_point]
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    let sender = Addr::unchecked(commands::SUDO_SENDER);
+
+    // governance can still recover the ownership, e.g. to continue the migration, and pause the
+    // contract while a data migration is in progress, as neither touches the data being migrated
+    if !matches!(
+        msg,
+        SudoMsg::ResetOwner { .. } | SudoMsg::Pause {} | SudoMsg::Resume {}
+    ) {
+        migrations::assert_no_migration_in_progress(deps.storage)?;
+    }
+
+    match msg {
+        SudoMsg::ForceCloseCampaign { refund_recipient } => {
+            let refund_recipient = deps.api.addr_validate(&refund_recipient)?;
+            commands::close_campaign(deps, env, &sender, Some(refund_recipient))
+        }
+        SudoMsg::ResetOwner { owner } => commands::reset_owner(deps, env, owner),
+        SudoMsg::Pause {} => commands::set_paused(deps, env, true),
+        SudoMsg::Resume {} => commands::set_paused(deps, env, false),
+        SudoMsg::BlacklistAddresses {
+            addresses,
+            blacklist,
+            reason,
+            note,
+            expiration,
+            freeze_vesting,
+        } => commands::blacklist_addresses_unchecked(
+            deps,
+            env,
+            &sender,
+            addresses,
+            blacklist,
+            reason,
+            note,
+            expiration,
+            freeze_vesting,
+        ),
+    }
+}
+
+#[entry_point]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        ibc::IBC_TRANSFER_REPLY_ID => ibc::track_transfer(deps, msg),
+        id => Err(ContractError::InvalidInput {
+            reason: format!("unknown reply id {id}"),
+        }),
     }
 }
 
 #[entry_point]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
     validate_contract!(deps, CONTRACT_NAME, CONTRACT_VERSION);
+    let from_version = cw2::get_contract_version(deps.storage)?.version;
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    Ok(Response::default())
+
+    migrations::start_migration(deps.storage, &from_version, CONTRACT_VERSION)?;
+    let (processed, progress) = migrations::run_migration(
+        deps.storage,
+        &env,
+        msg.batch_size
+            .unwrap_or(migrations::DEFAULT_MIGRATION_BATCH_SIZE),
+    )?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "migrate".to_string()),
+        ("from_version", from_version),
+        ("to_version", CONTRACT_VERSION.to_string()),
+        ("processed", processed.to_string()),
+        ("completed", progress.is_none().to_string()),
+    ]))
 }
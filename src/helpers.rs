@@ -1,10 +1,35 @@
 use std::collections::HashMap;
 
-use cosmwasm_std::{ensure, Coin, Decimal256, Deps, Timestamp, Uint128, Uint256};
+use cosmwasm_std::{
+    ensure, CanonicalAddr, Coin, Decimal256, Deps, Env, Timestamp, Uint128, Uint256,
+};
+use cw_utils::Expiration;
 
-use crate::state::{get_claims_for_address, Claim, DistributionSlot};
+use crate::ibc;
+use crate::state::{get_claims_for_address, Claim, DistributionSlot, CRANK_TIP_POOL};
 use mantra_claimdrop_std::error::ContractError;
-use mantra_claimdrop_std::msg::{Campaign, CampaignParams, DistributionType};
+use mantra_claimdrop_std::msg::{
+    AllocationRow, Campaign, CampaignParams, ComplianceMode, ComplianceQueryMsg, DistributionType,
+    IbcDestination, IsAllowedResponse,
+};
+
+/// Returns the funds available for claims, i.e. the balance of the contract in the reward denom
+/// minus the funds set aside in the crank tip pool.
+pub(crate) fn available_funds(
+    deps: Deps,
+    env: &Env,
+    campaign: &Campaign,
+) -> Result<Coin, ContractError> {
+    let balance = deps
+        .querier
+        .query_balance(env.contract.address.clone(), &campaign.reward_denom)?;
+    let tip_pool = CRANK_TIP_POOL.may_load(deps.storage)?.unwrap_or_default();
+
+    Ok(Coin {
+        denom: balance.denom,
+        amount: balance.amount.saturating_sub(tip_pool),
+    })
+}
 
 /// Validates the provided campaign parameters are valid.
 pub(crate) fn validate_campaign_params(
@@ -23,10 +48,13 @@ pub(crate) fn validate_campaign_params(
 /// Constant used for the fallback distribution slot
 const FALLBACK_DISTRIBUTION_SLOT: usize = 0usize;
 
-/// Calculates the amount a user can claim at this point in time
+/// Calculates the amount a user can claim at this point in time. The total claimable amount is the
+/// effective allocation of the user, including its share of the redistributed forfeitures, and the
+/// distribution types are the ones its allocation vests on.
 pub(crate) fn compute_claimable_amount(
     deps: Deps,
     campaign: &Campaign,
+    distribution_type: &[DistributionType],
     current_time: &Timestamp,
     address: &str,
     total_claimable_amount: Uint128,
@@ -37,9 +65,7 @@ pub(crate) fn compute_claimable_amount(
     if campaign.has_started(current_time) {
         let previous_claims_for_address = get_claims_for_address(deps, address.to_string())?;
 
-        for (distribution_slot, distribution) in
-            campaign.distribution_type.iter().enumerate().clone()
-        {
+        for (distribution_slot, distribution) in distribution_type.iter().enumerate() {
             // skip distributions that have not started yet
             if !distribution.has_started(current_time) {
                 continue;
@@ -64,10 +90,10 @@ pub(crate) fn compute_claimable_amount(
                 previous_claims_for_address.get(&distribution_slot);
 
             let claim_amount = calculate_claim_amount_for_distribution(
-                &current_time,
+                current_time,
                 total_claimable_amount,
-                &distribution,
-                &previous_claim_for_address_for_distribution,
+                distribution,
+                previous_claim_for_address_for_distribution,
             )?;
 
             // nothing to claim for the current distribution, skip
@@ -81,7 +107,7 @@ pub(crate) fn compute_claimable_amount(
         }
 
         let (rounding_error_compensation_amount, slot) = get_compensation_for_rounding_errors(
-            campaign,
+            distribution_type,
             current_time,
             total_claimable_amount,
             previous_claims_for_address,
@@ -119,29 +145,50 @@ pub(crate) fn compute_claimable_amount(
 
 /// Calculates the claimable amount for a given distribution, total amount and previous claim.
 fn calculate_claim_amount_for_distribution(
-    current_time: &&Timestamp,
+    current_time: &Timestamp,
     total_user_allocation: Uint128,
-    distribution_type: &&DistributionType,
-    previous_claim_for_this_slot: &Option<&Claim>,
+    distribution_type: &DistributionType,
+    previous_claim_for_this_slot: Option<&Claim>,
 ) -> Result<Uint128, ContractError> {
+    let (_, total_vested_for_slot_at_current_time) = calculate_vested_amount_for_distribution(
+        current_time,
+        total_user_allocation,
+        distribution_type,
+    )?;
+
+    let already_claimed_for_this_slot =
+        previous_claim_for_this_slot.map_or(Uint128::zero(), |(amount, _)| *amount);
+
+    Ok(total_vested_for_slot_at_current_time.saturating_sub(already_claimed_for_this_slot))
+}
+
+/// Calculates the amount of a user allocation assigned to a given distribution, and how much of
+/// it has vested at the given time, regardless of previous claims. Returns (entitlement, vested).
+pub(crate) fn calculate_vested_amount_for_distribution(
+    current_time: &Timestamp,
+    total_user_allocation: Uint128,
+    distribution_type: &DistributionType,
+) -> Result<(Uint128, Uint128), ContractError> {
+    let amount_allocated_to_this_slot =
+        compute_slot_entitlement(total_user_allocation, distribution_type)?;
+
+    if !distribution_type.has_started(current_time) {
+        return Ok((amount_allocated_to_this_slot, Uint128::zero()));
+    }
+
     match distribution_type {
         DistributionType::LinearVesting {
-            percentage,
             start_time,
             end_time,
+            cliff_duration,
             ..
         } => {
-            let amount_allocated_to_this_slot = Uint128::try_from(
-                Decimal256::from(*percentage)
-                    .checked_mul(Decimal256::from_ratio(
-                        Uint256::from_uint128(total_user_allocation),
-                        Uint256::one(),
-                    ))?
-                    .to_uint_floor(),
-            )?;
-
-            let already_claimed =
-                previous_claim_for_this_slot.map_or(Uint128::zero(), |(amount, _)| *amount);
+            // nothing is vested until the cliff period has passed
+            if let Some(cliff_duration) = cliff_duration {
+                if current_time.seconds() < start_time + cliff_duration {
+                    return Ok((amount_allocated_to_this_slot, Uint128::zero()));
+                }
+            }
 
             let distribution_duration = end_time.saturating_sub(*start_time);
 
@@ -172,38 +219,78 @@ fn calculate_claim_amount_for_distribution(
                 .to_uint_floor(),
             )?;
 
-            Ok(total_vested_for_slot_at_current_time.saturating_sub(already_claimed))
+            Ok((
+                amount_allocated_to_this_slot,
+                total_vested_for_slot_at_current_time,
+            ))
         }
-        DistributionType::LumpSum { percentage, .. } => {
-            let total_entitlement_for_lumpsum_slot = Uint128::try_from(
-                Decimal256::from(*percentage)
-                    .checked_mul(Decimal256::from_ratio(
-                        Uint256::from_uint128(total_user_allocation),
-                        Uint256::one(),
-                    ))?
-                    .to_uint_floor(),
-            )?;
+        DistributionType::LumpSum { .. } => {
+            Ok((amount_allocated_to_this_slot, amount_allocated_to_this_slot))
+        }
+        // nothing is vested until the distribution is anchored or triggered
+        DistributionType::RelativeLinearVesting { .. }
+        | DistributionType::RelativeLumpSum { .. }
+        | DistributionType::Milestone { .. } => {
+            Ok((amount_allocated_to_this_slot, Uint128::zero()))
+        }
+    }
+}
 
-            let already_claimed_for_this_slot =
-                previous_claim_for_this_slot.map_or(Uint128::zero(), |(amount, _)| *amount);
+/// Calculates the amount of a user allocation assigned to a given distribution, rounded down.
+pub(crate) fn compute_slot_entitlement(
+    total_user_allocation: Uint128,
+    distribution_type: &DistributionType,
+) -> Result<Uint128, ContractError> {
+    Ok(Uint128::try_from(
+        Decimal256::from(distribution_type.percentage())
+            .checked_mul(Decimal256::from_ratio(
+                Uint256::from_uint128(total_user_allocation),
+                Uint256::one(),
+            ))?
+            .to_uint_floor(),
+    )?)
+}
 
-            let newly_claimable =
-                total_entitlement_for_lumpsum_slot.saturating_sub(already_claimed_for_this_slot);
-            Ok(newly_claimable)
-        }
+/// Calculates the total amount of a user allocation vested at the given time, regardless of
+/// previous claims. Once all distribution types have ended the whole allocation is vested,
+/// including the dust left by rounding errors.
+pub(crate) fn compute_vested_amount(
+    campaign: &Campaign,
+    distribution_type: &[DistributionType],
+    current_time: &Timestamp,
+    total_user_allocation: Uint128,
+) -> Result<Uint128, ContractError> {
+    if !campaign.has_started(current_time) {
+        return Ok(Uint128::zero());
     }
+
+    if distribution_types_ended(distribution_type, current_time) {
+        return Ok(total_user_allocation);
+    }
+
+    let mut vested = Uint128::zero();
+    for distribution in distribution_type.iter() {
+        let (_, vested_for_distribution) = calculate_vested_amount_for_distribution(
+            current_time,
+            total_user_allocation,
+            distribution,
+        )?;
+        vested = vested.checked_add(vested_for_distribution)?;
+    }
+
+    Ok(vested)
 }
 
 /// Returns the compensation for rounding errors if the distribution types have ended. This is to claim
 /// the potential remaining dust in the campaign for the user due to rounding errors.
 fn get_compensation_for_rounding_errors(
-    campaign: &Campaign,
+    distribution_type: &[DistributionType],
     current_time: &Timestamp,
     total_claimable_amount: Uint128,
     previous_claims_for_address: HashMap<DistributionSlot, Claim>,
     new_claims: &HashMap<DistributionSlot, Claim>,
 ) -> Result<(Uint128, DistributionSlot), ContractError> {
-    if distribution_types_ended(campaign, current_time) {
+    if distribution_types_ended(distribution_type, current_time) {
         let updated_claims = aggregate_claims(&previous_claims_for_address, new_claims)?;
 
         let total_claimed = updated_claims
@@ -231,10 +318,13 @@ fn get_compensation_for_rounding_errors(
 }
 
 /// Checks if all distribution types have ended
-fn distribution_types_ended(campaign: &Campaign, current_time: &Timestamp) -> bool {
+fn distribution_types_ended(
+    distribution_type: &[DistributionType],
+    current_time: &Timestamp,
+) -> bool {
     let mut distribution_types_ended = true;
 
-    for distribution_type in campaign.distribution_type.iter() {
+    for distribution_type in distribution_type.iter() {
         match distribution_type {
             DistributionType::LinearVesting { end_time, .. } => {
                 if *end_time > current_time.seconds() {
@@ -249,12 +339,44 @@ fn distribution_types_ended(campaign: &Campaign, current_time: &Timestamp) -> bo
                     distribution_types_ended = false;
                 }
             }
+            DistributionType::RelativeLinearVesting { .. }
+            | DistributionType::RelativeLumpSum { .. }
+            | DistributionType::Milestone { .. } => distribution_types_ended = false,
         }
     }
 
     distribution_types_ended
 }
 
+/// Checks the given address against the compliance registry of the campaign, according to the
+/// campaign's compliance mode.
+pub(crate) fn is_compliant(
+    deps: Deps,
+    campaign: &Campaign,
+    address: &str,
+) -> Result<bool, ContractError> {
+    if campaign.compliance_mode == ComplianceMode::Disabled {
+        return Ok(true);
+    }
+
+    let compliance_contract =
+        campaign
+            .compliance_contract
+            .as_ref()
+            .ok_or(ContractError::CampaignError {
+                reason: "compliance contract not set".to_string(),
+            })?;
+
+    let response: IsAllowedResponse = deps.querier.query_wasm_smart(
+        compliance_contract,
+        &ComplianceQueryMsg::IsAllowed {
+            address: address.to_string(),
+        },
+    )?;
+
+    Ok(campaign.compliance_mode.permits(&response.status))
+}
+
 /// Validates the raw address string.
 pub fn validate_raw_address(deps: Deps, address_raw: &str) -> Result<String, ContractError> {
     if let Ok(addr) = deps.api.addr_validate(address_raw) {
@@ -296,6 +418,225 @@ pub fn validate_address_placeholder(placeholder: &str) -> Result<String, Contrac
     Ok(address)
 }
 
+// Maximum allowed length for the note of a blacklist entry.
+pub const MAX_BLACKLIST_NOTE_LEN: usize = 256;
+
+/// Validates the parameters of a blacklist entry.
+pub(crate) fn validate_blacklist_params(
+    env: &Env,
+    note: Option<&str>,
+    expiration: Option<&Expiration>,
+    freeze_vesting: bool,
+) -> Result<(), ContractError> {
+    if let Some(note) = note {
+        ensure!(
+            !note.is_empty() && note.len() <= MAX_BLACKLIST_NOTE_LEN,
+            ContractError::InvalidInput {
+                reason: format!(
+                    "note must be between 1 and {} characters long (got {})",
+                    MAX_BLACKLIST_NOTE_LEN,
+                    note.len()
+                ),
+            }
+        );
+    }
+
+    if let Some(expiration) = expiration {
+        ensure!(
+            !expiration.is_expired(&env.block),
+            ContractError::InvalidInput {
+                reason: format!("expiration {expiration} is already expired"),
+            }
+        );
+
+        // the frozen period is measured in time, so it can't end at a block height
+        ensure!(
+            !(freeze_vesting && matches!(expiration, Expiration::AtHeight(_))),
+            ContractError::InvalidInput {
+                reason: "vesting can only be frozen until a time expiration".to_string(),
+            }
+        );
+    }
+
+    Ok(())
+}
+
+/// Validates the destination of a claim paid out over ICS-20.
+pub(crate) fn validate_ibc_destination(destination: &IbcDestination) -> Result<(), ContractError> {
+    ensure!(
+        !destination.channel_id.is_empty(),
+        ContractError::InvalidIbcTransfer {
+            reason: "channel_id can't be empty".to_string(),
+        }
+    );
+
+    // remote addresses can't be validated by this chain, they only need to fit a placeholder
+    ensure!(
+        !destination.remote_address.is_empty()
+            && destination.remote_address.len() <= MAX_PLACEHOLDER_ADDRESS_LEN,
+        ContractError::InvalidIbcTransfer {
+            reason: format!(
+                "remote_address must be between 1 and {} characters long (got {})",
+                MAX_PLACEHOLDER_ADDRESS_LEN,
+                destination.remote_address.len()
+            ),
+        }
+    );
+
+    if let Some(timeout) = destination.timeout {
+        ensure!(
+            timeout > 0 && timeout <= ibc::MAX_IBC_TRANSFER_TIMEOUT_SECONDS,
+            ContractError::InvalidIbcTransfer {
+                reason: format!(
+                    "timeout must be between 1 and {} seconds (got {timeout})",
+                    ibc::MAX_IBC_TRANSFER_TIMEOUT_SECONDS
+                ),
+            }
+        );
+    }
+
+    Ok(())
+}
+
+/// Returns the sequence of the packet sent by an ICS-20 transfer, decoded from the protobuf
+/// encoded MsgTransferResponse.
+pub(crate) fn parse_transfer_sequence(data: &[u8]) -> Result<u64, ContractError> {
+    let mut bytes = data.iter().copied();
+
+    while let Some(key) = read_varint(&mut bytes)? {
+        match (key >> 3, key & 0x7) {
+            // sequence, a varint
+            (1, 0) => return read_varint(&mut bytes)?.ok_or(invalid_transfer_response()),
+            // other varint fields
+            (_, 0) => {
+                read_varint(&mut bytes)?.ok_or(invalid_transfer_response())?;
+            }
+            // other length-delimited fields
+            (_, 2) => {
+                let len = read_varint(&mut bytes)?.ok_or(invalid_transfer_response())?;
+                for _ in 0..len {
+                    bytes.next().ok_or(invalid_transfer_response())?;
+                }
+            }
+            _ => return Err(invalid_transfer_response()),
+        }
+    }
+
+    Err(invalid_transfer_response())
+}
+
+/// Reads a protobuf varint. Returns None if there are no bytes left.
+fn read_varint(bytes: &mut impl Iterator<Item = u8>) -> Result<Option<u64>, ContractError> {
+    let mut value = 0u64;
+
+    for shift in (0..64).step_by(7) {
+        let Some(byte) = bytes.next() else {
+            return if shift == 0 {
+                Ok(None)
+            } else {
+                Err(invalid_transfer_response())
+            };
+        };
+
+        value |= u64::from(byte & 0x7f) << shift;
+
+        if byte & 0x80 == 0 {
+            return Ok(Some(value));
+        }
+    }
+
+    Err(invalid_transfer_response())
+}
+
+fn invalid_transfer_response() -> ContractError {
+    ContractError::InvalidIbcTransfer {
+        reason: "invalid MsgTransferResponse".to_string(),
+    }
+}
+
+/// The length of the canonical addresses in packed allocations
+const PACKED_CANONICAL_ADDRESS_LEN: usize = 20;
+
+/// Decodes the allocations uploaded with the AddAllocationsPacked message into (address, amount)
+/// rows. See [mantra_claimdrop_std::msg::ExecuteMsg::AddAllocationsPacked] for the format.
+pub(crate) fn decode_packed_allocations(
+    deps: Deps,
+    data: &[u8],
+) -> Result<Vec<AllocationRow>, ContractError> {
+    ensure!(
+        !data.is_empty(),
+        ContractError::InvalidInput {
+            reason: "packed allocations can't be empty".to_string(),
+        }
+    );
+
+    let mut bytes = data.iter().copied();
+    let mut allocations = vec![];
+
+    while let Some(header) = read_packed_varint(&mut bytes)? {
+        let address = if header == 0 {
+            let canonical = read_packed_bytes(&mut bytes, PACKED_CANONICAL_ADDRESS_LEN)?;
+            deps.api
+                .addr_humanize(&CanonicalAddr::from(canonical))?
+                .to_string()
+        } else {
+            let len = usize::try_from(header).map_err(|_| invalid_packed_allocations())?;
+            String::from_utf8(read_packed_bytes(&mut bytes, len)?)
+                .map_err(|_| invalid_packed_allocations())?
+        };
+
+        let amount = read_packed_varint(&mut bytes)?.ok_or(invalid_packed_allocations())?;
+        allocations.push(AllocationRow::Default(address, Uint128::new(amount)));
+    }
+
+    Ok(allocations)
+}
+
+/// Reads an unsigned LEB128 varint of up to 128 bits. Returns None if there are no bytes left.
+fn read_packed_varint(bytes: &mut impl Iterator<Item = u8>) -> Result<Option<u128>, ContractError> {
+    let mut value = 0u128;
+
+    for shift in (0..128).step_by(7) {
+        let Some(byte) = bytes.next() else {
+            return if shift == 0 {
+                Ok(None)
+            } else {
+                Err(invalid_packed_allocations())
+            };
+        };
+
+        let bits = u128::from(byte & 0x7f);
+        // the last byte can only hold the 2 bits left
+        if shift == 126 && bits > 0b11 {
+            return Err(invalid_packed_allocations());
+        }
+        value |= bits << shift;
+
+        if byte & 0x80 == 0 {
+            return Ok(Some(value));
+        }
+    }
+
+    Err(invalid_packed_allocations())
+}
+
+/// Reads the given number of bytes.
+fn read_packed_bytes(
+    bytes: &mut impl Iterator<Item = u8>,
+    len: usize,
+) -> Result<Vec<u8>, ContractError> {
+    let read: Vec<u8> = bytes.take(len).collect();
+    ensure!(read.len() == len, invalid_packed_allocations());
+
+    Ok(read)
+}
+
+fn invalid_packed_allocations() -> ContractError {
+    ContractError::InvalidInput {
+        reason: "invalid packed allocations".to_string(),
+    }
+}
+
 /// Aggregates the new claims with the existing claims
 pub fn aggregate_claims(
     previous_claims: &HashMap<DistributionSlot, Claim>,
@@ -314,6 +655,27 @@ pub fn aggregate_claims(
     Ok(updated_claims)
 }
 
+/// Returns the total amount claimed across all distribution slots
+pub(crate) fn total_claimed(
+    claims: &HashMap<DistributionSlot, Claim>,
+) -> Result<Uint128, ContractError> {
+    claims
+        .values()
+        .try_fold(Uint128::zero(), |acc, (amount, _)| {
+            Ok(acc.checked_add(*amount)?)
+        })
+}
+
+/// Returns the claimed amount per distribution slot as a vector of (slot, amount), sorted by slot
+pub(crate) fn slot_amounts(claims: &HashMap<DistributionSlot, Claim>) -> Vec<(u64, Uint128)> {
+    let mut slots: Vec<(u64, Uint128)> = claims
+        .iter()
+        .map(|(slot, (amount, _))| (*slot as u64, *amount))
+        .collect();
+    slots.sort();
+    slots
+}
+
 /// Validates the contract version and name. To be taken from mantra-std in the future, for now,
 /// it's duplicated from MANTRA-dex.
 #[macro_export]
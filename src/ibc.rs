@@ -0,0 +1,439 @@
+use cosmwasm_std::{
+    ensure, entry_point, from_json, to_json_binary, Addr, BankMsg, Deps, DepsMut, Env,
+    Ibc3ChannelOpenResponse, IbcBasicResponse, IbcChannel, IbcChannelCloseMsg,
+    IbcChannelConnectMsg, IbcChannelOpenMsg, IbcChannelOpenResponse, IbcOrder, IbcPacket,
+    IbcPacketAckMsg, IbcPacketReceiveMsg, IbcPacketTimeoutMsg, IbcReceiveResponse,
+    IbcSourceCallbackMsg, IbcSrcCallback, Reply, Response, StdAck, StdError, SubMsg,
+    TransferMsgBuilder,
+};
+
+use crate::commands::{self, ClaimPlan};
+use crate::state::{
+    self, ClaimRestoration, PendingIbcTransfer, CLAIM_HISTORY_SEQUENCE, IBC_CHANNELS,
+    PENDING_IBC_TRANSFERS,
+};
+use crate::{helpers, migrations};
+use mantra_claimdrop_std::error::ContractError;
+use mantra_claimdrop_std::msg::{
+    IbcClaimAck, IbcClaimPacket, IbcClaimPayout, IbcDestination, IBC_APP_VERSION,
+};
+
+/// The time after which the ICS-20 transfers sending claimed tokens to remote chains time out, in
+/// seconds
+pub const IBC_TRANSFER_TIMEOUT_SECONDS: u64 = 60 * 60;
+
+/// The longest timeout a claim can set for its ICS-20 transfer, in seconds. The claim stays
+/// pending until the transfer is acknowledged or times out.
+pub const MAX_IBC_TRANSFER_TIMEOUT_SECONDS: u64 = 7 * 24 * 60 * 60;
+
+/// The id of the reply to the ICS-20 transfers sending claimed tokens to remote chains
+pub const IBC_TRANSFER_REPLY_ID: u64 = 1;
+
+/// A claim received over IBC that passed all the checks.
+struct IbcClaim {
+    /// The claim to record
+    plan: ClaimPlan,
+    /// The address the claimed tokens are sent to
+    destination: String,
+    /// The ICS-20 channel the claimed tokens are sent over, if they're sent to a remote chain
+    transfer_channel: Option<String>,
+}
+
+#[entry_point]
+pub fn ibc_channel_open(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelOpenMsg,
+) -> Result<IbcChannelOpenResponse, ContractError> {
+    validate_channel(msg.channel(), msg.counterparty_version())?;
+
+    Ok(Some(Ibc3ChannelOpenResponse {
+        version: IBC_APP_VERSION.to_string(),
+    }))
+}
+
+#[entry_point]
+pub fn ibc_channel_connect(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelConnectMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    validate_channel(msg.channel(), msg.counterparty_version())?;
+
+    Ok(IbcBasicResponse::new().add_attributes(vec![
+        ("action", "ibc_channel_connect".to_string()),
+        ("channel_id", msg.channel().endpoint.channel_id.clone()),
+    ]))
+}
+
+#[entry_point]
+pub fn ibc_channel_close(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelCloseMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let channel_id = msg.channel().endpoint.channel_id.clone();
+
+    // a closed channel can't be reopened, so it doesn't need to stay allowed
+    IBC_CHANNELS.remove(deps.storage, channel_id.as_str());
+
+    Ok(IbcBasicResponse::new().add_attributes(vec![
+        ("action", "ibc_channel_close".to_string()),
+        ("channel_id", channel_id),
+    ]))
+}
+
+/// Receives a claim packet. Claims that fail are acknowledged with an error, without changing
+/// state.
+#[entry_point]
+pub fn ibc_packet_receive(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketReceiveMsg,
+) -> Result<IbcReceiveResponse, ContractError> {
+    migrations::assert_no_migration_in_progress(deps.storage)?;
+
+    let packet = msg.packet;
+    let channel_id = packet.dest.channel_id.clone();
+
+    match receive_claim(deps, &env, &packet) {
+        Ok(response) => Ok(response),
+        Err(err) => Ok(
+            IbcReceiveResponse::new(StdAck::error(err.to_string())).add_attributes(vec![
+                ("action", "ibc_claim".to_string()),
+                ("channel_id", channel_id),
+                ("error", err.to_string()),
+            ]),
+        ),
+    }
+}
+
+/// Checks and records a claim packet, paying out the claimed tokens. The claim is only recorded
+/// once all its checks passed, so an error leaves no partial writes behind.
+fn receive_claim(
+    mut deps: DepsMut,
+    env: &Env,
+    packet: &IbcPacket,
+) -> Result<IbcReceiveResponse, ContractError> {
+    let channel_id = packet.dest.channel_id.clone();
+
+    let IbcClaim {
+        plan,
+        destination,
+        transfer_channel,
+    } = check_claim_packet(deps.as_ref(), env, packet)?;
+
+    let address = plan.address.clone();
+    let slots = helpers::slot_amounts(&plan.claims_to_record);
+    // the claim is initiated by the companion contract on the remote chain
+    let sender = Addr::unchecked(packet.src.port_id.as_str());
+    let applied = commands::apply_claim(deps.branch(), env, &sender, plan, &destination)?;
+    let amount = applied.amount.clone();
+
+    let message = match transfer_channel {
+        Some(transfer_channel) => claim_transfer_msg(
+            env,
+            PendingIbcTransfer {
+                channel_id: transfer_channel,
+                address: address.clone(),
+                amount: amount.clone(),
+                slots,
+                // the claim was just appended to the claim history
+                history_sequence: CLAIM_HISTORY_SEQUENCE.load(deps.storage)?,
+                approved_operator: applied.approved_operator.map(Addr::into_string),
+                anchored_first_claim: applied.anchored_first_claim,
+            },
+            destination.clone(),
+            IBC_TRANSFER_TIMEOUT_SECONDS,
+        )?,
+        None => SubMsg::new(BankMsg::Send {
+            to_address: destination.clone(),
+            amount: vec![amount.clone()],
+        }),
+    };
+
+    Ok(
+        IbcReceiveResponse::new(StdAck::success(to_json_binary(&IbcClaimAck {
+            amount: amount.clone(),
+        })?))
+        .add_submessage(message)
+        .add_attributes(vec![
+            ("action", "ibc_claim".to_string()),
+            ("channel_id", channel_id),
+            ("address", address),
+            ("destination", destination),
+            ("claimed_amount", amount.to_string()),
+        ]),
+    )
+}
+
+/// The contract doesn't send packets over its channels, so there are no acknowledgements to handle.
+#[entry_point]
+pub fn ibc_packet_ack(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: IbcPacketAckMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    Ok(IbcBasicResponse::new().add_attribute("action", "ibc_packet_ack"))
+}
+
+/// The contract doesn't send packets over its channels, so there are no timeouts to handle.
+#[entry_point]
+pub fn ibc_packet_timeout(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: IbcPacketTimeoutMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    Ok(IbcBasicResponse::new().add_attribute("action", "ibc_packet_timeout"))
+}
+
+/// Receives the acknowledgement or timeout of the ICS-20 transfers sent by the contract. The claims
+/// paid out by transfers that failed are restored, so they can be claimed again. If the campaign
+/// was closed in the meantime, the refunded tokens are sent to the owner instead, as closing the
+/// campaign would have done.
+#[entry_point]
+pub fn ibc_source_callback(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcSourceCallbackMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let (packet, failure) = match msg {
+        IbcSourceCallbackMsg::Acknowledgement(ack) => {
+            // ICS-20 acknowledgements have the same format as StdAck
+            let failure = match from_json::<StdAck>(&ack.acknowledgement.data) {
+                Ok(StdAck::Success(_)) => None,
+                Ok(StdAck::Error(err)) => Some(err),
+                Err(err) => Some(err.to_string()),
+            };
+            (ack.original_packet, failure)
+        }
+        IbcSourceCallbackMsg::Timeout(timeout) => (timeout.packet, Some("timeout".to_string())),
+    };
+
+    let channel_id = packet.src.channel_id;
+    let Some(transfer) =
+        PENDING_IBC_TRANSFERS.may_load(deps.storage, (channel_id.as_str(), packet.sequence))?
+    else {
+        // not a claim transfer
+        return Ok(IbcBasicResponse::new().add_attribute("action", "ibc_transfer_callback"));
+    };
+
+    PENDING_IBC_TRANSFERS.remove(deps.storage, (channel_id.as_str(), packet.sequence));
+
+    let mut attributes = vec![
+        ("action", "ibc_transfer_callback".to_string()),
+        ("channel_id", channel_id),
+        ("sequence", packet.sequence.to_string()),
+        ("address", transfer.address.clone()),
+        ("amount", transfer.amount.to_string()),
+    ];
+
+    let mut messages = vec![];
+
+    match failure {
+        None => attributes.push(("status", "success".to_string())),
+        Some(reason) => {
+            let status = match state::restore_claim(deps.storage, &transfer)? {
+                ClaimRestoration::Restored => "restored",
+                ClaimRestoration::CampaignClosed => {
+                    match cw_ownable::get_ownership(deps.storage)?.owner {
+                        Some(owner) => {
+                            messages.push(BankMsg::Send {
+                                to_address: owner.to_string(),
+                                amount: vec![transfer.amount.clone()],
+                            });
+                            "refunded"
+                        }
+                        // the ownership was renounced, so the refunded tokens stay in the contract
+                        None => "unrestorable",
+                    }
+                }
+                // the refunded tokens stay in the campaign, and are refunded when it's closed
+                ClaimRestoration::ClaimsMissing => "unrestorable",
+            };
+            attributes.push(("status", status.to_string()));
+            attributes.push(("reason", reason));
+        }
+    }
+
+    Ok(IbcBasicResponse::new()
+        .add_messages(messages)
+        .add_attributes(attributes))
+}
+
+/// Builds the ICS-20 transfer paying out a claim to a remote chain. The transfer requests a source
+/// callback, so the claim can be restored if the transfer fails.
+///
+/// # Arguments
+/// * `env` - The env context
+/// * `transfer` - The claim paid out by the transfer. The history sequence must already be set.
+/// * `to_address` - The address on the remote chain receiving the tokens
+/// * `timeout_seconds` - The number of seconds after which the transfer times out
+///
+/// # Returns
+/// * `Result<SubMsg, ContractError>` - The transfer, replying with the packet sequence
+pub(crate) fn claim_transfer_msg(
+    env: &Env,
+    transfer: PendingIbcTransfer,
+    to_address: String,
+    timeout_seconds: u64,
+) -> Result<SubMsg, ContractError> {
+    let msg = TransferMsgBuilder::new(
+        transfer.channel_id.clone(),
+        to_address,
+        transfer.amount.clone(),
+        env.block.time.plus_seconds(timeout_seconds),
+    )
+    .with_src_callback(IbcSrcCallback {
+        address: env.contract.address.clone(),
+        gas_limit: None,
+    })
+    .build();
+
+    // the packet sequence the callbacks refer to is only known once the transfer is sent
+    Ok(SubMsg::reply_on_success(msg, IBC_TRANSFER_REPLY_ID)
+        .with_payload(to_json_binary(&transfer)?))
+}
+
+/// Stores a claim paid out over ICS-20 until its transfer is acknowledged, keyed by the sequence of
+/// the packet sent.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `msg` - The reply to the transfer built by [claim_transfer_msg]
+///
+/// # Returns
+/// * `Result<Response, ContractError>` - The response with attributes
+pub(crate) fn track_transfer(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let response = msg.result.into_result().map_err(StdError::generic_err)?;
+    let transfer: PendingIbcTransfer = from_json(&msg.payload)?;
+
+    let transfer_response =
+        response
+            .msg_responses
+            .first()
+            .ok_or(ContractError::InvalidIbcTransfer {
+                reason: "missing MsgTransferResponse".to_string(),
+            })?;
+    let sequence = helpers::parse_transfer_sequence(transfer_response.value.as_slice())?;
+
+    PENDING_IBC_TRANSFERS.save(
+        deps.storage,
+        (transfer.channel_id.as_str(), sequence),
+        &transfer,
+    )?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "track_ibc_transfer".to_string()),
+        ("channel_id", transfer.channel_id),
+        ("sequence", sequence.to_string()),
+    ]))
+}
+
+/// Validates the order and version of a channel being opened.
+fn validate_channel(
+    channel: &IbcChannel,
+    counterparty_version: Option<&str>,
+) -> Result<(), ContractError> {
+    ensure!(
+        channel.order == IbcOrder::Unordered,
+        ContractError::InvalidIbcChannel {
+            reason: "only unordered channels are supported".to_string(),
+        }
+    );
+
+    for version in std::iter::once(channel.version.as_str()).chain(counterparty_version) {
+        ensure!(
+            version == IBC_APP_VERSION,
+            ContractError::InvalidIbcChannel {
+                reason: format!("expected version {IBC_APP_VERSION}, got {version}"),
+            }
+        );
+    }
+
+    Ok(())
+}
+
+/// Runs all the checks of a claim packet without mutating state.
+fn check_claim_packet(
+    deps: Deps,
+    env: &Env,
+    packet: &IbcPacket,
+) -> Result<IbcClaim, ContractError> {
+    let channel_id = packet.dest.channel_id.as_str();
+    let config = IBC_CHANNELS.may_load(deps.storage, channel_id)?.ok_or(
+        ContractError::IbcChannelNotAllowed {
+            channel_id: channel_id.to_string(),
+        },
+    )?;
+
+    let IbcClaimPacket {
+        address,
+        amount,
+        payout,
+    } = from_json(&packet.data)?;
+    let address = helpers::validate_raw_address(deps, &address)?;
+    // a channel can only claim for the addresses of the chain it's connected to
+    ensure!(
+        address.starts_with(&format!("{}1", config.address_prefix)),
+        ContractError::Unauthorized
+    );
+
+    let campaign = commands::claimable_campaign(deps, env)?
+        .map_err(|reason| ContractError::from_claim_failure(reason, &address))?;
+
+    let (destination, transfer_channel, approval) = match payout {
+        IbcClaimPayout::Local { receiver } => {
+            let receiver = deps.api.addr_validate(&receiver)?;
+            // only the allocation holder or an operator it approved can receive the claim
+            let approval = if receiver.as_str() == address {
+                None
+            } else {
+                let approval = commands::active_claim_approval(
+                    deps,
+                    env,
+                    &Addr::unchecked(address.as_str()),
+                    &receiver,
+                )?
+                .ok_or(ContractError::Unauthorized)?;
+                Some((receiver.clone(), approval))
+            };
+
+            ensure!(
+                !state::is_blacklisted(deps, env, receiver.as_str())?,
+                ContractError::AddressBlacklisted
+            );
+            ensure!(
+                helpers::is_compliant(deps, &campaign, receiver.as_str())?,
+                ContractError::ComplianceCheckFailed {
+                    address: receiver.to_string(),
+                }
+            );
+
+            (receiver.to_string(), None, approval)
+        }
+        IbcClaimPayout::Remote { remote_address } => {
+            let transfer_channel =
+                config
+                    .transfer_channel
+                    .ok_or(ContractError::InvalidIbcChannel {
+                        reason: format!("remote payouts are not enabled for {channel_id}"),
+                    })?;
+            helpers::validate_ibc_destination(&IbcDestination {
+                channel_id: transfer_channel.clone(),
+                remote_address: remote_address.clone(),
+                timeout: None,
+            })?;
+            (remote_address, Some(transfer_channel), None)
+        }
+    };
+
+    let plan = commands::plan_approved_claim(deps, env, campaign, &address, approval, amount)?
+        .map_err(|reason| ContractError::from_claim_failure(reason, &address))?;
+
+    Ok(IbcClaim {
+        plan,
+        destination,
+        transfer_channel,
+    })
+}
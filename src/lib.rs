@@ -1,5 +1,7 @@
 pub mod commands;
 pub mod contract;
 pub mod helpers;
+pub mod ibc;
+pub mod migrations;
 pub mod queries;
 pub mod state;
@@ -0,0 +1,172 @@
+use cosmwasm_std::{ensure, Env, Storage};
+
+use crate::state::{self, MIGRATION};
+use mantra_claimdrop_std::error::ContractError;
+use mantra_claimdrop_std::msg::MigrationProgress;
+
+/// Default number of entries processed by a migration batch
+pub const DEFAULT_MIGRATION_BATCH_SIZE: u32 = 1_000;
+
+/// Processes up to `limit` entries after the cursor. Returns the number of entries processed, and
+/// the cursor to resume from if there may be more entries left.
+type MigrationBatch = fn(
+    &mut dyn Storage,
+    &Env,
+    Option<String>,
+    usize,
+) -> Result<(usize, Option<String>), ContractError>;
+
+/// A migration step transforms the data stored by versions prior to the one introducing it.
+/// Steps process their entries in batches, resuming after the cursor returned by the previous
+/// batch.
+struct MigrationStep {
+    /// The version of the contract that introduced the step
+    version: &'static str,
+    /// The name of the step, reported by the MigrationStatus query
+    name: &'static str,
+    /// Runs a batch of the step
+    run: MigrationBatch,
+}
+
+/// The migration steps, in the order they have to run.
+const MIGRATION_STEPS: &[MigrationStep] = &[
+    MigrationStep {
+        version: "2.1.0",
+        name: "blacklist_entries",
+        run: state::migrate_blacklist_flags,
+    },
+    MigrationStep {
+        version: "2.2.0",
+        name: "slot_claims",
+        run: state::migrate_legacy_claims,
+    },
+    MigrationStep {
+        version: "2.3.0",
+        name: "allocation_totals",
+        run: state::migrate_allocation_totals,
+    },
+];
+
+/// Starts a migration from the given version to the given one, scheduling the steps introduced by
+/// the versions in between.
+///
+/// # Arguments
+/// * `storage` - The storage
+/// * `from_version` - The version of the contract being migrated from
+/// * `to_version` - The version of the contract being migrated to
+///
+/// # Returns
+/// * `Result<(), ContractError>` - Success or error
+pub(crate) fn start_migration(
+    storage: &mut dyn Storage,
+    from_version: &str,
+    to_version: &str,
+) -> Result<(), ContractError> {
+    ensure!(
+        MIGRATION.may_load(storage)?.is_none(),
+        ContractError::MigrationInProgress
+    );
+
+    let from: semver::Version = from_version.parse()?;
+    let to: semver::Version = to_version.parse()?;
+
+    let pending_steps = MIGRATION_STEPS
+        .iter()
+        .filter(|step| {
+            let version: semver::Version = step.version.parse().unwrap();
+            from < version && version <= to
+        })
+        .map(|step| step.name.to_string())
+        .collect::<Vec<String>>();
+
+    MIGRATION.save(
+        storage,
+        &MigrationProgress {
+            from_version: from_version.to_string(),
+            to_version: to_version.to_string(),
+            pending_steps,
+            cursor: None,
+            processed: 0,
+        },
+    )?;
+
+    Ok(())
+}
+
+/// Runs the pending migration steps, processing up to `batch_size` entries. Once all the steps are
+/// done, the migration is cleared.
+///
+/// # Arguments
+/// * `storage` - The storage
+/// * `env` - The environment
+/// * `batch_size` - The maximum number of entries to process
+///
+/// # Returns
+/// * `Result<(u64, Option<MigrationProgress>), ContractError>` - The number of entries processed,
+///   and the progress of the migration if it hasn't completed
+pub(crate) fn run_migration(
+    storage: &mut dyn Storage,
+    env: &Env,
+    batch_size: u32,
+) -> Result<(u64, Option<MigrationProgress>), ContractError> {
+    ensure!(
+        batch_size > 0,
+        ContractError::InvalidInput {
+            reason: "batch_size must be greater than zero".to_string(),
+        }
+    );
+
+    let mut progress = MIGRATION
+        .may_load(storage)?
+        .ok_or(ContractError::NoMigrationInProgress)?;
+
+    let mut remaining = batch_size as usize;
+    let mut processed = 0u64;
+
+    while remaining > 0 {
+        let Some(step_name) = progress.pending_steps.first() else {
+            break;
+        };
+
+        let step = MIGRATION_STEPS
+            .iter()
+            .find(|step| step.name == step_name)
+            .ok_or(ContractError::InvalidInput {
+                reason: format!("unknown migration step {step_name}"),
+            })?;
+
+        let (step_processed, cursor) = (step.run)(storage, env, progress.cursor.take(), remaining)?;
+
+        remaining -= step_processed;
+        processed += step_processed as u64;
+
+        match cursor {
+            Some(cursor) => {
+                progress.cursor = Some(cursor);
+                progress.processed += step_processed as u64;
+            }
+            None => {
+                progress.pending_steps.remove(0);
+                progress.processed = 0;
+            }
+        }
+    }
+
+    if progress.pending_steps.is_empty() {
+        MIGRATION.remove(storage);
+        return Ok((processed, None));
+    }
+
+    MIGRATION.save(storage, &progress)?;
+    Ok((processed, Some(progress)))
+}
+
+/// Returns an error if a migration is in progress.
+pub(crate) fn assert_no_migration_in_progress(storage: &dyn Storage) -> Result<(), ContractError> {
+    ensure!(
+        !MIGRATION.exists(storage),
+        ContractError::MigrationInProgress
+    );
+
+    Ok(())
+}
@@ -1,15 +1,28 @@
-use cosmwasm_std::{coin, Coin, Deps, Env, Order, StdResult, Uint128};
-use cw_storage_plus::Bound;
+use std::collections::HashMap;
+
+use cosmwasm_std::{
+    coin, ensure, Coin, Deps, Env, HexBinary, Order, StdResult, Timestamp, Uint128,
+};
+use cw_storage_plus::{Bound, PrefixBound};
 
-use crate::helpers;
 use crate::state::{
-    get_allocation, get_total_claims_amount_for_address, is_authorized, is_blacklisted,
-    ALLOCATIONS, AUTHORIZED_WALLETS, CAMPAIGN, CLAIMS,
+    audit_log, get_allocation, get_claims_for_address, get_distribution_type,
+    get_effective_allocation, get_total_claims_amount_for_address, get_vesting_time, is_authorized,
+    is_blacklisted, is_paused, AllocationChecksum, Claim, DistributionSlot, ALLOCATIONS,
+    ALLOCATION_SCHEDULES, AUTHORIZED_WALLETS, AUTO_CLAIM_CURSOR, AUTO_CLAIM_SUBSCRIPTIONS,
+    BLACKLIST, CAMPAIGN, CLAIMS, CLAIM_APPROVALS, CLAIM_HISTORY, CRANK_TIP, CRANK_TIP_POOL,
+    FORFEITURES, IBC_CHANNELS, MIGRATION, VESTING_ANCHORS,
 };
+use crate::{commands, helpers};
 use mantra_claimdrop_std::error::ContractError;
 use mantra_claimdrop_std::msg::{
-    AllocationsResponse, AuthorizedResponse, AuthorizedWalletsResponse, BlacklistResponse,
-    CampaignResponse, ClaimedResponse, RewardsResponse,
+    AllocationChecksumResponse, AllocationsResponse, AuditEntry, AuditLogResponse,
+    AuthorizedResponse, AuthorizedWalletsResponse, AutoClaimResponse, BlacklistEntry,
+    BlacklistResponse, BlacklistedAddressesResponse, CampaignResponse, ClaimApproval,
+    ClaimApprovalsResponse, ClaimHistoryResponse, ClaimRecord, ClaimedResponse, DistributionType,
+    ExportStateResponse, IbcChannelConfig, IbcChannelsResponse, MigrationStatusResponse,
+    PausedResponse, ProjectedRewards, RewardsAtResponse, RewardsBreakdownResponse, RewardsResponse,
+    SimulateClaimResponse, SlotClaimEntry, SlotRewards, StateEntry, VestingScheduleResponse,
 };
 
 /// Returns the active airdrop campaign.
@@ -51,11 +64,15 @@ pub(crate) fn query_rewards(
 
     let validated_receiver_string = helpers::validate_raw_address(deps, &receiver)?;
 
-    let total_claimable_amount = get_allocation(deps, validated_receiver_string.as_str())?.ok_or(
-        ContractError::NoAllocationFound {
-            address: receiver.to_string(),
-        },
-    )?;
+    let total_claimable_amount =
+        get_effective_allocation(deps, &campaign, validated_receiver_string.as_str())?.ok_or(
+            ContractError::NoAllocationFound {
+                address: receiver.to_string(),
+            },
+        )?;
+
+    let allocation = get_allocation(deps, validated_receiver_string.as_str())?.unwrap_or_default();
+    let redistributed = total_claimable_amount.saturating_sub(allocation);
 
     let total_claimed: Uint128 =
         get_total_claims_amount_for_address(deps, validated_receiver_string.as_str())?;
@@ -72,10 +89,15 @@ pub(crate) fn query_rewards(
         pending.push(pending_rewards);
     }
 
+    let vesting_time =
+        get_vesting_time(deps, &campaign, &validated_receiver_string, &env.block.time)?;
+    let distribution_type =
+        get_distribution_type(deps, &campaign, &validated_receiver_string, &vesting_time)?;
     let (claimable_amount, _) = helpers::compute_claimable_amount(
         deps,
         &campaign,
-        &env.block.time,
+        &distribution_type,
+        &vesting_time,
         &validated_receiver_string,
         total_claimable_amount,
     )?;
@@ -84,9 +106,9 @@ pub(crate) fn query_rewards(
         available_to_claim.push(claimable_amount);
     }
 
-    // if the campaign is closed, clear the pending and available to claim rewards as there's nothing else
-    // to claim
-    if campaign.closed.is_some() {
+    // if the campaign is closed or the allocation was forfeited, clear the pending and available to
+    // claim rewards as there's nothing else to claim
+    if campaign.closed.is_some() || FORFEITURES.has(deps.storage, &validated_receiver_string) {
         pending.clear();
         available_to_claim.clear();
     }
@@ -95,9 +117,275 @@ pub(crate) fn query_rewards(
         claimed,
         pending,
         available_to_claim,
+        redistributed: if redistributed.is_zero() {
+            vec![]
+        } else {
+            vec![coin(redistributed.u128(), &campaign.reward_denom)]
+        },
     })
 }
 
+/// Returns the rewards information for a specific address, broken down by distribution slot.
+/// For every slot it includes the entitlement, the amount vested so far, the amount claimed, the
+/// amount claimable now and the slot's schedule.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `env` - The environment
+/// * `receiver` - The address to get rewards for
+///
+/// # Returns
+/// * `Result<RewardsBreakdownResponse, ContractError>` - The rewards information per slot
+pub(crate) fn query_rewards_breakdown(
+    deps: Deps,
+    env: Env,
+    receiver: String,
+) -> Result<RewardsBreakdownResponse, ContractError> {
+    let rewards = query_rewards(deps, env.clone(), receiver.clone())?;
+
+    let campaign = CAMPAIGN.load(deps.storage)?;
+    let validated_receiver_string = helpers::validate_raw_address(deps, &receiver)?;
+    let total_claimable_amount =
+        get_effective_allocation(deps, &campaign, validated_receiver_string.as_str())?.ok_or(
+            ContractError::NoAllocationFound {
+                address: receiver.to_string(),
+            },
+        )?;
+
+    let previous_claims = get_claims_for_address(deps, validated_receiver_string.clone())?;
+    let vesting_time =
+        get_vesting_time(deps, &campaign, &validated_receiver_string, &env.block.time)?;
+    let distribution_type =
+        get_distribution_type(deps, &campaign, &validated_receiver_string, &vesting_time)?;
+    let (_, new_claims) = helpers::compute_claimable_amount(
+        deps,
+        &campaign,
+        &distribution_type,
+        &vesting_time,
+        &validated_receiver_string,
+        total_claimable_amount,
+    )?;
+
+    let forfeited = FORFEITURES.has(deps.storage, &validated_receiver_string);
+    let denom = campaign.reward_denom.as_str();
+    let mut slots = vec![];
+
+    for (slot, distribution) in distribution_type.iter().enumerate() {
+        let (entitlement, vested) = helpers::calculate_vested_amount_for_distribution(
+            &vesting_time,
+            total_claimable_amount,
+            distribution,
+        )?;
+
+        let claimed = previous_claims
+            .get(&slot)
+            .map_or(Uint128::zero(), |(amount, _)| *amount);
+
+        // nothing else can be claimed once the campaign is closed or the allocation forfeited
+        let claimable = if campaign.closed.is_some() || forfeited {
+            Uint128::zero()
+        } else {
+            new_claims
+                .get(&slot)
+                .map_or(Uint128::zero(), |(amount, _)| *amount)
+        };
+
+        let (start_time, end_time, cliff_end_time) = match distribution {
+            DistributionType::LinearVesting {
+                start_time,
+                end_time,
+                cliff_duration,
+                ..
+            } => (
+                Some(*start_time),
+                Some(*end_time),
+                cliff_duration.map(|cliff_duration| start_time + cliff_duration),
+            ),
+            DistributionType::LumpSum { start_time, .. } => {
+                (Some(*start_time), Some(*start_time), None)
+            }
+            // relative distribution types are anchored by get_distribution_type
+            DistributionType::RelativeLinearVesting { .. }
+            | DistributionType::RelativeLumpSum { .. } => {
+                return Err(ContractError::CampaignError {
+                    reason: "distribution type is not anchored".to_string(),
+                })
+            }
+            // the milestone hasn't been triggered yet
+            DistributionType::Milestone { .. } => (None, None, None),
+        };
+
+        slots.push(SlotRewards {
+            slot: slot as u64,
+            distribution_type: distribution.clone(),
+            entitlement: coin(entitlement.u128(), denom),
+            vested: coin(vested.u128(), denom),
+            claimed: coin(claimed.u128(), denom),
+            claimable: coin(claimable.u128(), denom),
+            start_time,
+            end_time,
+            cliff_end_time,
+        });
+    }
+
+    Ok(RewardsBreakdownResponse { rewards, slots })
+}
+
+/// Simulates a claim without executing it. Runs the same checks as the Claim message and returns
+/// either the amount and per-slot split that would be recorded, or the reason the claim would fail.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `env` - The environment
+/// * `sender` - The address that would send the Claim message
+/// * `receiver` - Optional receiver of the claimed rewards
+/// * `amount` - Optional amount to claim
+///
+/// # Returns
+/// * `Result<SimulateClaimResponse, ContractError>` - The outcome of the simulated claim
+pub(crate) fn query_simulate_claim(
+    deps: Deps,
+    env: Env,
+    sender: String,
+    receiver: Option<String>,
+    amount: Option<Uint128>,
+) -> Result<SimulateClaimResponse, ContractError> {
+    let sender = deps.api.addr_validate(&sender)?;
+
+    let response = match commands::prepare_claim(deps, &env, &sender, receiver, amount)? {
+        Ok(plan) => SimulateClaimResponse::Success {
+            slots: helpers::slot_amounts(&plan.claims_to_record),
+            amount: plan.amount,
+        },
+        Err(reason) => SimulateClaimResponse::Failure { reason },
+    };
+
+    Ok(response)
+}
+
+/// Maximum number of timestamps that can be evaluated in a single VestingSchedule query
+pub const MAX_VESTING_SCHEDULE_POINTS: usize = 100;
+
+/// Returns the rewards of an address projected at the given timestamp.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `env` - The environment
+/// * `address` - The address to get rewards for
+/// * `timestamp` - The unix timestamp to evaluate the rewards at, in seconds
+///
+/// # Returns
+/// * `Result<RewardsAtResponse, ContractError>` - The projected rewards
+pub(crate) fn query_rewards_at(
+    deps: Deps,
+    env: Env,
+    address: String,
+    timestamp: u64,
+) -> Result<RewardsAtResponse, ContractError> {
+    let schedule = query_vesting_schedule(deps, env, address, vec![timestamp])?.schedule;
+    // there is exactly one point in the schedule
+    Ok(schedule.into_iter().next().unwrap())
+}
+
+/// Returns the rewards of an address projected at each of the given timestamps. The distribution
+/// types are resolved at each timestamp. Relative distribution types anchored to the first claim
+/// of an address that hasn't claimed yet are projected as if it claimed now.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `env` - The environment
+/// * `address` - The address to get rewards for
+/// * `points` - The unix timestamps to evaluate the rewards at, in seconds
+///
+/// # Returns
+/// * `Result<VestingScheduleResponse, ContractError>` - The projected rewards at each timestamp
+pub(crate) fn query_vesting_schedule(
+    deps: Deps,
+    env: Env,
+    address: String,
+    points: Vec<u64>,
+) -> Result<VestingScheduleResponse, ContractError> {
+    ensure!(
+        !points.is_empty() && points.len() <= MAX_VESTING_SCHEDULE_POINTS,
+        ContractError::InvalidInput {
+            reason: format!(
+                "points must contain between 1 and {} timestamps",
+                MAX_VESTING_SCHEDULE_POINTS
+            ),
+        }
+    );
+
+    let campaign = CAMPAIGN
+        .may_load(deps.storage)?
+        .ok_or(ContractError::CampaignError {
+            reason: "there's not an active campaign".to_string(),
+        })?;
+
+    let validated_address = helpers::validate_raw_address(deps, &address)?;
+    let total_claimable_amount =
+        get_effective_allocation(deps, &campaign, validated_address.as_str())?.ok_or(
+            ContractError::NoAllocationFound {
+                address: address.to_string(),
+            },
+        )?;
+    let total_claimed = get_total_claims_amount_for_address(deps, validated_address.as_str())?;
+    let current_vesting_time =
+        get_vesting_time(deps, &campaign, &validated_address, &env.block.time)?;
+    let denom = campaign.reward_denom.as_str();
+
+    let schedule = points
+        .into_iter()
+        .map(|timestamp| {
+            // Timestamp::from_seconds panics if the timestamp doesn't fit in nanoseconds
+            let time = timestamp
+                .checked_mul(1_000_000_000)
+                .map(Timestamp::from_nanos)
+                .ok_or(ContractError::InvalidInput {
+                    reason: format!("timestamp {timestamp} is out of range"),
+                })?;
+            let vesting_time = get_vesting_time(deps, &campaign, &validated_address, &time)?;
+            // an address that hasn't claimed yet is projected as claiming now, or at the
+            // timestamp if it's in the past
+            let distribution_type = get_distribution_type(
+                deps,
+                &campaign,
+                &validated_address,
+                &std::cmp::min(current_vesting_time, vesting_time),
+            )?;
+            let vested = helpers::compute_vested_amount(
+                &campaign,
+                &distribution_type,
+                &vesting_time,
+                total_claimable_amount,
+            )?;
+
+            // nothing can be claimed before the campaign starts or once it is closed
+            let claimable = if campaign.has_started(&time) && campaign.closed.is_none() {
+                let (claimable, _) = helpers::compute_claimable_amount(
+                    deps,
+                    &campaign,
+                    &distribution_type,
+                    &vesting_time,
+                    &validated_address,
+                    total_claimable_amount,
+                )?;
+                claimable.amount
+            } else {
+                Uint128::zero()
+            };
+
+            Ok(ProjectedRewards {
+                timestamp,
+                vested: coin(vested.u128(), denom),
+                claimed: coin(total_claimed.u128(), denom),
+                claimable: coin(claimable.u128(), denom),
+            })
+        })
+        .collect::<Result<Vec<ProjectedRewards>, ContractError>>()?;
+
+    Ok(VestingScheduleResponse { schedule })
+}
+
 // settings for pagination
 pub(crate) const MAX_LIMIT: u16 = 5_000;
 const DEFAULT_LIMIT: u16 = 100;
@@ -120,43 +408,64 @@ pub(crate) fn query_claimed(
     limit: Option<u16>,
 ) -> Result<ClaimedResponse, ContractError> {
     let mut claimed = vec![];
+    let mut claimed_by_slot = vec![];
 
     let campaign = CAMPAIGN.may_load(deps.storage)?;
 
     // returns empty if the campaign is not set
     if campaign.is_none() {
-        return Ok(ClaimedResponse { claimed });
+        return Ok(ClaimedResponse {
+            claimed,
+            claimed_by_slot,
+        });
     }
 
     if let Some(address) = address {
         // For an address to have claimed, it must have been a valid cosmos address
         let address = deps.api.addr_validate(&address)?.to_string();
-        let claims = CLAIMS.may_load(deps.storage, address.clone())?;
-
-        if let Some(claims) = claims {
-            //iterate in hashmap and aggregate amount from claim
-            let total_claimed = claims
-                .iter()
-                .fold(Uint128::zero(), |acc, (_, (amount, _))| {
-                    acc.checked_add(*amount).unwrap()
-                });
-
-            if total_claimed > Uint128::zero() {
-                let denom = CAMPAIGN.load(deps.storage)?.reward_denom.clone();
-                claimed.push((address, coin(total_claimed.u128(), denom)));
-            }
+        let claims = get_claims_for_address(deps, address.clone())?;
+
+        //iterate in hashmap and aggregate amount from claim
+        let total_claimed = claims
+            .iter()
+            .fold(Uint128::zero(), |acc, (_, (amount, _))| {
+                acc.checked_add(*amount).unwrap()
+            });
+
+        if total_claimed > Uint128::zero() {
+            let denom = CAMPAIGN.load(deps.storage)?.reward_denom.clone();
+            claimed_by_slot.push((address.clone(), claims_by_slot(&claims, &denom)));
+            claimed.push((address, coin(total_claimed.u128(), denom)));
         }
     } else {
         let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-        let start = start_from.map(Bound::exclusive);
+        let start = start_from.as_deref().map(PrefixBound::exclusive);
 
         let denom = CAMPAIGN.load(deps.storage)?.reward_denom.clone();
 
-        CLAIMS
-            .range(deps.storage, start, None, Order::Ascending)
-            .take(limit)
-            .map(|item| {
-                let (address, claims) = item?;
+        // the claims are stored per (address, slot), so group them by address
+        let mut claims_by_address: Vec<(String, HashMap<DistributionSlot, Claim>)> = vec![];
+        for item in CLAIMS.prefix_range(deps.storage, start, None, Order::Ascending) {
+            let ((address, slot), claim) = item?;
+            match claims_by_address.last_mut() {
+                Some((last_address, claims)) if *last_address == address => {
+                    claims.insert(slot as DistributionSlot, claim.into());
+                }
+                _ => {
+                    if claims_by_address.len() == limit {
+                        break;
+                    }
+                    claims_by_address.push((
+                        address,
+                        HashMap::from([(slot as DistributionSlot, claim.into())]),
+                    ));
+                }
+            }
+        }
+
+        claims_by_address
+            .into_iter()
+            .map(|(address, claims)| {
                 //iterate in hashmap and aggregate amount from claim
                 let total_claimed = claims
                     .iter()
@@ -164,15 +473,33 @@ pub(crate) fn query_claimed(
                         acc.checked_add(*amount).unwrap()
                     });
 
-                Ok((address, coin(total_claimed.u128(), denom.clone())))
+                (
+                    address,
+                    coin(total_claimed.u128(), denom.clone()),
+                    claims_by_slot(&claims, &denom),
+                )
             })
-            .collect::<Result<Vec<(String, Coin)>, ContractError>>()?
-            .into_iter()
-            .filter(|(_, coin)| coin.amount > Uint128::zero())
-            .for_each(|claim| claimed.push(claim));
+            .filter(|(_, coin, _)| coin.amount > Uint128::zero())
+            .for_each(|(address, total, by_slot)| {
+                claimed_by_slot.push((address.clone(), by_slot));
+                claimed.push((address, total));
+            });
     }
 
-    Ok(ClaimedResponse { claimed })
+    Ok(ClaimedResponse {
+        claimed,
+        claimed_by_slot,
+    })
+}
+
+/// Returns the claimed amounts per distribution slot, sorted by slot.
+fn claims_by_slot(claims: &HashMap<DistributionSlot, Claim>, denom: &str) -> Vec<(u64, Coin)> {
+    let mut by_slot: Vec<(u64, Coin)> = claims
+        .iter()
+        .map(|(slot, (amount, _))| (*slot as u64, coin(amount.u128(), denom)))
+        .collect();
+    by_slot.sort_by_key(|(slot, _)| *slot);
+    by_slot
 }
 
 /// Returns the allocation for an address.
@@ -224,18 +551,45 @@ pub fn query_allocation(
 ///
 /// # Arguments
 /// * `deps` - The dependencies
+/// * `env` - The environment
 /// * `address` - The address to check
 ///
 /// # Returns
 /// * `Result<BlacklistResponse, ContractError>` - The blacklist status
 pub fn query_is_blacklisted(
     deps: Deps,
+    env: Env,
     address: String,
 ) -> Result<BlacklistResponse, ContractError> {
-    let is_blacklisted = is_blacklisted(deps, &address)?;
+    let is_blacklisted = is_blacklisted(deps, &env, &address)?;
     Ok(BlacklistResponse { is_blacklisted })
 }
 
+/// Returns a list of blacklisted addresses with pagination support.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `start_after` - Optional address to start pagination from
+/// * `limit` - Optional limit for pagination
+///
+/// # Returns
+/// * `Result<BlacklistedAddressesResponse, ContractError>` - The blacklisted addresses
+pub fn query_blacklist(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<BlacklistedAddressesResponse, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT as u32).min(MAX_LIMIT as u32) as usize;
+    let start = cw_utils::calc_range_start_string(start_after).map(Bound::ExclusiveRaw);
+
+    let entries = BLACKLIST
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<(String, BlacklistEntry)>>>()?;
+
+    Ok(BlacklistedAddressesResponse { entries })
+}
+
 /// Returns whether an address is authorized (owner or authorized wallet).
 ///
 /// # Arguments
@@ -281,3 +635,280 @@ pub fn query_authorized_wallets(
 
     Ok(AuthorizedWalletsResponse { wallets })
 }
+
+/// Returns the administrative audit log with pagination support. Entries can be filtered by
+/// action name and by subject address.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `action` - Optional action name to filter by, e.g. "blacklist_address"
+/// * `subject` - Optional subject address to filter by
+/// * `start_after` - Optional sequence number to start pagination from
+/// * `limit` - Optional limit for pagination
+///
+/// # Returns
+/// * `Result<AuditLogResponse, ContractError>` - The audit log entries
+pub fn query_audit_log(
+    deps: Deps,
+    action: Option<String>,
+    subject: Option<String>,
+    start_after: Option<u64>,
+    limit: Option<u16>,
+) -> Result<AuditLogResponse, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let subject = subject
+        .map(|subject| helpers::validate_raw_address(deps, &subject))
+        .transpose()?;
+
+    let log = audit_log();
+    let entries = match (action, subject) {
+        (Some(action), subject) => log
+            .idx
+            .action
+            .prefix(action)
+            .range(deps.storage, start, None, Order::Ascending)
+            .filter(|item| match (item, &subject) {
+                (Ok((_, entry)), Some(subject)) => entry.subject.as_ref() == Some(subject),
+                _ => true,
+            })
+            .take(limit)
+            .map(|item| item.map(|(_, entry)| entry))
+            .collect::<StdResult<Vec<AuditEntry>>>()?,
+        (None, Some(subject)) => log
+            .idx
+            .subject
+            .prefix(subject)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(_, entry)| entry))
+            .collect::<StdResult<Vec<AuditEntry>>>()?,
+        (None, None) => log
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(_, entry)| entry))
+            .collect::<StdResult<Vec<AuditEntry>>>()?,
+    };
+
+    Ok(AuditLogResponse { entries })
+}
+
+/// Returns the individual claims made by an address with pagination support.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `address` - The address to get the claim history for
+/// * `start_after` - Optional sequence number to start pagination from
+/// * `limit` - Optional limit for pagination
+///
+/// # Returns
+/// * `Result<ClaimHistoryResponse, ContractError>` - The claim history
+pub fn query_claim_history(
+    deps: Deps,
+    address: String,
+    start_after: Option<u64>,
+    limit: Option<u16>,
+) -> Result<ClaimHistoryResponse, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let address = helpers::validate_raw_address(deps, &address)?;
+
+    let history = CLAIM_HISTORY
+        .prefix(address.as_str())
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, record)| record))
+        .collect::<StdResult<Vec<ClaimRecord>>>()?;
+
+    Ok(ClaimHistoryResponse { history })
+}
+
+/// Returns the allocations joined with the claims and blacklist entries of each address, with
+/// pagination. Used to hand the state of the campaign over to another contract, which can restore
+/// it with the ImportState message.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `start_after` - Optional address to start pagination from
+/// * `limit` - Optional limit for pagination
+///
+/// # Returns
+/// * `Result<ExportStateResponse, ContractError>` - The state of each address
+pub fn query_export_state(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u16>,
+) -> Result<ExportStateResponse, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = cw_utils::calc_range_start_string(start_after).map(Bound::ExclusiveRaw);
+
+    let entries = ALLOCATIONS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (address, allocation) = item?;
+
+            let claims = CLAIMS
+                .prefix(address.as_str())
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|item| {
+                    let (slot, claim) = item?;
+                    Ok(SlotClaimEntry {
+                        slot,
+                        amount: claim.amount,
+                        timestamp: claim.timestamp,
+                    })
+                })
+                .collect::<StdResult<Vec<SlotClaimEntry>>>()?;
+
+            let blacklist = BLACKLIST.may_load(deps.storage, address.as_str())?;
+            let schedule = ALLOCATION_SCHEDULES.may_load(deps.storage, address.as_str())?;
+            let anchors = VESTING_ANCHORS
+                .may_load(deps.storage, address.as_str())?
+                .unwrap_or_default();
+
+            Ok(StateEntry {
+                address,
+                allocation,
+                claims,
+                blacklist,
+                schedule,
+                registered_at: anchors.registered_at,
+                first_claimed_at: anchors.first_claimed_at,
+            })
+        })
+        .collect::<StdResult<Vec<StateEntry>>>()?;
+
+    Ok(ExportStateResponse { entries })
+}
+
+/// Returns the checksum, count and total of the uploaded allocations, and the time they were
+/// finalized, if they have been.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+///
+/// # Returns
+/// * `Result<AllocationChecksumResponse, ContractError>` - The allocation checksum
+pub fn query_allocation_checksum(deps: Deps) -> Result<AllocationChecksumResponse, ContractError> {
+    let checksum = AllocationChecksum::load(deps.storage)?;
+
+    Ok(AllocationChecksumResponse {
+        hash: HexBinary::from(checksum.hash),
+        count: checksum.count,
+        total: checksum.total,
+        finalized_at: checksum.finalized_at,
+    })
+}
+
+/// Returns the IBC channels allowed to send claim packets, with pagination support.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `start_after` - Optional channel to start pagination from
+/// * `limit` - Optional limit for pagination
+///
+/// # Returns
+/// * `Result<IbcChannelsResponse, ContractError>` - The allowed channels
+pub fn query_ibc_channels(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<IbcChannelsResponse, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT as u32).min(MAX_LIMIT as u32) as usize;
+    let start = cw_utils::calc_range_start_string(start_after).map(Bound::ExclusiveRaw);
+
+    let channels = IBC_CHANNELS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<(String, IbcChannelConfig)>>>()?;
+
+    Ok(IbcChannelsResponse { channels })
+}
+
+/// Returns the operators approved to claim on behalf of an address, with pagination support.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `owner` - The address that approved the operators
+/// * `start_after` - Optional operator to start pagination from
+/// * `limit` - Optional limit for pagination
+///
+/// # Returns
+/// * `Result<ClaimApprovalsResponse, ContractError>` - The approvals, including expired ones
+pub fn query_claim_approvals(
+    deps: Deps,
+    owner: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<ClaimApprovalsResponse, ContractError> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT as u32).min(MAX_LIMIT as u32) as usize;
+    let start = cw_utils::calc_range_start_string(start_after).map(Bound::ExclusiveRaw);
+
+    let approvals = CLAIM_APPROVALS
+        .prefix(owner.as_str())
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<(String, ClaimApproval)>>>()?;
+
+    Ok(ClaimApprovalsResponse { approvals })
+}
+
+/// Returns the auto-claim configuration and the addresses subscribed to auto-claims, with
+/// pagination support.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `start_after` - Optional address to start pagination from
+/// * `limit` - Optional limit for pagination
+///
+/// # Returns
+/// * `Result<AutoClaimResponse, ContractError>` - The auto-claim configuration and subscriptions
+pub fn query_auto_claim(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<AutoClaimResponse, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT as u32).min(MAX_LIMIT as u32) as usize;
+    let start = cw_utils::calc_range_start_string(start_after).map(Bound::ExclusiveRaw);
+
+    let subscriptions = AUTO_CLAIM_SUBSCRIPTIONS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<String>>>()?;
+
+    Ok(AutoClaimResponse {
+        tip: CRANK_TIP.may_load(deps.storage)?.unwrap_or_default(),
+        tip_pool: CRANK_TIP_POOL.may_load(deps.storage)?.unwrap_or_default(),
+        cursor: AUTO_CLAIM_CURSOR.may_load(deps.storage)?,
+        subscriptions,
+    })
+}
+
+/// Returns whether the contract is paused.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+///
+/// # Returns
+/// * `Result<PausedResponse, ContractError>` - The pause status
+pub fn query_is_paused(deps: Deps) -> Result<PausedResponse, ContractError> {
+    Ok(PausedResponse {
+        is_paused: is_paused(deps.storage)?,
+    })
+}
+
+/// Returns the version of the contract and the progress of the data migration, if any.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+///
+/// # Returns
+/// * `Result<MigrationStatusResponse, ContractError>` - The migration status
+pub fn query_migration_status(deps: Deps) -> Result<MigrationStatusResponse, ContractError> {
+    Ok(MigrationStatusResponse {
+        version: cw2::get_contract_version(deps.storage)?.version,
+        migration: MIGRATION.may_load(deps.storage)?,
+    })
+}
@@ -1,20 +1,60 @@
 use std::collections::HashMap;
 
-use cosmwasm_std::{Addr, Deps, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{
+    Addr, Coin, Decimal, Decimal256, Deps, DepsMut, Env, Order, OverflowError, OverflowOperation,
+    StdResult, Storage, Timestamp, Uint128, Uint256,
+};
+use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 use crate::helpers;
 use mantra_claimdrop_std::error::ContractError;
-use mantra_claimdrop_std::msg::Campaign;
+use mantra_claimdrop_std::msg::{
+    AuditAction, AuditEntry, BlacklistEntry, BlacklistReason, Campaign, ClaimApproval, ClaimRecord,
+    DistributionType, ForfeitureReason, IbcChannelConfig, MigrationProgress, VestingAnchor,
+};
 
 /// The campaign item that stores the current active campaign
 pub const CAMPAIGN: Item<Campaign> = Item::new("campaign");
 
 /// Contains information about how much has an address claimed for a given distribution type.
-/// The key is the address and the value is a hashmap where the key is the distribution_type index,
-/// and the value is a tuple with the amount claimed and the timestamp at which it was claimed.
+/// The key is (address, distribution_type index) and the value is the amount claimed along with
+/// the timestamp at which it was last claimed.
 /// Used primarily to compute the correct claim amounts when doing the linear vesting.
-pub const CLAIMS: Map<String, HashMap<DistributionSlot, Claim>> = Map::new("claims");
+pub const CLAIMS: Map<(&str, u64), SlotClaim> = Map::new("slot_claims");
+
+/// The claims as stored by versions prior to 2.2.0, keyed by address, where the value is a hashmap
+/// with the distribution_type index as key.
+const LEGACY_CLAIMS: Map<String, HashMap<DistributionSlot, Claim>> = Map::new("claims");
+
+/// The amount an address has claimed from a distribution slot.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SlotClaim {
+    /// The total amount claimed from the slot
+    pub amount: Uint128,
+    /// The timestamp of the last claim from the slot, in seconds
+    pub timestamp: u64,
+}
+
+impl From<Claim> for SlotClaim {
+    fn from((amount, timestamp): Claim) -> Self {
+        SlotClaim { amount, timestamp }
+    }
+}
+
+impl From<SlotClaim> for Claim {
+    fn from(claim: SlotClaim) -> Self {
+        (claim.amount, claim.timestamp)
+    }
+}
+
+/// Stores every individual claim made by an address. The key is (address, sequence), where the
+/// sequence is unique across all addresses.
+pub const CLAIM_HISTORY: Map<(&str, u64), ClaimRecord> = Map::new("claim_history");
+
+/// Stores the sequence number of the last claim appended to the claim history.
+pub const CLAIM_HISTORY_SEQUENCE: Item<u64> = Item::new("claim_history_sequence");
 
 /// The claim is a tuple of the amount and the timestamp when it was claimed.
 pub type Claim = (Uint128, u64);
@@ -25,13 +65,386 @@ pub type DistributionSlot = usize;
 /// and cannot be modified after that.
 pub const ALLOCATIONS: Map<&str, Uint128> = Map::new("allocations");
 
-/// Stores blacklisted addresses. Blacklisted addresses cannot claim their allocations.
-pub const BLACKLIST: Map<&str, bool> = Map::new("blacklist");
+/// Stores the schedule the allocation of an address vests on, for the allocations that don't vest
+/// on the distribution types of the campaign.
+pub const ALLOCATION_SCHEDULES: Map<&str, String> = Map::new("allocation_schedules");
+
+/// Stores the times the relative distribution types of an address are anchored to.
+pub const VESTING_ANCHORS: Map<&str, VestingAnchors> = Map::new("vesting_anchors");
+
+/// Stores the sum of all the allocations.
+pub const TOTAL_ALLOCATED: Item<Uint128> = Item::new("total_allocated");
+
+/// The times the relative distribution types of an address are anchored to.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default)]
+pub struct VestingAnchors {
+    /// The time the allocation was added, if it was added after the campaign started. Otherwise
+    /// the allocation is anchored at the start time of the campaign.
+    pub registered_at: Option<u64>,
+    /// The vesting time of the first claim of the address
+    pub first_claimed_at: Option<u64>,
+    /// The reward per share of the campaign when the allocation was added. The allocation only
+    /// shares in the forfeitures redistributed afterwards.
+    #[serde(default)]
+    pub registered_reward_per_share: Decimal256,
+}
+
+/// Stores the checksum, count and total of the allocations, checked when the allocations are
+/// finalized.
+pub const ALLOCATION_CHECKSUM: Item<AllocationChecksum> = Item::new("allocation_checksum");
+
+/// The checksum, count and total of the allocations, kept up to date as they're uploaded, replaced
+/// and removed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AllocationChecksum {
+    /// The sum of the SHA-256 hashes of the (address, amount) pairs, modulo 2^256
+    pub hash: [u8; 32],
+    /// The number of allocations
+    pub count: u64,
+    /// The sum of the allocations
+    pub total: Uint128,
+    /// The time the allocations were finalized, in seconds. No allocations can be uploaded or
+    /// removed after that.
+    pub finalized_at: Option<u64>,
+}
+
+impl AllocationChecksum {
+    /// Loads the checksum of the uploaded allocations, which starts as 32 zero bytes
+    pub fn load(storage: &dyn Storage) -> StdResult<Self> {
+        Ok(ALLOCATION_CHECKSUM
+            .may_load(storage)?
+            .unwrap_or(AllocationChecksum {
+                hash: [0; 32],
+                count: 0,
+                total: Uint128::zero(),
+                finalized_at: None,
+            }))
+    }
+
+    /// Adds an allocation to the checksum, adding
+    /// `sha256(address || amount as 16 big-endian bytes)` to the hash
+    pub fn record(&mut self, address: &str, amount: Uint128) -> Result<(), ContractError> {
+        self.hash = Uint256::from_be_bytes(self.hash)
+            .wrapping_add(Self::allocation_hash(address, amount))
+            .to_be_bytes();
+        self.count += 1;
+        self.total = self.total.checked_add(amount)?;
+
+        Ok(())
+    }
+
+    /// Takes an allocation out of the checksum, reverting [AllocationChecksum::record]
+    pub fn unrecord(&mut self, address: &str, amount: Uint128) -> Result<(), ContractError> {
+        self.hash = Uint256::from_be_bytes(self.hash)
+            .wrapping_sub(Self::allocation_hash(address, amount))
+            .to_be_bytes();
+        self.count = self
+            .count
+            .checked_sub(1)
+            .ok_or(OverflowError::new(OverflowOperation::Sub))?;
+        self.total = self.total.checked_sub(amount)?;
+
+        Ok(())
+    }
+
+    fn allocation_hash(address: &str, amount: Uint128) -> Uint256 {
+        let mut hasher = Sha256::new();
+        hasher.update(address.as_bytes());
+        hasher.update(amount.u128().to_be_bytes());
+
+        Uint256::from_be_bytes(hasher.finalize().into())
+    }
+}
+
+/// Stores the addresses that have exited the distributions with an early exit penalty.
+pub const EARLY_EXITS: Map<&str, EarlyExit> = Map::new("early_exits");
+
+/// Stores the addresses whose allocations have been forfeited.
+pub const FORFEITURES: Map<&str, Forfeiture> = Map::new("forfeitures");
+
+/// Stores the sum of the allocations of the addresses that have exited early or forfeited their
+/// allocation, which no longer share in the redistributed forfeitures.
+pub const EXITED_SHARES: Item<Uint128> = Item::new("exited_shares");
+
+/// An early exit from the distributions with an early exit penalty.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EarlyExit {
+    /// The timestamp of the early exit, in seconds
+    pub timestamp: u64,
+    /// The campaign reward per share at the time of the early exit, which the allocation of the
+    /// address is frozen at
+    pub reward_per_share: Decimal256,
+    /// The amount forfeited by the early exit
+    pub penalty: Coin,
+}
+
+/// A forfeited allocation.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Forfeiture {
+    /// The timestamp of the forfeiture, in seconds
+    pub timestamp: u64,
+    /// Why the allocation was forfeited
+    pub reason: ForfeitureReason,
+    /// The campaign reward per share at the time of the forfeiture, which the allocation of the
+    /// address is frozen at
+    pub reward_per_share: Decimal256,
+    /// The amount forfeited
+    pub amount: Coin,
+}
+
+/// Stores blacklisted addresses, along with the reason, time and sender of the blacklisting.
+/// Blacklisted addresses cannot claim their allocations.
+pub const BLACKLIST: Map<&str, BlacklistEntry> = Map::new("blacklist");
+
+/// Stores the periods, as (start, end) timestamps in seconds, during which the vesting clock of an
+/// address was frozen by a blacklisting that has since been lifted.
+pub const VESTING_FREEZES: Map<&str, Vec<(u64, u64)>> = Map::new("vesting_freezes");
+
+/// A blacklist value as stored in the [BLACKLIST] map. Versions prior to 2.1.0 only stored a flag.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum StoredBlacklistValue {
+    Flag(bool),
+    Entry(BlacklistEntry),
+}
+
+/// Rewrites the blacklist entries stored as a plain flag by previous versions of the contract.
+/// As the original time and sender are unknown, the time of the migration and the contract
+/// address are recorded instead.
+///
+/// # Arguments
+/// * `storage` - The storage
+/// * `env` - The environment
+/// * `cursor` - The last address processed by the previous batch, if any
+/// * `limit` - The maximum number of entries to process
+///
+/// # Returns
+/// * `Result<(usize, Option<String>), ContractError>` - The number of entries processed, and the
+///   last address processed if there may be more entries left
+pub(crate) fn migrate_blacklist_flags(
+    storage: &mut dyn Storage,
+    env: &Env,
+    cursor: Option<String>,
+    limit: usize,
+) -> Result<(usize, Option<String>), ContractError> {
+    let stored_blacklist: Map<&str, StoredBlacklistValue> = Map::new("blacklist");
+
+    let entries = stored_blacklist
+        .range(
+            storage,
+            cursor.as_deref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<(String, StoredBlacklistValue)>>>()?;
+
+    for (address, value) in entries.iter() {
+        match value {
+            StoredBlacklistValue::Flag(true) => BLACKLIST.save(
+                storage,
+                address,
+                &BlacklistEntry {
+                    reason: BlacklistReason::Unspecified,
+                    blacklisted_at: env.block.time.seconds(),
+                    blacklisted_by: env.contract.address.to_string(),
+                    note: None,
+                    expiration: None,
+                    freeze_vesting: false,
+                },
+            )?,
+            StoredBlacklistValue::Flag(false) => BLACKLIST.remove(storage, address),
+            StoredBlacklistValue::Entry(_) => {}
+        }
+    }
+
+    Ok(migration_batch(entries, limit))
+}
+
+/// Sums the allocations stored by versions prior to 2.3.0, which didn't keep track of the total
+/// allocated amount.
+///
+/// # Arguments
+/// * `storage` - The storage
+/// * `_env` - The environment
+/// * `cursor` - The last address processed by the previous batch, if any
+/// * `limit` - The maximum number of allocations to process
+///
+/// # Returns
+/// * `Result<(usize, Option<String>), ContractError>` - The number of allocations processed, and
+///   the last address processed if there may be more allocations left
+pub(crate) fn migrate_allocation_totals(
+    storage: &mut dyn Storage,
+    _env: &Env,
+    cursor: Option<String>,
+    limit: usize,
+) -> Result<(usize, Option<String>), ContractError> {
+    let total_allocated = match cursor {
+        Some(_) => TOTAL_ALLOCATED.may_load(storage)?.unwrap_or_default(),
+        None => Uint128::zero(),
+    };
+
+    let allocations = ALLOCATIONS
+        .range(
+            storage,
+            cursor.as_deref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<(String, Uint128)>>>()?;
+
+    let total_allocated = allocations
+        .iter()
+        .try_fold(total_allocated, |total, (_, allocation)| {
+            total.checked_add(*allocation)
+        })?;
+    TOTAL_ALLOCATED.save(storage, &total_allocated)?;
+
+    Ok(migration_batch(allocations, limit))
+}
+
+/// Returns the number of entries processed by a migration batch, along with the last key
+/// processed if the batch was full, as there may be more entries left.
+fn migration_batch<T>(entries: Vec<(String, T)>, limit: usize) -> (usize, Option<String>) {
+    let processed = entries.len();
+    let cursor = if processed == limit {
+        entries.into_iter().last().map(|(key, _)| key)
+    } else {
+        None
+    };
+
+    (processed, cursor)
+}
 
 /// Stores authorized wallet addresses that can perform admin actions.
 /// Key: address string, Value: () (presence indicates authorization)
 pub const AUTHORIZED_WALLETS: Map<&str, ()> = Map::new("authorized_wallets");
 
+/// Stores the operators allowed to claim on behalf of allocation owners. The key is
+/// (owner, operator).
+pub const CLAIM_APPROVALS: Map<(&str, &str), ClaimApproval> = Map::new("claim_approvals");
+
+/// Stores the addresses subscribed to auto-claims.
+pub const AUTO_CLAIM_SUBSCRIPTIONS: Map<&str, ()> = Map::new("auto_claim_subscriptions");
+
+/// Stores the last address processed by the previous crank, if the crank hasn't gone through all
+/// the subscribed addresses yet.
+pub const AUTO_CLAIM_CURSOR: Item<String> = Item::new("auto_claim_cursor");
+
+/// Stores the share of the auto-claimed rewards paid to crank callers. Defaults to zero.
+pub const CRANK_TIP: Item<Decimal> = Item::new("crank_tip");
+
+/// Stores the funds set aside to pay the crank tips, kept apart from the funds available for
+/// claims. Defaults to zero.
+pub const CRANK_TIP_POOL: Item<Uint128> = Item::new("crank_tip_pool");
+
+/// Stores the IBC channels allowed to send claim packets, with their configuration.
+pub const IBC_CHANNELS: Map<&str, IbcChannelConfig> = Map::new("ibc_channels");
+
+/// Stores the claims paid out over ICS-20 transfers that haven't been acknowledged yet. The key is
+/// (channel id, packet sequence). The claims are restored if the transfer fails.
+pub const PENDING_IBC_TRANSFERS: Map<(&str, u64), PendingIbcTransfer> =
+    Map::new("pending_ibc_transfers");
+
+/// A claim paid out over an ICS-20 transfer that is awaiting its acknowledgement.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PendingIbcTransfer {
+    /// The ICS-20 transfer channel the tokens were sent over
+    pub channel_id: String,
+    /// The address whose allocation was claimed
+    pub address: String,
+    /// The amount transferred
+    pub amount: Coin,
+    /// A vector with a tuple with (distribution slot, amount) breaking down the claimed amount
+    pub slots: Vec<(u64, Uint128)>,
+    /// The sequence number of the claim in the claim history
+    pub history_sequence: u64,
+    /// The operator that claimed on behalf of the address through a claim approval, if any
+    #[serde(default)]
+    pub approved_operator: Option<String>,
+    /// Whether the claim anchored the distribution types relative to the first claim
+    #[serde(default)]
+    pub anchored_first_claim: bool,
+}
+
+/// Whether the contract has been paused through a sudo message.
+pub const PAUSED: Item<bool> = Item::new("paused");
+
+/// Stores the progress of the data migration in progress, if any.
+pub const MIGRATION: Item<MigrationProgress> = Item::new("migration");
+
+/// Stores the sequence number of the last entry appended to the audit log.
+pub const AUDIT_LOG_SEQUENCE: Item<u64> = Item::new("audit_log_sequence");
+
+/// Indexes for the audit log, by action name and by subject address. Entries without a subject
+/// are indexed under an empty string, which is never a valid address.
+pub struct AuditLogIndexes<'a> {
+    pub action: MultiIndex<'a, String, AuditEntry, u64>,
+    pub subject: MultiIndex<'a, String, AuditEntry, u64>,
+}
+
+impl IndexList<AuditEntry> for AuditLogIndexes<'_> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<AuditEntry>> + '_> {
+        let v: Vec<&dyn Index<AuditEntry>> = vec![&self.action, &self.subject];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Stores the audit log of administrative actions, keyed by sequence number.
+pub fn audit_log<'a>() -> IndexedMap<u64, AuditEntry, AuditLogIndexes<'a>> {
+    let indexes = AuditLogIndexes {
+        action: MultiIndex::new(
+            |_pk, entry: &AuditEntry| entry.action.kind().to_string(),
+            "audit_log",
+            "audit_log__action",
+        ),
+        subject: MultiIndex::new(
+            |_pk, entry: &AuditEntry| entry.subject.clone().unwrap_or_default(),
+            "audit_log",
+            "audit_log__subject",
+        ),
+    };
+    IndexedMap::new("audit_log", indexes)
+}
+
+/// Appends an entry to the audit log
+///
+/// # Arguments
+/// * `storage` - The storage
+/// * `env` - The environment
+/// * `sender` - The address that performed the action
+/// * `action` - The action performed
+/// * `subject` - The address affected by the action, if any
+///
+/// # Returns
+/// * `Result<(), ContractError>` - Success or storage error
+pub fn record_audit_entry(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: &Addr,
+    action: AuditAction,
+    subject: Option<String>,
+) -> Result<(), ContractError> {
+    let sequence = AUDIT_LOG_SEQUENCE.may_load(storage)?.unwrap_or_default() + 1;
+    AUDIT_LOG_SEQUENCE.save(storage, &sequence)?;
+
+    audit_log().save(
+        storage,
+        sequence,
+        &AuditEntry {
+            sequence,
+            height: env.block.height,
+            time: env.block.time.seconds(),
+            sender: sender.to_string(),
+            action,
+            subject,
+        },
+    )?;
+
+    Ok(())
+}
+
 /// Returns the claims that an address has made for a campaign
 ///
 /// # Arguments
@@ -44,8 +457,101 @@ pub fn get_claims_for_address(
     deps: Deps,
     address: String,
 ) -> Result<HashMap<DistributionSlot, Claim>, ContractError> {
-    let claimed = CLAIMS.may_load(deps.storage, helpers::validate_raw_address(deps, &address)?)?;
-    Ok(claimed.unwrap_or_default())
+    let address = helpers::validate_raw_address(deps, &address)?;
+
+    CLAIMS
+        .prefix(address.as_str())
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (slot, claim) = item?;
+            Ok((slot as DistributionSlot, claim.into()))
+        })
+        .collect()
+}
+
+/// Saves the claims of an address for the distribution slots present in the given claims.
+/// Slots not present are left untouched.
+///
+/// # Arguments
+/// * `storage` - The storage
+/// * `address` - The address the claims belong to
+/// * `claims` - The claims per distribution slot
+///
+/// # Returns
+/// * `Result<(), ContractError>` - Success or storage error
+pub fn save_claims(
+    storage: &mut dyn Storage,
+    address: &str,
+    claims: &HashMap<DistributionSlot, Claim>,
+) -> Result<(), ContractError> {
+    for (slot, claim) in claims.iter() {
+        CLAIMS.save(storage, (address, *slot as u64), &(*claim).into())?;
+    }
+
+    Ok(())
+}
+
+/// Moves the claims of an address to a new address
+///
+/// # Arguments
+/// * `storage` - The storage
+/// * `old_address` - The address to move the claims from
+/// * `new_address` - The address to move the claims to
+///
+/// # Returns
+/// * `Result<(), ContractError>` - Success or storage error
+pub fn move_claims(
+    storage: &mut dyn Storage,
+    old_address: &str,
+    new_address: &str,
+) -> Result<(), ContractError> {
+    let claims = CLAIMS
+        .prefix(old_address)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(u64, SlotClaim)>>>()?;
+
+    for (slot, claim) in claims {
+        CLAIMS.remove(storage, (old_address, slot));
+        CLAIMS.save(storage, (new_address, slot), &claim)?;
+    }
+
+    Ok(())
+}
+
+/// Rewrites the claims stored by previous versions of the contract, as a hashmap per address,
+/// under the (address, distribution slot) keys of [CLAIMS].
+///
+/// # Arguments
+/// * `storage` - The storage
+/// * `_env` - The environment
+/// * `cursor` - The last address processed by the previous batch, if any
+/// * `limit` - The maximum number of addresses to process
+///
+/// # Returns
+/// * `Result<(usize, Option<String>), ContractError>` - The number of addresses processed, and the
+///   last address processed if there may be more addresses left
+pub(crate) fn migrate_legacy_claims(
+    storage: &mut dyn Storage,
+    _env: &Env,
+    cursor: Option<String>,
+    limit: usize,
+) -> Result<(usize, Option<String>), ContractError> {
+    let legacy_claims = LEGACY_CLAIMS
+        .range(
+            storage,
+            cursor.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<(String, HashMap<DistributionSlot, Claim>)>>>()?;
+
+    for (address, claims) in legacy_claims.iter() {
+        save_claims(storage, address, claims)?;
+        LEGACY_CLAIMS.remove(storage, address.to_string());
+    }
+
+    Ok(migration_batch(legacy_claims, limit))
 }
 
 /// Returns the total amount of tokens claimed by an address
@@ -69,6 +575,231 @@ pub fn get_total_claims_amount_for_address(
     Ok(total)
 }
 
+/// Appends a claim to the claim history of an address
+///
+/// # Arguments
+/// * `storage` - The storage
+/// * `env` - The environment
+/// * `sender` - The address that initiated the claim
+/// * `address` - The address whose allocation was claimed
+/// * `destination` - The address the claimed tokens were sent to
+/// * `amount` - The total amount claimed
+/// * `claims` - The claimed amount per distribution slot
+///
+/// # Returns
+/// * `Result<(), ContractError>` - Success or storage error
+pub fn record_claim(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: &Addr,
+    address: &str,
+    destination: &str,
+    amount: &Coin,
+    claims: &HashMap<DistributionSlot, Claim>,
+) -> Result<(), ContractError> {
+    let sequence = CLAIM_HISTORY_SEQUENCE
+        .may_load(storage)?
+        .unwrap_or_default()
+        + 1;
+    CLAIM_HISTORY_SEQUENCE.save(storage, &sequence)?;
+
+    CLAIM_HISTORY.save(
+        storage,
+        (address, sequence),
+        &ClaimRecord {
+            sequence,
+            timestamp: env.block.time.seconds(),
+            amount: amount.clone(),
+            slots: helpers::slot_amounts(claims),
+            destination: destination.to_string(),
+            sender: sender.to_string(),
+        },
+    )?;
+
+    Ok(())
+}
+
+/// The outcome of restoring a claim whose ICS-20 transfer failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ClaimRestoration {
+    /// The claim was reverted, so it can be claimed again
+    Restored,
+    /// The campaign was closed, so the claim can't be made again
+    CampaignClosed,
+    /// The claimed amounts are no longer recorded for the address, so there's nothing to revert
+    ClaimsMissing,
+}
+
+/// Reverts a claim whose ICS-20 transfer failed, so the tokens can be claimed again. The claim is
+/// removed from the claim history, the amount is given back to the claim approval it was made
+/// through, and the first claim anchor it set is cleared if no other claims were made since. Nothing is reverted if the campaign was closed in the meantime,
+/// or if the claimed amounts are no longer recorded for the address.
+///
+/// # Arguments
+/// * `storage` - The storage
+/// * `transfer` - The failed transfer
+///
+/// # Returns
+/// * `Result<ClaimRestoration, ContractError>` - Whether the claim was restored
+pub fn restore_claim(
+    storage: &mut dyn Storage,
+    transfer: &PendingIbcTransfer,
+) -> Result<ClaimRestoration, ContractError> {
+    let address = transfer.address.as_str();
+
+    // the campaign is kept after being closed, so it's only missing if it was never created
+    let Some(mut campaign) = CAMPAIGN
+        .may_load(storage)?
+        .filter(|campaign| campaign.closed.is_none())
+    else {
+        return Ok(ClaimRestoration::CampaignClosed);
+    };
+
+    let mut restored_claims = vec![];
+    for (slot, amount) in transfer.slots.iter() {
+        match CLAIMS.may_load(storage, (address, *slot))? {
+            Some(mut claim) if claim.amount >= *amount => {
+                claim.amount -= *amount;
+                restored_claims.push((*slot, claim));
+            }
+            _ => return Ok(ClaimRestoration::ClaimsMissing),
+        }
+    }
+
+    for (slot, claim) in restored_claims {
+        if claim.amount.is_zero() {
+            CLAIMS.remove(storage, (address, slot));
+        } else {
+            CLAIMS.save(storage, (address, slot), &claim)?;
+        }
+    }
+
+    campaign.claimed.amount = campaign
+        .claimed
+        .amount
+        .checked_sub(transfer.amount.amount)?;
+    CAMPAIGN.save(storage, &campaign)?;
+
+    CLAIM_HISTORY.remove(storage, (address, transfer.history_sequence));
+
+    if let Some(operator) = transfer.approved_operator.as_deref() {
+        // the approval may have been revoked in the meantime
+        if let Some(mut approval) = CLAIM_APPROVALS.may_load(storage, (address, operator))? {
+            if let Some(max_amount) = approval.max_amount {
+                approval.max_amount = Some(max_amount.checked_add(transfer.amount.amount)?);
+                CLAIM_APPROVALS.save(storage, (address, operator), &approval)?;
+            }
+        }
+    }
+
+    // later claims were vested relative to the anchor, so it's only cleared if there are none
+    if transfer.anchored_first_claim
+        && CLAIMS
+            .prefix(address)
+            .range(storage, None, None, Order::Ascending)
+            .next()
+            .is_none()
+    {
+        if let Some(mut anchors) = VESTING_ANCHORS.may_load(storage, address)? {
+            anchors.first_claimed_at = None;
+            VESTING_ANCHORS.save(storage, address, &anchors)?;
+        }
+    }
+
+    Ok(ClaimRestoration::Restored)
+}
+
+/// Moves the claim approvals granted by an address to a new address, so the operators it approved
+/// can keep claiming on its behalf. The approvals granted to the address by others are kept as
+/// they are, as they were granted to that address only.
+///
+/// # Arguments
+/// * `storage` - The storage
+/// * `old_address` - The address to move the claim approvals from
+/// * `new_address` - The address to move the claim approvals to
+///
+/// # Returns
+/// * `Result<(), ContractError>` - Success or storage error
+pub fn move_claim_approvals(
+    storage: &mut dyn Storage,
+    old_address: &str,
+    new_address: &str,
+) -> Result<(), ContractError> {
+    let approvals = CLAIM_APPROVALS
+        .prefix(old_address)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(String, ClaimApproval)>>>()?;
+
+    for (operator, approval) in approvals {
+        CLAIM_APPROVALS.remove(storage, (old_address, operator.as_str()));
+        // the new address can't approve itself
+        if operator != new_address {
+            CLAIM_APPROVALS.save(storage, (new_address, operator.as_str()), &approval)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Moves the pending ICS-20 transfers of an address to a new address, so they're restored to it if
+/// they fail. Pending transfers only live until they're acknowledged, so there are few of them.
+///
+/// # Arguments
+/// * `storage` - The storage
+/// * `old_address` - The address to move the pending transfers from
+/// * `new_address` - The address to move the pending transfers to
+///
+/// # Returns
+/// * `Result<(), ContractError>` - Success or storage error
+pub fn move_pending_ibc_transfers(
+    storage: &mut dyn Storage,
+    old_address: &str,
+    new_address: &str,
+) -> Result<(), ContractError> {
+    let transfers = PENDING_IBC_TRANSFERS
+        .range(storage, None, None, Order::Ascending)
+        .filter(|transfer| {
+            transfer
+                .as_ref()
+                .map_or(true, |(_, transfer)| transfer.address == old_address)
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for ((channel_id, sequence), mut transfer) in transfers {
+        transfer.address = new_address.to_string();
+        PENDING_IBC_TRANSFERS.save(storage, (channel_id.as_str(), sequence), &transfer)?;
+    }
+
+    Ok(())
+}
+
+/// Moves the claim history of an address to a new address
+///
+/// # Arguments
+/// * `storage` - The storage
+/// * `old_address` - The address to move the claim history from
+/// * `new_address` - The address to move the claim history to
+///
+/// # Returns
+/// * `Result<(), ContractError>` - Success or storage error
+pub fn move_claim_history(
+    storage: &mut dyn Storage,
+    old_address: &str,
+    new_address: &str,
+) -> Result<(), ContractError> {
+    let history = CLAIM_HISTORY
+        .prefix(old_address)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(u64, ClaimRecord)>>>()?;
+
+    for (sequence, record) in history {
+        CLAIM_HISTORY.remove(storage, (old_address, sequence));
+        CLAIM_HISTORY.save(storage, (new_address, sequence), &record)?;
+    }
+
+    Ok(())
+}
+
 /// Returns the allocation for an address
 ///
 /// # Arguments
@@ -84,21 +815,288 @@ pub fn get_allocation(deps: Deps, address: &str) -> Result<Option<Uint128>, Cont
     )?)
 }
 
+/// Returns the allocation for an address including its share of the redistributed forfeitures.
+/// The share of addresses that have exited early or forfeited their allocation is frozen at the
+/// time they did, and allocations added after the campaign started only share in the forfeitures
+/// redistributed since.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `campaign` - The campaign
+/// * `address` - The address to get the allocation for
+///
+/// # Returns
+/// * `Result<Option<Uint128>, ContractError>` - The effective allocation amount if it exists
+pub fn get_effective_allocation(
+    deps: Deps,
+    campaign: &Campaign,
+    address: &str,
+) -> Result<Option<Uint128>, ContractError> {
+    let Some(allocation) = get_allocation(deps, address)? else {
+        return Ok(None);
+    };
+
+    let reward_per_share = match (
+        EARLY_EXITS.may_load(deps.storage, address)?,
+        FORFEITURES.may_load(deps.storage, address)?,
+    ) {
+        (_, Some(forfeiture)) => forfeiture.reward_per_share,
+        (Some(early_exit), None) => early_exit.reward_per_share,
+        (None, None) => campaign.reward_per_share,
+    };
+    let registered_reward_per_share = VESTING_ANCHORS
+        .may_load(deps.storage, address)?
+        .unwrap_or_default()
+        .registered_reward_per_share;
+
+    let redistributed = Uint128::try_from(
+        Decimal256::from_ratio(allocation, 1u128)
+            .checked_mul(reward_per_share.saturating_sub(registered_reward_per_share))?
+            .to_uint_floor(),
+    )?;
+
+    Ok(Some(allocation.checked_add(redistributed)?))
+}
+
+/// Stops an allocation from sharing in the redistributed forfeitures, once the address it belongs
+/// to has exited early or forfeited it.
+///
+/// # Arguments
+/// * `storage` - The storage
+/// * `allocation` - The allocation to stop sharing in the redistributed forfeitures
+///
+/// # Returns
+/// * `Result<(), ContractError>` - Success or error
+pub fn retire_shares(storage: &mut dyn Storage, allocation: Uint128) -> Result<(), ContractError> {
+    let exited_shares = EXITED_SHARES
+        .may_load(storage)?
+        .unwrap_or_default()
+        .checked_add(allocation)?;
+    EXITED_SHARES.save(storage, &exited_shares)?;
+
+    Ok(())
+}
+
+/// Shares the given amount pro rata among the allocations still sharing in the redistributed
+/// forfeitures, by increasing the reward per share of the campaign. The caller is responsible for
+/// saving the campaign.
+///
+/// # Arguments
+/// * `storage` - The storage
+/// * `campaign` - The campaign
+/// * `amount` - The amount to redistribute
+///
+/// # Returns
+/// * `Result<bool, ContractError>` - Whether the amount was redistributed. If no allocation
+///   shares in the redistributed forfeitures, the amount is left in the contract and returned to
+///   the owner when the campaign is closed.
+pub fn redistribute(
+    storage: &dyn Storage,
+    campaign: &mut Campaign,
+    amount: Uint128,
+) -> Result<bool, ContractError> {
+    let remaining_shares = TOTAL_ALLOCATED
+        .may_load(storage)?
+        .unwrap_or_default()
+        .checked_sub(EXITED_SHARES.may_load(storage)?.unwrap_or_default())?;
+
+    if amount.is_zero() || remaining_shares.is_zero() {
+        return Ok(false);
+    }
+
+    campaign.reward_per_share = campaign
+        .reward_per_share
+        .checked_add(Decimal256::from_ratio(amount, remaining_shares))?;
+
+    Ok(true)
+}
+
+/// Returns the distribution types the allocation of an address vests on, which are the ones of its
+/// schedule if it has one, or the ones of the campaign otherwise. Relative distribution types are
+/// anchored at the times stored for the address. If the address hasn't claimed yet, the ones
+/// relative to its first claim are anchored at the given time, i.e. as if it claimed now.
+/// Milestones that have been triggered start at their trigger time.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `campaign` - The campaign
+/// * `address` - The address to get the distribution types for
+/// * `current_time` - The vesting time of the address
+///
+/// # Returns
+/// * `Result<Vec<DistributionType>, ContractError>` - The distribution types of the allocation
+pub fn get_distribution_type(
+    deps: Deps,
+    campaign: &Campaign,
+    address: &str,
+    current_time: &Timestamp,
+) -> Result<Vec<DistributionType>, ContractError> {
+    let schedule = ALLOCATION_SCHEDULES.may_load(deps.storage, address)?;
+    let distribution_type = campaign.distribution_type_for(schedule.as_deref())?;
+
+    if distribution_type.iter().all(|dist| dist.anchor().is_none()) {
+        return Ok(distribution_type
+            .iter()
+            .map(|dist| dist.triggered(&campaign.milestones))
+            .collect());
+    }
+
+    let anchors = VESTING_ANCHORS
+        .may_load(deps.storage, address)?
+        .unwrap_or_default();
+    let registered_at = anchors.registered_at.unwrap_or(campaign.start_time);
+    let first_claimed_at = anchors
+        .first_claimed_at
+        .unwrap_or_else(|| current_time.seconds());
+
+    Ok(distribution_type
+        .iter()
+        .map(|dist| {
+            dist.anchored(registered_at, first_claimed_at)
+                .triggered(&campaign.milestones)
+        })
+        .collect())
+}
+
+/// Anchors the distribution types of an address relative to its first claim at the given vesting
+/// time, unless it has claimed before.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `campaign` - The campaign
+/// * `address` - The address that claimed
+/// * `current_time` - The vesting time of the address
+///
+/// # Returns
+/// * `Result<bool, ContractError>` - Whether the distribution types were anchored
+pub fn anchor_first_claim(
+    deps: DepsMut,
+    campaign: &Campaign,
+    address: &str,
+    current_time: &Timestamp,
+) -> Result<bool, ContractError> {
+    let schedule = ALLOCATION_SCHEDULES.may_load(deps.storage, address)?;
+    let anchored_to_first_claim = campaign
+        .distribution_type_for(schedule.as_deref())?
+        .iter()
+        .any(|dist| dist.anchor() == Some(&VestingAnchor::FirstClaim));
+
+    if !anchored_to_first_claim {
+        return Ok(false);
+    }
+
+    let mut anchors = VESTING_ANCHORS
+        .may_load(deps.storage, address)?
+        .unwrap_or_default();
+
+    if anchors.first_claimed_at.is_some() {
+        return Ok(false);
+    }
+
+    anchors.first_claimed_at = Some(current_time.seconds());
+    VESTING_ANCHORS.save(deps.storage, address, &anchors)?;
+
+    Ok(true)
+}
+
 /// Returns whether an address is blacklisted
 ///
 /// # Arguments
 /// * `deps` - The dependencies
+/// * `env` - The environment
 /// * `address` - The address to check
 ///
 /// # Returns
-/// * `Result<bool, ContractError>` - Whether the address is blacklisted
-pub fn is_blacklisted(deps: Deps, address: &str) -> Result<bool, ContractError> {
+/// * `Result<bool, ContractError>` - Whether the address is blacklisted and the blacklisting hasn't expired
+pub fn is_blacklisted(deps: Deps, env: &Env, address: &str) -> Result<bool, ContractError> {
     Ok(BLACKLIST
         .may_load(
             deps.storage,
             helpers::validate_raw_address(deps, address)?.as_str(),
         )?
-        .unwrap_or(false))
+        .is_some_and(|entry| entry.is_active(&env.block)))
+}
+
+/// Removes the blacklist entry of an address, if any. If the entry froze the vesting clock of the
+/// address, the frozen period is kept in [VESTING_FREEZES].
+///
+/// # Arguments
+/// * `storage` - The storage
+/// * `env` - The environment
+/// * `address` - The address to unblacklist, already validated
+///
+/// # Returns
+/// * `Result<Option<BlacklistEntry>, ContractError>` - The removed entry, if any
+pub(crate) fn lift_blacklist(
+    storage: &mut dyn Storage,
+    env: &Env,
+    address: &str,
+) -> Result<Option<BlacklistEntry>, ContractError> {
+    let Some(entry) = BLACKLIST.may_load(storage, address)? else {
+        return Ok(None);
+    };
+
+    BLACKLIST.remove(storage, address);
+
+    if let Some(period) = entry.frozen_period(env.block.time.seconds()) {
+        let mut freezes = VESTING_FREEZES
+            .may_load(storage, address)?
+            .unwrap_or_default();
+        freezes.push(period);
+        VESTING_FREEZES.save(storage, address, &freezes)?;
+    }
+
+    Ok(Some(entry))
+}
+
+/// Returns the time the vesting clock of an address shows at the given time, that is, the given
+/// time minus the periods since the start of the campaign during which its vesting was frozen.
+///
+/// # Arguments
+/// * `deps` - The dependencies
+/// * `campaign` - The campaign
+/// * `address` - The address
+/// * `time` - The time
+///
+/// # Returns
+/// * `Result<Timestamp, ContractError>` - The vesting time of the address
+pub fn get_vesting_time(
+    deps: Deps,
+    campaign: &Campaign,
+    address: &str,
+    time: &Timestamp,
+) -> Result<Timestamp, ContractError> {
+    let address = helpers::validate_raw_address(deps, address)?;
+
+    let mut periods = VESTING_FREEZES
+        .may_load(deps.storage, address.as_str())?
+        .unwrap_or_default();
+
+    if let Some(period) = BLACKLIST
+        .may_load(deps.storage, address.as_str())?
+        .and_then(|entry| entry.frozen_period(time.seconds()))
+    {
+        periods.push(period);
+    }
+
+    let frozen_seconds = periods.iter().fold(0u64, |acc, (start, end)| {
+        let start = std::cmp::max(*start, campaign.start_time);
+        let end = std::cmp::min(*end, time.seconds());
+        acc + end.saturating_sub(start)
+    });
+
+    Ok(time.minus_seconds(frozen_seconds))
+}
+
+/// Returns whether the contract is paused.
+///
+/// # Arguments
+/// * `storage` - The storage
+///
+/// # Returns
+/// * `Result<bool, ContractError>` - Whether the contract is paused
+pub fn is_paused(storage: &dyn Storage) -> Result<bool, ContractError> {
+    Ok(PAUSED.may_load(storage)?.unwrap_or(false))
 }
 
 /// Checks if an address is authorized (owner or authorized wallet)
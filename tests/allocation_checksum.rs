@@ -0,0 +1,387 @@
+use crate::suite::TestingSuite;
+use cosmwasm_std::{coin, Decimal, HexBinary, Uint128, Uint256};
+use cw_multi_test::AppResponse;
+use mantra_claimdrop_std::error::ContractError;
+use mantra_claimdrop_std::msg::{AuditAction, CampaignAction, CampaignParams, DistributionType};
+use sha2::{Digest, Sha256};
+mod suite;
+
+/// Computes the checksum the contract keeps over the allocations
+fn checksum(allocations: &[(String, Uint128)]) -> HexBinary {
+    let hash = allocations
+        .iter()
+        .fold(Uint256::zero(), |hash, (address, amount)| {
+            let mut hasher = Sha256::new();
+            hasher.update(address.as_bytes());
+            hasher.update(amount.u128().to_be_bytes());
+            hash.wrapping_add(Uint256::from_be_bytes(hasher.finalize().into()))
+        });
+
+    HexBinary::from(hash.to_be_bytes())
+}
+
+fn campaign_params(start_time: u64) -> CampaignParams {
+    CampaignParams {
+        name: "Test Airdrop I".to_string(),
+        description: "This is an airdrop".to_string(),
+        ty: "airdrop".to_string(),
+        reward_denom: "uom".to_string(),
+        total_reward: coin(1_500, "uom"),
+        distribution_type: vec![DistributionType::LumpSum {
+            percentage: Decimal::one(),
+            start_time,
+        }],
+        start_time,
+        end_time: start_time + 86_400 * 10,
+        schedules: vec![],
+        require_finalized_allocations: true,
+    }
+}
+
+#[test]
+fn finalize_allocations_with_matching_checksum() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+    let dave = &suite.senders[3].clone();
+
+    let allocations = vec![
+        (bob.to_string(), Uint128::new(1_000)),
+        (carol.to_string(), Uint128::new(500)),
+    ];
+    let expected_hash = checksum(&allocations);
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .query_allocation_checksum(|result| {
+            let checksum = result.unwrap();
+            assert_eq!(checksum.hash, HexBinary::from([0u8; 32]));
+            assert_eq!(checksum.count, 0);
+        })
+        .add_allocations(
+            alice,
+            &allocations[..1].to_vec(),
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .add_allocations(
+            alice,
+            &allocations[1..].to_vec(),
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_allocation_checksum(|result| {
+            let checksum = result.unwrap();
+            assert_eq!(checksum.hash, expected_hash);
+            assert_eq!(checksum.count, 2);
+            assert_eq!(checksum.total, Uint128::new(1_500));
+            assert_eq!(checksum.finalized_at, None);
+        })
+        .finalize_allocations(
+            bob,
+            expected_hash.clone(),
+            2,
+            Uint128::new(1_500),
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::OwnershipError(_) => {}
+                    _ => panic!("Wrong error type, should return ContractError::OwnershipError"),
+                }
+            },
+        );
+
+    for (hash, count, total, mismatch) in [
+        (expected_hash.clone(), 3, 1_500, "count"),
+        (expected_hash.clone(), 2, 1_000, "total"),
+        (checksum(&allocations[..1]), 2, 1_500, "hash"),
+    ] {
+        suite.finalize_allocations(
+            alice,
+            hash,
+            count,
+            Uint128::new(total),
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::AllocationsMismatch { field, .. } => {
+                        assert_eq!(field, mismatch);
+                    }
+                    _ => {
+                        panic!("Wrong error type, should return ContractError::AllocationsMismatch")
+                    }
+                }
+            },
+        );
+    }
+
+    let finalized_at = suite.get_time().seconds();
+
+    suite
+        .finalize_allocations(
+            alice,
+            expected_hash.clone(),
+            2,
+            Uint128::new(1_500),
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_allocation_checksum(|result| {
+            let checksum = result.unwrap();
+            assert_eq!(checksum.hash, expected_hash);
+            assert_eq!(checksum.finalized_at, Some(finalized_at));
+        })
+        .add_allocations(
+            alice,
+            &vec![(dave.to_string(), Uint128::new(1_000))],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::AllocationsFinalized => {}
+                    _ => panic!(
+                        "Wrong error type, should return ContractError::AllocationsFinalized"
+                    ),
+                }
+            },
+        )
+        .finalize_allocations(
+            alice,
+            expected_hash.clone(),
+            2,
+            Uint128::new(1_500),
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::AllocationsFinalized => {}
+                    _ => panic!(
+                        "Wrong error type, should return ContractError::AllocationsFinalized"
+                    ),
+                }
+            },
+        )
+        .query_audit_log(Some("finalize_allocations"), None, None, None, |result| {
+            let entries = result.unwrap().entries;
+            assert_eq!(entries.len(), 1);
+            assert_eq!(
+                entries[0].action,
+                AuditAction::FinalizeAllocations { count: 2 }
+            );
+        });
+}
+
+#[test]
+fn campaign_requiring_finalized_allocations() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+    let start_time = suite.get_time().plus_days(1).seconds();
+
+    let allocations = vec![
+        (bob.to_string(), Uint128::new(1_000)),
+        (carol.to_string(), Uint128::new(500)),
+    ];
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .add_allocations(
+            alice,
+            &allocations,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        // the campaign can be created before the allocations are finalized
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(campaign_params(start_time)),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .top_up_campaign(
+            alice,
+            &[coin(1_500, "uom")],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .add_day()
+        .add_day()
+        // but it can't be claimed from until they are
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::AllocationsNotFinalized => {}
+                    _ => panic!(
+                        "Wrong error type, should return ContractError::AllocationsNotFinalized"
+                    ),
+                }
+            },
+        )
+        .finalize_allocations(
+            alice,
+            checksum(&allocations),
+            2,
+            Uint128::new(1_500),
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_balance("uom", bob, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_001_000));
+        })
+        .query_campaign(|result| {
+            assert!(result.unwrap().require_finalized_allocations);
+        });
+}
+
+#[test]
+fn removed_allocations_are_taken_out_of_the_checksum() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+    let dave = &suite.senders[3].clone();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .add_allocations(
+            alice,
+            &vec![
+                (bob.to_string(), Uint128::new(1_000)),
+                (carol.to_string(), Uint128::new(500)),
+                (dave.to_string(), Uint128::new(250)),
+            ],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .remove_address(
+            alice,
+            carol,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_allocation_checksum(|result| {
+            let checksum = result.unwrap();
+            assert_eq!(checksum.count, 2);
+            assert_eq!(checksum.total, Uint128::new(1_250));
+        })
+        // the checksum only covers the remaining allocations, regardless of the upload order
+        .finalize_allocations(
+            alice,
+            checksum(&[
+                (dave.to_string(), Uint128::new(250)),
+                (bob.to_string(), Uint128::new(1_000)),
+            ]),
+            2,
+            Uint128::new(1_250),
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        );
+}
+
+#[test]
+fn finalized_allocations_can_only_be_replaced() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+    let dave = &suite.senders[3].clone();
+
+    let allocations = vec![
+        (bob.to_string(), Uint128::new(1_000)),
+        (carol.to_string(), Uint128::new(500)),
+    ];
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .add_allocations(
+            alice,
+            &allocations,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .finalize_allocations(
+            alice,
+            checksum(&allocations),
+            2,
+            Uint128::new(1_500),
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        // a lost key can still be replaced, which moves the allocation in the checksum
+        .replace_address(
+            alice,
+            bob,
+            dave,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_allocation_checksum(|result| {
+            let response = result.unwrap();
+            assert_eq!(
+                response.hash,
+                checksum(&[
+                    (dave.to_string(), Uint128::new(1_000)),
+                    (carol.to_string(), Uint128::new(500)),
+                ])
+            );
+            assert_eq!(response.count, 2);
+            assert_eq!(response.total, Uint128::new(1_500));
+            assert!(response.finalized_at.is_some());
+        })
+        .query_audit_log(Some("replace_address"), Some(bob), None, None, |result| {
+            assert_eq!(result.unwrap().entries.len(), 1);
+        })
+        .remove_address(
+            alice,
+            carol,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::AllocationsFinalized => {}
+                    _ => panic!(
+                        "Wrong error type, should return ContractError::AllocationsFinalized"
+                    ),
+                }
+            },
+        )
+        .query_allocations(None, None, None, |result| {
+            assert_eq!(result.unwrap().allocations.len(), 2);
+        })
+        .query_allocation_checksum(|result| {
+            let checksum = result.unwrap();
+            assert_eq!(checksum.count, 2);
+            assert_eq!(checksum.total, Uint128::new(1_500));
+        });
+}
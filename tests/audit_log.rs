@@ -0,0 +1,225 @@
+use crate::suite::TestingSuite;
+use cosmwasm_std::{coin, Decimal, Uint128};
+use cw_multi_test::AppResponse;
+use mantra_claimdrop_std::msg::{AuditAction, CampaignAction, CampaignParams, DistributionType};
+mod suite;
+
+#[test]
+fn admin_actions_are_recorded_in_audit_log() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+    let dan = &suite.senders[3].clone();
+    let current_time = &suite.get_time();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .manage_authorized_wallets(
+            alice,
+            vec![dan.to_string()],
+            true,
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .add_allocations(
+            dan,
+            &vec![
+                (bob.to_string(), Uint128::new(100)),
+                (carol.to_string(), Uint128::new(200)),
+            ],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(CampaignParams {
+                    name: "Test Airdrop I".to_string(),
+                    description: "This is an airdrop".to_string(),
+                    ty: "airdrop".to_string(),
+                    reward_denom: "uom".to_string(),
+                    total_reward: coin(300, "uom"),
+                    distribution_type: vec![DistributionType::LumpSum {
+                        percentage: Decimal::one(),
+                        start_time: current_time.seconds() + 1,
+                    }],
+                    start_time: current_time.seconds() + 1,
+                    end_time: current_time.seconds() + 172_800,
+                    schedules: vec![],
+                    require_finalized_allocations: false,
+                }),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .blacklist_address(
+            dan,
+            bob,
+            true,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .blacklist_address(
+            alice,
+            bob,
+            false,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .replace_address(
+            alice,
+            carol,
+            dan,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        );
+
+    let block_height = suite.get_block_height();
+
+    suite
+        .query_audit_log(None, None, None, None, |result| {
+            let entries = result.unwrap().entries;
+            assert_eq!(entries.len(), 6);
+
+            let actions: Vec<AuditAction> = entries.iter().map(|e| e.action.clone()).collect();
+            assert_eq!(
+                actions,
+                vec![
+                    AuditAction::AuthorizeWallet,
+                    AuditAction::AddAllocations { count: 2 },
+                    AuditAction::CreateCampaign,
+                    AuditAction::BlacklistAddress,
+                    AuditAction::UnblacklistAddress,
+                    AuditAction::ReplaceAddress {
+                        new_address: dan.to_string()
+                    },
+                ]
+            );
+
+            for (i, entry) in entries.iter().enumerate() {
+                assert_eq!(entry.sequence, i as u64 + 1);
+                assert_eq!(entry.height, block_height);
+                assert_eq!(entry.time, current_time.seconds());
+            }
+
+            assert_eq!(entries[0].sender, alice.to_string());
+            assert_eq!(entries[0].subject, Some(dan.to_string()));
+            assert_eq!(entries[1].sender, dan.to_string());
+            assert_eq!(entries[1].subject, None);
+            assert_eq!(entries[3].sender, dan.to_string());
+            assert_eq!(entries[3].subject, Some(bob.to_string()));
+            assert_eq!(entries[5].subject, Some(carol.to_string()));
+        })
+        .query_audit_log(Some("blacklist_address"), None, None, None, |result| {
+            let entries = result.unwrap().entries;
+            assert_eq!(entries.len(), 1);
+            assert_eq!(entries[0].sequence, 4);
+        })
+        .query_audit_log(None, Some(bob), None, None, |result| {
+            let entries = result.unwrap().entries;
+            assert_eq!(entries.len(), 2);
+            assert_eq!(entries[0].action, AuditAction::BlacklistAddress);
+            assert_eq!(entries[1].action, AuditAction::UnblacklistAddress);
+        })
+        .query_audit_log(
+            Some("unblacklist_address"),
+            Some(bob),
+            None,
+            None,
+            |result| {
+                let entries = result.unwrap().entries;
+                assert_eq!(entries.len(), 1);
+                assert_eq!(entries[0].sequence, 5);
+            },
+        )
+        .query_audit_log(Some("remove_address"), Some(bob), None, None, |result| {
+            assert!(result.unwrap().entries.is_empty());
+        });
+}
+
+#[test]
+fn audit_log_pagination() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+
+    suite.instantiate_claimdrop_contract(Some(alice.to_string()));
+
+    for _ in 0..5 {
+        suite
+            .blacklist_address(
+                alice,
+                bob,
+                true,
+                |result: Result<AppResponse, anyhow::Error>| {
+                    result.unwrap();
+                },
+            )
+            .blacklist_address(
+                alice,
+                bob,
+                false,
+                |result: Result<AppResponse, anyhow::Error>| {
+                    result.unwrap();
+                },
+            );
+    }
+
+    suite
+        .query_audit_log(None, None, None, Some(4), |result| {
+            let sequences: Vec<u64> = result.unwrap().entries.iter().map(|e| e.sequence).collect();
+            assert_eq!(sequences, vec![1, 2, 3, 4]);
+        })
+        .query_audit_log(None, None, Some(8), Some(4), |result| {
+            let sequences: Vec<u64> = result.unwrap().entries.iter().map(|e| e.sequence).collect();
+            assert_eq!(sequences, vec![9, 10]);
+        })
+        .query_audit_log(
+            Some("blacklist_address"),
+            None,
+            Some(3),
+            Some(2),
+            |result| {
+                let sequences: Vec<u64> =
+                    result.unwrap().entries.iter().map(|e| e.sequence).collect();
+                assert_eq!(sequences, vec![5, 7]);
+            },
+        )
+        .query_audit_log(None, Some(bob), Some(6), None, |result| {
+            let sequences: Vec<u64> = result.unwrap().entries.iter().map(|e| e.sequence).collect();
+            assert_eq!(sequences, vec![7, 8, 9, 10]);
+        });
+}
+
+#[test]
+fn failed_admin_actions_are_not_recorded() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .blacklist_address(
+            bob,
+            alice,
+            true,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap_err();
+            },
+        )
+        .query_audit_log(None, None, None, None, |result| {
+            assert!(result.unwrap().entries.is_empty());
+        });
+}
@@ -0,0 +1,398 @@
+use crate::suite::TestingSuite;
+use cosmwasm_std::{coin, Decimal, Uint128};
+use cw_multi_test::AppResponse;
+use mantra_claimdrop_std::error::ContractError;
+use mantra_claimdrop_std::msg::{CampaignAction, CampaignParams, DistributionType};
+mod suite;
+
+#[test]
+fn crank_claims_for_subscribed_addresses() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+    let dave = &suite.senders[3].clone();
+    let eve = &suite.senders[4].clone();
+    let start_time = suite.get_time().plus_days(1);
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .add_allocations(
+            alice,
+            &vec![
+                (bob.to_string(), Uint128::new(1_000)),
+                (carol.to_string(), Uint128::new(1_000)),
+                (dave.to_string(), Uint128::new(1_000)),
+            ],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(CampaignParams {
+                    name: "Test Airdrop I".to_string(),
+                    description: "This is an airdrop".to_string(),
+                    ty: "airdrop".to_string(),
+                    reward_denom: "uom".to_string(),
+                    total_reward: coin(3_000, "uom"),
+                    distribution_type: vec![DistributionType::LinearVesting {
+                        percentage: Decimal::one(),
+                        start_time: start_time.seconds(),
+                        end_time: start_time.plus_days(10).seconds(),
+                        cliff_duration: None,
+                        early_exit_penalty: None,
+                    }],
+                    start_time: start_time.seconds(),
+                    end_time: start_time.plus_days(10).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
+                }),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .top_up_campaign(
+            alice,
+            &[coin(3_000, "uom")],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        // only addresses with an allocation can subscribe
+        .auto_claim(eve, true, |result: Result<AppResponse, anyhow::Error>| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::NoAllocationFound { .. } => {}
+                _ => panic!("Wrong error type, should return ContractError::NoAllocationFound"),
+            }
+        })
+        .auto_claim(bob, true, |result: Result<AppResponse, anyhow::Error>| {
+            result.unwrap();
+        })
+        .auto_claim(carol, true, |result: Result<AppResponse, anyhow::Error>| {
+            result.unwrap();
+        })
+        .update_crank_tip(
+            eve,
+            Decimal::percent(1),
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::OwnershipError(_) => {}
+                    _ => panic!("Wrong error type, should return ContractError::OwnershipError"),
+                }
+            },
+        )
+        .update_crank_tip(
+            alice,
+            Decimal::percent(10),
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidInput { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::InvalidInput"),
+                }
+            },
+        )
+        .update_crank_tip(
+            alice,
+            Decimal::percent(1),
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .fund_crank_tips(
+            eve,
+            &[coin(7, "uom")],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::OwnershipError(_) => {}
+                    _ => panic!("Wrong error type, should return ContractError::OwnershipError"),
+                }
+            },
+        )
+        .fund_crank_tips(alice, &[], |result: Result<AppResponse, anyhow::Error>| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::PaymentError(_) => {}
+                _ => panic!("Wrong error type, should return ContractError::PaymentError"),
+            }
+        })
+        // the tips are paid from the pool, until it runs out
+        .fund_crank_tips(
+            alice,
+            &[coin(7, "uom")],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_auto_claim(None, None, |result| {
+            let response = result.unwrap();
+            assert_eq!(response.tip, Decimal::percent(1));
+            assert_eq!(response.tip_pool, Uint128::new(7));
+            assert_eq!(response.cursor, None);
+            assert_eq!(response.subscriptions.len(), 2);
+        })
+        .crank(eve, None, |result: Result<AppResponse, anyhow::Error>| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::CampaignError { .. } => {}
+                _ => panic!("Wrong error type, should return ContractError::CampaignError"),
+            }
+        })
+        .add_day()
+        .add_day()
+        .add_day()
+        .add_day()
+        .add_day()
+        .add_day();
+
+    // half of the rewards have vested, the crank goes through the subscribers in two batches and
+    // pays out their rewards in full
+    let (first, second) = if bob < carol {
+        (bob, carol)
+    } else {
+        (carol, bob)
+    };
+
+    suite
+        .crank(
+            eve,
+            Some(1),
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_auto_claim(None, None, |result| {
+            assert_eq!(result.unwrap().cursor, Some(first.to_string()));
+        })
+        .query_balance("uom", first, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_000_500));
+        })
+        .query_balance("uom", second, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_000_000));
+        })
+        .query_balance("uom", eve, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_000_005));
+        })
+        .crank(eve, None, |result: Result<AppResponse, anyhow::Error>| {
+            result.unwrap();
+        })
+        .query_auto_claim(None, None, |result| {
+            let response = result.unwrap();
+            assert_eq!(response.cursor, None);
+            assert_eq!(response.tip_pool, Uint128::zero());
+        })
+        .query_balance("uom", second, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_000_500));
+        })
+        // only what was left in the pool is paid as tip
+        .query_balance("uom", eve, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_000_007));
+        })
+        // dave didn't subscribe
+        .query_rewards(dave, |result| {
+            assert!(result.unwrap().claimed.is_empty());
+        })
+        // there's nothing left to claim, the subscribers are skipped
+        .crank(eve, None, |result: Result<AppResponse, anyhow::Error>| {
+            let response = result.unwrap();
+            assert!(response.events.iter().any(|event| event
+                .attributes
+                .iter()
+                .any(|attr| attr.key == "claimed" && attr.value == "0")));
+        })
+        .auto_claim(bob, false, |result: Result<AppResponse, anyhow::Error>| {
+            result.unwrap();
+        })
+        .add_day()
+        .crank(eve, None, |result: Result<AppResponse, anyhow::Error>| {
+            result.unwrap();
+        })
+        .query_balance("uom", bob, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_000_500));
+        })
+        .query_balance("uom", carol, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_000_600));
+        })
+        // the pool is empty, so no tip is paid
+        .query_balance("uom", eve, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_000_007));
+        });
+}
+
+#[test]
+fn subscriptions_follow_replaced_addresses() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+    let dave = &suite.senders[3].clone();
+    let start_time = suite.get_time();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .add_allocations(
+            alice,
+            &vec![(bob.to_string(), Uint128::new(1_000))],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(CampaignParams {
+                    name: "Test Airdrop I".to_string(),
+                    description: "This is an airdrop".to_string(),
+                    ty: "airdrop".to_string(),
+                    reward_denom: "uom".to_string(),
+                    total_reward: coin(1_000, "uom"),
+                    distribution_type: vec![DistributionType::LumpSum {
+                        percentage: Decimal::one(),
+                        start_time: start_time.seconds(),
+                    }],
+                    start_time: start_time.seconds(),
+                    end_time: start_time.plus_days(10).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
+                }),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .top_up_campaign(
+            alice,
+            &[coin(1_000, "uom")],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .auto_claim(bob, true, |result: Result<AppResponse, anyhow::Error>| {
+            result.unwrap();
+        })
+        .replace_address(
+            alice,
+            bob,
+            carol,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_auto_claim(None, None, |result| {
+            assert_eq!(result.unwrap().subscriptions, vec![carol.to_string()]);
+        })
+        .add_day()
+        .crank(dave, None, |result: Result<AppResponse, anyhow::Error>| {
+            result.unwrap();
+        })
+        .query_balance("uom", carol, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_001_000));
+        })
+        .query_balance("uom", bob, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_000_000));
+        });
+}
+
+#[test]
+fn crank_tip_pool_is_kept_apart_from_the_claims() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let current_time = suite.get_time();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .add_allocations(
+            alice,
+            &vec![(bob.to_string(), Uint128::new(1_000))],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(CampaignParams {
+                    name: "Test Airdrop I".to_string(),
+                    description: "This is an airdrop".to_string(),
+                    ty: "airdrop".to_string(),
+                    reward_denom: "uom".to_string(),
+                    total_reward: coin(1_000, "uom"),
+                    distribution_type: vec![DistributionType::LumpSum {
+                        percentage: Decimal::one(),
+                        start_time: current_time.seconds() + 1,
+                    }],
+                    start_time: current_time.seconds() + 1,
+                    end_time: current_time.plus_days(10).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
+                }),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .top_up_campaign(
+            alice,
+            &[coin(900, "uom")],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .fund_crank_tips(
+            alice,
+            &[coin(100, "uom")],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .add_day()
+        // the funds set aside for the tips can't be claimed
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::CampaignError { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::CampaignError"),
+                }
+            },
+        )
+        .claim(
+            bob,
+            None,
+            Some(Uint128::new(900)),
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        // the pool is refunded when the campaign is closed
+        .manage_campaign(
+            alice,
+            CampaignAction::CloseCampaign {},
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_balance("uom", alice, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_000_000 - 900));
+        })
+        .query_auto_claim(None, None, |result| {
+            assert_eq!(result.unwrap().tip_pool, Uint128::zero());
+        });
+}
@@ -0,0 +1,610 @@
+use crate::suite::TestingSuite;
+use claimdrop_contract::commands::MAX_BLACKLIST_BATCH_SIZE;
+use claimdrop_contract::contract::migrate;
+use claimdrop_contract::queries::query_blacklist;
+use claimdrop_contract::state::is_blacklisted;
+use cosmwasm_std::testing::{mock_dependencies, mock_env};
+use cosmwasm_std::{coin, coins, Decimal, Uint128};
+use cosmwasm_std::{Addr, Timestamp};
+use cw_multi_test::AppResponse;
+use cw_ownable::OwnershipError;
+use cw_storage_plus::Map;
+use cw_utils::Expiration;
+use mantra_claimdrop_std::error::ContractError;
+use mantra_claimdrop_std::msg::{
+    BlacklistEntry, BlacklistReason, CampaignAction, CampaignParams, DistributionType, MigrateMsg,
+};
+mod suite;
+
+#[test]
+fn blacklist_entries_can_be_listed() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+    let dan = &suite.senders[3].clone();
+    let current_time = suite.get_time();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .manage_authorized_wallets(
+            alice,
+            vec![dan.to_string()],
+            true,
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .blacklist_address_with_reason(
+            alice,
+            bob.as_str(),
+            BlacklistReason::Compromised,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .blacklist_address(
+            dan,
+            carol,
+            true,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .blacklist_address_with_reason(
+            dan,
+            "Vitalik.eth",
+            BlacklistReason::Sanctioned,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_blacklist(None, None, |result| {
+            let entries = result.unwrap().entries;
+            assert_eq!(entries.len(), 3);
+
+            let mut expected = vec![
+                (
+                    bob.to_string(),
+                    BlacklistEntry {
+                        reason: BlacklistReason::Compromised,
+                        blacklisted_at: current_time.seconds(),
+                        blacklisted_by: alice.to_string(),
+                        note: None,
+                        expiration: None,
+                        freeze_vesting: false,
+                    },
+                ),
+                (
+                    carol.to_string(),
+                    BlacklistEntry {
+                        reason: BlacklistReason::Unspecified,
+                        blacklisted_at: current_time.seconds(),
+                        blacklisted_by: dan.to_string(),
+                        note: None,
+                        expiration: None,
+                        freeze_vesting: false,
+                    },
+                ),
+                (
+                    "vitalik.eth".to_string(),
+                    BlacklistEntry {
+                        reason: BlacklistReason::Sanctioned,
+                        blacklisted_at: current_time.seconds(),
+                        blacklisted_by: dan.to_string(),
+                        note: None,
+                        expiration: None,
+                        freeze_vesting: false,
+                    },
+                ),
+            ];
+            expected.sort_by(|a, b| a.0.cmp(&b.0));
+            assert_eq!(entries, expected);
+        })
+        .query_blacklist(None, Some(1), |result| {
+            assert_eq!(result.unwrap().entries.len(), 1);
+        })
+        .blacklist_address(
+            alice,
+            carol,
+            false,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_blacklist(None, None, |result| {
+            let entries = result.unwrap().entries;
+            assert_eq!(entries.len(), 2);
+            assert!(entries.iter().all(|(address, _)| address != carol.as_str()));
+        });
+
+    let mut addresses = [bob.to_string(), "vitalik.eth".to_string()];
+    addresses.sort();
+
+    suite.query_blacklist(Some(addresses[0].clone()), None, |result| {
+        let entries = result.unwrap().entries;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].0, addresses[1]);
+    });
+}
+
+#[test]
+fn replacing_address_keeps_blacklist_entry() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .add_allocations(
+            alice,
+            &vec![(bob.to_string(), Uint128::new(100))],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .blacklist_address_with_reason(
+            alice,
+            bob.as_str(),
+            BlacklistReason::Investigation,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .replace_address(
+            alice,
+            bob,
+            carol,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_blacklist(None, None, |result| {
+            let entries = result.unwrap().entries;
+            assert_eq!(entries.len(), 1);
+            assert_eq!(entries[0].0, carol.to_string());
+            assert_eq!(entries[0].1.reason, BlacklistReason::Investigation);
+            assert_eq!(entries[0].1.blacklisted_by, alice.to_string());
+        });
+}
+
+#[test]
+fn migrate_rewrites_legacy_blacklist_flags() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let blacklisted = deps.api.addr_make("blacklisted");
+
+    cw2::set_contract_version(deps.as_mut().storage, "mantra_claimdrop-contract", "2.0.0").unwrap();
+
+    let legacy_blacklist: Map<&str, bool> = Map::new("blacklist");
+    legacy_blacklist
+        .save(deps.as_mut().storage, blacklisted.as_str(), &true)
+        .unwrap();
+    legacy_blacklist
+        .save(deps.as_mut().storage, "placeholder.eth", &true)
+        .unwrap();
+
+    let response = migrate(deps.as_mut(), env.clone(), MigrateMsg { batch_size: None }).unwrap();
+    assert!(response
+        .attributes
+        .iter()
+        .any(|attribute| attribute.key == "processed" && attribute.value == "2"));
+
+    assert!(is_blacklisted(deps.as_ref(), &env, blacklisted.as_str()).unwrap());
+
+    let entries = query_blacklist(deps.as_ref(), None, None).unwrap().entries;
+    assert_eq!(entries.len(), 2);
+    for (_, entry) in entries {
+        assert_eq!(
+            entry,
+            BlacklistEntry {
+                reason: BlacklistReason::Unspecified,
+                blacklisted_at: env.block.time.seconds(),
+                blacklisted_by: env.contract.address.to_string(),
+                note: None,
+                expiration: None,
+                freeze_vesting: false,
+            }
+        );
+    }
+}
+
+/// Sets up a campaign vesting linearly over 10 days, with an allocation of 1_000 for bob and carol.
+fn setup_vesting_campaign(suite: &mut TestingSuite, current_time: &Timestamp) {
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .add_allocations(
+            alice,
+            &vec![
+                (bob.to_string(), Uint128::new(1_000)),
+                (carol.to_string(), Uint128::new(1_000)),
+            ],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(CampaignParams {
+                    name: "Test Airdrop I".to_string(),
+                    description: "This is an airdrop".to_string(),
+                    ty: "airdrop".to_string(),
+                    reward_denom: "uom".to_string(),
+                    total_reward: coin(2_000, "uom"),
+                    distribution_type: vec![DistributionType::LinearVesting {
+                        percentage: Decimal::one(),
+                        start_time: current_time.seconds(),
+                        end_time: current_time.plus_days(10).seconds(),
+                        cliff_duration: None,
+                        early_exit_penalty: None,
+                    }],
+                    start_time: current_time.seconds(),
+                    end_time: current_time.plus_days(10).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
+                }),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .top_up_campaign(
+            alice,
+            &coins(2_000, "uom"),
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        );
+}
+
+fn assert_claimable(suite: &mut TestingSuite, address: &Addr, expected: u128) {
+    suite.query_rewards(address, |result| {
+        let rewards = result.unwrap();
+        let claimable = rewards
+            .available_to_claim
+            .first()
+            .map_or(0, |coin| coin.amount.u128());
+        assert_eq!(claimable, expected);
+    });
+}
+
+#[test]
+fn blacklisting_expires_automatically() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let current_time = suite.get_time();
+    setup_vesting_campaign(&mut suite, &current_time);
+
+    suite
+        .blacklist_address_until(
+            alice,
+            bob,
+            Some("incident #42"),
+            Expiration::AtTime(current_time.plus_days(1)),
+            false,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_is_blacklisted(bob, |result| {
+            assert!(result.unwrap().is_blacklisted);
+        })
+        .add_seconds(43_200)
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                assert!(matches!(err, ContractError::AddressBlacklisted));
+            },
+        )
+        .add_seconds(43_200)
+        .query_is_blacklisted(bob, |result| {
+            assert!(!result.unwrap().is_blacklisted);
+        })
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_blacklist(None, None, |result| {
+            let entries = result.unwrap().entries;
+            assert_eq!(entries.len(), 1);
+            assert_eq!(entries[0].1.note, Some("incident #42".to_string()));
+            assert_eq!(
+                entries[0].1.expiration,
+                Some(Expiration::AtTime(current_time.plus_days(1)))
+            );
+            assert!(!entries[0].1.freeze_vesting);
+        });
+
+    // without freezing, the vesting clock kept running while blacklisted
+    assert_claimable(&mut suite, bob, 0);
+    suite.add_day();
+    assert_claimable(&mut suite, bob, 100);
+}
+
+#[test]
+fn invalid_blacklist_params_are_rejected() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let current_time = suite.get_time();
+    let current_height = suite.get_block_height();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .blacklist_address_until(
+            alice,
+            bob,
+            None,
+            Expiration::AtTime(current_time),
+            false,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                assert!(matches!(err, ContractError::InvalidInput { .. }));
+            },
+        )
+        .blacklist_address_until(
+            alice,
+            bob,
+            None,
+            Expiration::AtHeight(current_height + 10),
+            true,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                assert!(matches!(err, ContractError::InvalidInput { .. }));
+            },
+        )
+        .blacklist_address_until(
+            alice,
+            bob,
+            Some(""),
+            Expiration::Never {},
+            false,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                assert!(matches!(err, ContractError::InvalidInput { .. }));
+            },
+        )
+        .blacklist_address_until(
+            alice,
+            bob,
+            None,
+            Expiration::AtHeight(current_height + 10),
+            false,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        );
+}
+
+#[test]
+fn vesting_is_frozen_while_blacklisted() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+    let current_time = suite.get_time();
+    setup_vesting_campaign(&mut suite, &current_time);
+
+    suite.add_day().add_day().blacklist_address_until(
+        alice,
+        bob,
+        None,
+        Expiration::Never {},
+        true,
+        |result: Result<AppResponse, anyhow::Error>| {
+            result.unwrap();
+        },
+    );
+
+    for _ in 0..3 {
+        suite.add_day();
+    }
+
+    // the clock is frozen at day 2 while blacklisted
+    assert_claimable(&mut suite, bob, 200);
+    assert_claimable(&mut suite, carol, 500);
+
+    suite.blacklist_address(
+        alice,
+        bob,
+        false,
+        |result: Result<AppResponse, anyhow::Error>| {
+            result.unwrap();
+        },
+    );
+    assert_claimable(&mut suite, bob, 200);
+
+    suite.add_day();
+    assert_claimable(&mut suite, bob, 300);
+
+    for _ in 0..6 {
+        suite.add_day();
+    }
+
+    // bob's vesting ends three days after carol's
+    assert_claimable(&mut suite, bob, 900);
+    assert_claimable(&mut suite, carol, 1_000);
+
+    suite.add_day();
+    assert_claimable(&mut suite, bob, 1_000);
+    suite.claim(
+        bob,
+        None,
+        None,
+        |result: Result<AppResponse, anyhow::Error>| {
+            result.unwrap();
+        },
+    );
+    assert_claimable(&mut suite, bob, 0);
+}
+
+#[test]
+fn vesting_freeze_ends_when_blacklisting_expires() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let dan = &suite.senders[3].clone();
+    let current_time = suite.get_time();
+    setup_vesting_campaign(&mut suite, &current_time);
+
+    suite.add_day().add_day().blacklist_address_until(
+        alice,
+        bob,
+        None,
+        Expiration::AtTime(current_time.plus_days(4)),
+        true,
+        |result: Result<AppResponse, anyhow::Error>| {
+            result.unwrap();
+        },
+    );
+
+    for _ in 0..3 {
+        suite.add_day();
+    }
+
+    // frozen from day 2 to day 4
+    assert_claimable(&mut suite, bob, 300);
+
+    // lifting the expired entry and replacing the address keep the frozen period
+    suite
+        .blacklist_address(
+            alice,
+            bob,
+            false,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .replace_address(
+            alice,
+            bob,
+            dan,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        );
+    assert_claimable(&mut suite, dan, 300);
+}
+
+#[test]
+fn addresses_can_be_blacklisted_in_batches() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+    let dan = &suite.senders[3].clone();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .blacklist_addresses(
+            alice,
+            vec![
+                bob.to_string(),
+                carol.to_string(),
+                "Vitalik.eth".to_string(),
+            ],
+            true,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let response = result.unwrap();
+                let blacklisted: Vec<String> = response
+                    .events
+                    .iter()
+                    .filter(|event| event.ty == "wasm-blacklist_address")
+                    .flat_map(|event| event.attributes.iter())
+                    .filter(|attribute| attribute.key == "address")
+                    .map(|attribute| attribute.value.clone())
+                    .collect();
+                assert_eq!(
+                    blacklisted,
+                    vec![
+                        bob.to_string(),
+                        carol.to_string(),
+                        "vitalik.eth".to_string()
+                    ]
+                );
+            },
+        )
+        .query_blacklist(None, None, |result| {
+            let entries = result.unwrap().entries;
+            assert_eq!(entries.len(), 3);
+            assert!(entries.iter().all(|(_, entry)| {
+                entry.reason == BlacklistReason::Compromised
+                    && entry.blacklisted_by == alice.to_string()
+            }));
+        })
+        .query_audit_log(Some("blacklist_address"), None, None, None, |result| {
+            assert_eq!(result.unwrap().entries.len(), 3);
+        })
+        .blacklist_addresses(
+            alice,
+            vec![bob.to_string(), "vitalik.eth".to_string()],
+            false,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_blacklist(None, None, |result| {
+            let entries = result.unwrap().entries;
+            assert_eq!(entries.len(), 1);
+            assert_eq!(entries[0].0, carol.to_string());
+        })
+        .blacklist_addresses(
+            dan,
+            vec![bob.to_string()],
+            true,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                assert!(matches!(
+                    err,
+                    ContractError::OwnershipError(OwnershipError::NotOwner)
+                ));
+            },
+        )
+        .blacklist_addresses(
+            alice,
+            vec![],
+            true,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                assert!(matches!(err, ContractError::InvalidInput { .. }));
+            },
+        )
+        .blacklist_addresses(
+            alice,
+            (0..=MAX_BLACKLIST_BATCH_SIZE)
+                .map(|i| format!("placeholder{i}"))
+                .collect(),
+            true,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                assert!(matches!(
+                    err,
+                    ContractError::BatchSizeLimitExceeded { actual, max }
+                        if actual == MAX_BLACKLIST_BATCH_SIZE + 1 && max == MAX_BLACKLIST_BATCH_SIZE
+                ));
+            },
+        );
+}
@@ -54,6 +54,8 @@ fn bug_large_numbers() {
                     }],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(14).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -77,6 +79,7 @@ fn bug_large_numbers() {
                     claimed: vec![],
                     pending: coins(alice_amount, denom),
                     available_to_claim: coins(alice_amount, denom),
+                    redistributed: vec![],
                 }
             );
         })
@@ -135,9 +138,12 @@ fn bug_large_numbers_2() {
                         start_time: current_time.seconds(),
                         end_time: current_time.plus_days(7).seconds(),
                         cliff_duration: None,
+                        early_exit_penalty: None,
                     }],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(7).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -163,6 +169,7 @@ fn bug_large_numbers_2() {
                     claimed: vec![],
                     pending: coins(alice_amount, denom),
                     available_to_claim: coins(9_999_999_999_999_999990000000000000, denom),
+                    redistributed: vec![],
                 }
             );
         })
@@ -238,6 +245,8 @@ fn bug_large_numbers_3() {
                     }],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(7).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -263,6 +272,7 @@ fn bug_large_numbers_3() {
                     claimed: vec![],
                     pending: coins(alice_amount, denom),
                     available_to_claim: coins(alice_amount, denom),
+                    redistributed: vec![],
                 }
             );
         })
@@ -0,0 +1,346 @@
+use crate::suite::TestingSuite;
+use cosmwasm_std::{coin, Decimal, Uint128};
+use cw_multi_test::AppResponse;
+use cw_utils::Expiration;
+use mantra_claimdrop_std::error::ContractError;
+use mantra_claimdrop_std::msg::{CampaignAction, CampaignParams, ClaimApproval, DistributionType};
+mod suite;
+
+/// Sets up a started campaign where bob can claim 1000 uom
+fn setup(suite: &mut TestingSuite) {
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let start_time = suite.get_time();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .add_allocations(
+            alice,
+            &vec![(bob.to_string(), Uint128::new(1_000))],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(CampaignParams {
+                    name: "Test Airdrop I".to_string(),
+                    description: "This is an airdrop".to_string(),
+                    ty: "airdrop".to_string(),
+                    reward_denom: "uom".to_string(),
+                    total_reward: coin(1_000, "uom"),
+                    distribution_type: vec![DistributionType::LumpSum {
+                        percentage: Decimal::one(),
+                        start_time: start_time.seconds(),
+                    }],
+                    start_time: start_time.seconds(),
+                    end_time: start_time.plus_days(10).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
+                }),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .top_up_campaign(
+            alice,
+            &[coin(1_000, "uom")],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .add_day();
+}
+
+#[test]
+fn approved_operators_can_claim_up_to_the_approved_amount() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+    setup(&mut suite);
+
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+    let dave = &suite.senders[3].clone();
+
+    suite
+        .claim(
+            carol,
+            Some(bob.to_string()),
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::Unauthorized => {}
+                    _ => panic!("Wrong error type, should return ContractError::Unauthorized"),
+                }
+            },
+        )
+        // only addresses with an allocation can approve claimers
+        .approve_claimer(
+            dave,
+            carol,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::NoAllocationFound { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::NoAllocationFound"),
+                }
+            },
+        )
+        .approve_claimer(
+            bob,
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidInput { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::InvalidInput"),
+                }
+            },
+        )
+        .approve_claimer(
+            bob,
+            carol,
+            None,
+            Some(Uint128::new(600)),
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_claim_approvals(bob, None, None, |result| {
+            assert_eq!(
+                result.unwrap().approvals,
+                vec![(
+                    carol.to_string(),
+                    ClaimApproval {
+                        expires: None,
+                        max_amount: Some(Uint128::new(600)),
+                    }
+                )]
+            );
+        })
+        .claim(
+            carol,
+            Some(bob.to_string()),
+            Some(Uint128::new(700)),
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidClaimAmount { .. } => {}
+                    _ => {
+                        panic!("Wrong error type, should return ContractError::InvalidClaimAmount")
+                    }
+                }
+            },
+        )
+        // the claim is capped to the approved amount, and sent to bob
+        .claim(
+            carol,
+            Some(bob.to_string()),
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_balance("uom", bob, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_000_600));
+        })
+        .query_balance("uom", carol, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_000_000));
+        })
+        .query_claim_approvals(bob, None, None, |result| {
+            assert_eq!(
+                result.unwrap().approvals[0].1.max_amount,
+                Some(Uint128::zero())
+            );
+        })
+        .claim(
+            carol,
+            Some(bob.to_string()),
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidClaimAmount { .. } => {}
+                    _ => {
+                        panic!("Wrong error type, should return ContractError::InvalidClaimAmount")
+                    }
+                }
+            },
+        )
+        // approvals are per operator
+        .claim(
+            dave,
+            Some(bob.to_string()),
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::Unauthorized => {}
+                    _ => panic!("Wrong error type, should return ContractError::Unauthorized"),
+                }
+            },
+        )
+        // bob can still claim the rest himself
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_balance("uom", bob, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_001_000));
+        });
+}
+
+#[test]
+fn expired_and_revoked_approvals_cannot_claim() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+    setup(&mut suite);
+
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+    let dave = &suite.senders[3].clone();
+    let now = suite.get_time();
+
+    suite
+        .approve_claimer(
+            bob,
+            carol,
+            Some(Expiration::AtTime(now)),
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidInput { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::InvalidInput"),
+                }
+            },
+        )
+        .approve_claimer(
+            bob,
+            carol,
+            Some(Expiration::AtTime(now.plus_days(1))),
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .approve_claimer(
+            bob,
+            dave,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_claim_approvals(bob, Some(carol), None, |result| {
+            let approvals = result.unwrap().approvals;
+            assert_eq!(approvals.len(), 1);
+            assert_eq!(approvals[0].0, dave.to_string());
+        })
+        .add_day()
+        .add_day()
+        .claim(
+            carol,
+            Some(bob.to_string()),
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::Unauthorized => {}
+                    _ => panic!("Wrong error type, should return ContractError::Unauthorized"),
+                }
+            },
+        )
+        .revoke_claimer(bob, dave, |result: Result<AppResponse, anyhow::Error>| {
+            result.unwrap();
+        })
+        .revoke_claimer(bob, dave, |result: Result<AppResponse, anyhow::Error>| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::ClaimApprovalNotFound { .. } => {}
+                _ => panic!("Wrong error type, should return ContractError::ClaimApprovalNotFound"),
+            }
+        })
+        .claim(
+            dave,
+            Some(bob.to_string()),
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::Unauthorized => {}
+                    _ => panic!("Wrong error type, should return ContractError::Unauthorized"),
+                }
+            },
+        )
+        .query_claim_approvals(bob, None, None, |result| {
+            assert_eq!(result.unwrap().approvals.len(), 1);
+        });
+}
+
+#[test]
+fn approvals_follow_replaced_addresses() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+    setup(&mut suite);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+    let dave = &suite.senders[3].clone();
+
+    suite
+        .approve_claimer(
+            bob,
+            carol,
+            None,
+            Some(Uint128::new(600)),
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .replace_address(
+            alice,
+            bob,
+            dave,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_claim_approvals(bob, None, None, |result| {
+            assert!(result.unwrap().approvals.is_empty());
+        })
+        .query_claim_approvals(dave, None, None, |result| {
+            assert_eq!(
+                result.unwrap().approvals,
+                vec![(
+                    carol.to_string(),
+                    ClaimApproval {
+                        expires: None,
+                        max_amount: Some(Uint128::new(600)),
+                    }
+                )]
+            );
+        })
+        .claim(
+            carol,
+            Some(dave.to_string()),
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_balance("uom", dave, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_000_600));
+        });
+}
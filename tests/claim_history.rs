@@ -0,0 +1,172 @@
+use crate::suite::TestingSuite;
+use cosmwasm_std::{coin, coins, Decimal, Uint128};
+use cw_multi_test::AppResponse;
+use mantra_claimdrop_std::msg::{CampaignAction, CampaignParams, DistributionType};
+mod suite;
+
+#[test]
+fn claims_are_recorded_in_claim_history() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+    let current_time = &suite.get_time();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .add_allocations(
+            alice,
+            &vec![(bob.to_string(), Uint128::new(1_000))],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(CampaignParams {
+                    name: "Test Airdrop I".to_string(),
+                    description: "This is an airdrop".to_string(),
+                    ty: "airdrop".to_string(),
+                    reward_denom: "uom".to_string(),
+                    total_reward: coin(1_000, "uom"),
+                    distribution_type: vec![
+                        DistributionType::LumpSum {
+                            percentage: Decimal::percent(25),
+                            start_time: current_time.seconds(),
+                        },
+                        DistributionType::LinearVesting {
+                            percentage: Decimal::percent(75),
+                            start_time: current_time.seconds(),
+                            end_time: current_time.plus_days(10).seconds(),
+                            cliff_duration: None,
+                            early_exit_penalty: None,
+                        },
+                    ],
+                    start_time: current_time.seconds(),
+                    end_time: current_time.plus_days(10).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
+                }),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .top_up_campaign(
+            alice,
+            &coins(1_000, "uom"),
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        );
+
+    for _ in 0..5 {
+        suite.add_day();
+    }
+
+    suite
+        .claim(
+            alice,
+            Some(bob.to_string()),
+            Some(Uint128::new(100)),
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_claim_history(bob, None, None, |result| {
+            let history = result.unwrap().history;
+            assert_eq!(history.len(), 3);
+
+            assert_eq!(history[0].sequence, 1);
+            assert_eq!(history[0].timestamp, current_time.seconds());
+            assert_eq!(history[0].amount, coin(250, "uom"));
+            assert_eq!(history[0].slots, vec![(0, Uint128::new(250))]);
+            assert_eq!(history[0].destination, bob.to_string());
+            assert_eq!(history[0].sender, bob.to_string());
+
+            assert_eq!(history[1].sequence, 2);
+            assert_eq!(history[1].timestamp, current_time.plus_days(5).seconds());
+            assert_eq!(history[1].amount, coin(100, "uom"));
+            assert_eq!(history[1].slots, vec![(1, Uint128::new(100))]);
+            assert_eq!(history[1].destination, bob.to_string());
+            assert_eq!(history[1].sender, alice.to_string());
+
+            assert_eq!(history[2].amount, coin(275, "uom"));
+            assert_eq!(history[2].slots, vec![(1, Uint128::new(275))]);
+        })
+        .query_claim_history(bob, Some(1), Some(1), |result| {
+            let history = result.unwrap().history;
+            assert_eq!(history.len(), 1);
+            assert_eq!(history[0].sequence, 2);
+        })
+        .query_claimed(Some(bob), None, None, |result| {
+            let response = result.unwrap();
+            assert_eq!(response.claimed, vec![(bob.to_string(), coin(625, "uom"))]);
+            assert_eq!(
+                response.claimed_by_slot,
+                vec![(
+                    bob.to_string(),
+                    vec![(0, coin(250, "uom")), (1, coin(375, "uom"))]
+                )]
+            );
+        })
+        .query_claimed(None, None, None, |result| {
+            let response = result.unwrap();
+            assert_eq!(
+                response.claimed_by_slot,
+                vec![(
+                    bob.to_string(),
+                    vec![(0, coin(250, "uom")), (1, coin(375, "uom"))]
+                )]
+            );
+        })
+        .replace_address(
+            alice,
+            bob,
+            carol,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_claim_history(bob, None, None, |result| {
+            assert!(result.unwrap().history.is_empty());
+        })
+        .query_claim_history(carol, None, None, |result| {
+            let history = result.unwrap().history;
+            assert_eq!(history.len(), 3);
+            assert_eq!(history[0].destination, bob.to_string());
+        });
+}
+
+#[test]
+fn claim_history_is_empty_without_claims() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .query_claim_history(bob, None, None, |result| {
+            assert!(result.unwrap().history.is_empty());
+        });
+}
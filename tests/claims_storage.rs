@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+
+use crate::suite::TestingSuite;
+use claimdrop_contract::contract::{migrate, query};
+use claimdrop_contract::state::{get_claims_for_address, Claim, DistributionSlot, CAMPAIGN};
+use cosmwasm_std::testing::{mock_dependencies, mock_env};
+use cosmwasm_std::{coin, coins, from_json, Decimal, Uint128};
+use cw_multi_test::AppResponse;
+use cw_storage_plus::Map;
+use mantra_claimdrop_std::msg::{
+    Campaign, CampaignAction, CampaignParams, ClaimedResponse, DistributionType, MigrateMsg,
+    QueryMsg,
+};
+mod suite;
+
+#[test]
+fn migrate_rewrites_legacy_claims() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let bob = deps.api.addr_make("bob").to_string();
+    let carol = deps.api.addr_make("carol").to_string();
+
+    cw2::set_contract_version(deps.as_mut().storage, "mantra_claimdrop-contract", "2.1.0").unwrap();
+
+    CAMPAIGN
+        .save(
+            deps.as_mut().storage,
+            &Campaign::from_params(CampaignParams {
+                name: "Test Airdrop I".to_string(),
+                description: "This is an airdrop".to_string(),
+                ty: "airdrop".to_string(),
+                reward_denom: "uom".to_string(),
+                total_reward: coin(1_000, "uom"),
+                distribution_type: vec![
+                    DistributionType::LumpSum {
+                        percentage: Decimal::percent(25),
+                        start_time: env.block.time.seconds(),
+                    },
+                    DistributionType::LinearVesting {
+                        percentage: Decimal::percent(75),
+                        start_time: env.block.time.seconds(),
+                        end_time: env.block.time.plus_days(10).seconds(),
+                        cliff_duration: None,
+                        early_exit_penalty: None,
+                    },
+                ],
+                start_time: env.block.time.seconds(),
+                end_time: env.block.time.plus_days(10).seconds(),
+                schedules: vec![],
+                require_finalized_allocations: false,
+            }),
+        )
+        .unwrap();
+
+    let bob_claims: HashMap<DistributionSlot, Claim> = HashMap::from([
+        (0, (Uint128::new(250), env.block.time.seconds())),
+        (
+            1,
+            (Uint128::new(100), env.block.time.plus_days(1).seconds()),
+        ),
+    ]);
+    let carol_claims: HashMap<DistributionSlot, Claim> =
+        HashMap::from([(0, (Uint128::new(50), env.block.time.seconds()))]);
+
+    let legacy_claims: Map<String, HashMap<DistributionSlot, Claim>> = Map::new("claims");
+    legacy_claims
+        .save(deps.as_mut().storage, bob.clone(), &bob_claims)
+        .unwrap();
+    legacy_claims
+        .save(deps.as_mut().storage, carol.clone(), &carol_claims)
+        .unwrap();
+
+    let response = migrate(deps.as_mut(), env.clone(), MigrateMsg { batch_size: None }).unwrap();
+    assert!(response
+        .attributes
+        .iter()
+        .any(|attribute| attribute.key == "processed" && attribute.value == "2"));
+
+    assert_eq!(
+        get_claims_for_address(deps.as_ref(), bob.clone()).unwrap(),
+        bob_claims
+    );
+    assert_eq!(
+        get_claims_for_address(deps.as_ref(), carol.clone()).unwrap(),
+        carol_claims
+    );
+    assert!(legacy_claims.is_empty(deps.as_ref().storage));
+
+    let claimed: ClaimedResponse = from_json(
+        query(
+            deps.as_ref(),
+            env,
+            QueryMsg::Claimed {
+                address: None,
+                start_from: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    let mut expected = vec![
+        (bob.clone(), coin(350, "uom")),
+        (carol.clone(), coin(50, "uom")),
+    ];
+    expected.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(claimed.claimed, expected);
+}
+
+#[test]
+fn claimed_query_paginates_by_address() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let current_time = &suite.get_time();
+    let claimers: Vec<_> = suite.senders[1..].to_vec();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .add_allocations(
+            alice,
+            &claimers
+                .iter()
+                .map(|claimer| (claimer.to_string(), Uint128::new(1_000)))
+                .collect(),
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(CampaignParams {
+                    name: "Test Airdrop I".to_string(),
+                    description: "This is an airdrop".to_string(),
+                    ty: "airdrop".to_string(),
+                    reward_denom: "uom".to_string(),
+                    total_reward: coin(4_000, "uom"),
+                    distribution_type: vec![
+                        DistributionType::LumpSum {
+                            percentage: Decimal::percent(25),
+                            start_time: current_time.seconds(),
+                        },
+                        DistributionType::LinearVesting {
+                            percentage: Decimal::percent(75),
+                            start_time: current_time.seconds(),
+                            end_time: current_time.plus_days(10).seconds(),
+                            cliff_duration: None,
+                            early_exit_penalty: None,
+                        },
+                    ],
+                    start_time: current_time.seconds(),
+                    end_time: current_time.plus_days(10).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
+                }),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .top_up_campaign(
+            alice,
+            &coins(4_000, "uom"),
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .add_day();
+
+    for claimer in claimers.iter() {
+        suite.claim(
+            claimer,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        );
+    }
+
+    let mut sorted_claimers = claimers.clone();
+    sorted_claimers.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+    let addresses: Vec<String> = sorted_claimers.iter().map(|c| c.to_string()).collect();
+
+    suite
+        .query_claimed(None, None, Some(3), |result| {
+            let response = result.unwrap();
+            let claimed: Vec<String> = response.claimed.iter().map(|(a, _)| a.clone()).collect();
+            assert_eq!(claimed, addresses[..3].to_vec());
+            assert!(response
+                .claimed
+                .iter()
+                .all(|(_, claimed)| *claimed == coin(325, "uom")));
+            assert!(response
+                .claimed_by_slot
+                .iter()
+                .all(|(_, slots)| *slots == vec![(0, coin(250, "uom")), (1, coin(75, "uom"))]));
+        })
+        .query_claimed(None, Some(&sorted_claimers[2]), None, |result| {
+            let claimed: Vec<String> = result
+                .unwrap()
+                .claimed
+                .iter()
+                .map(|(a, _)| a.clone())
+                .collect();
+            assert_eq!(claimed, addresses[3..].to_vec());
+        });
+}
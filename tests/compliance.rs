@@ -0,0 +1,248 @@
+use crate::suite::TestingSuite;
+use cosmwasm_std::{coin, coins, Decimal, Uint128};
+use cw_multi_test::AppResponse;
+use mantra_claimdrop_std::error::ContractError;
+use mantra_claimdrop_std::msg::{
+    AuditAction, CampaignAction, CampaignParams, ClaimFailureReason, ComplianceMode,
+    DistributionType, SimulateClaimResponse,
+};
+mod suite;
+
+fn update_compliance(compliance_contract: Option<String>, mode: ComplianceMode) -> CampaignAction {
+    CampaignAction::UpdateCompliance {
+        compliance_contract,
+        mode,
+    }
+}
+
+#[test]
+fn claims_are_checked_against_compliance_registry() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+    let dan = &suite.senders[3].clone();
+    let current_time = &suite.get_time();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .instantiate_compliance_registry(vec![bob.to_string()], vec![carol.to_string()])
+        .add_allocations(
+            alice,
+            &vec![
+                (bob.to_string(), Uint128::new(100)),
+                (carol.to_string(), Uint128::new(100)),
+                (dan.to_string(), Uint128::new(100)),
+            ],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(CampaignParams {
+                    name: "Test Airdrop I".to_string(),
+                    description: "This is an airdrop".to_string(),
+                    ty: "airdrop".to_string(),
+                    reward_denom: "uom".to_string(),
+                    total_reward: coin(300, "uom"),
+                    distribution_type: vec![DistributionType::LumpSum {
+                        percentage: Decimal::one(),
+                        start_time: current_time.seconds(),
+                    }],
+                    start_time: current_time.seconds(),
+                    end_time: current_time.plus_days(10).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
+                }),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .top_up_campaign(
+            alice,
+            &coins(300, "uom"),
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_campaign(|result| {
+            let campaign = result.unwrap();
+            assert_eq!(campaign.compliance_contract, None);
+            assert_eq!(campaign.compliance_mode, ComplianceMode::Disabled);
+        });
+
+    let compliance_registry = suite.compliance_registry_addr.clone();
+
+    suite
+        .manage_campaign(
+            alice,
+            update_compliance(None, ComplianceMode::AllowlistRequired),
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                assert!(matches!(err, ContractError::InvalidInput { .. }));
+            },
+        )
+        .manage_campaign(
+            bob,
+            update_compliance(
+                Some(compliance_registry.to_string()),
+                ComplianceMode::Disabled,
+            ),
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap_err();
+            },
+        )
+        .manage_campaign(
+            alice,
+            update_compliance(
+                Some(compliance_registry.to_string()),
+                ComplianceMode::AllowlistRequired,
+            ),
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_campaign(|result| {
+            let campaign = result.unwrap();
+            assert_eq!(
+                campaign.compliance_contract,
+                Some(compliance_registry.to_string())
+            );
+            assert_eq!(campaign.compliance_mode, ComplianceMode::AllowlistRequired);
+        })
+        // only allowed addresses can claim
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .claim(
+            carol,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::ComplianceCheckFailed { address } => {
+                        assert_eq!(address, carol.to_string())
+                    }
+                    _ => panic!(
+                        "Wrong error type, should return ContractError::ComplianceCheckFailed"
+                    ),
+                }
+            },
+        )
+        .query_simulate_claim(dan, None, None, |result| {
+            assert_eq!(
+                result.unwrap(),
+                SimulateClaimResponse::Failure {
+                    reason: ClaimFailureReason::NotCompliant
+                }
+            );
+        })
+        // switching to denylist only lets unlisted addresses claim
+        .manage_campaign(
+            alice,
+            update_compliance(None, ComplianceMode::DenylistOnly),
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .claim(
+            dan,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .claim(
+            carol,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                assert!(matches!(err, ContractError::ComplianceCheckFailed { .. }));
+            },
+        )
+        // disabling the checks lets everyone claim
+        .manage_campaign(
+            alice,
+            update_compliance(None, ComplianceMode::Disabled),
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .claim(
+            carol,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_audit_log(Some("update_compliance"), None, None, None, |result| {
+            let actions: Vec<AuditAction> = result
+                .unwrap()
+                .entries
+                .into_iter()
+                .map(|entry| entry.action)
+                .collect();
+            assert_eq!(
+                actions,
+                vec![
+                    AuditAction::UpdateCompliance {
+                        compliance_contract: Some(compliance_registry.to_string()),
+                        mode: ComplianceMode::AllowlistRequired,
+                    },
+                    AuditAction::UpdateCompliance {
+                        compliance_contract: Some(compliance_registry.to_string()),
+                        mode: ComplianceMode::DenylistOnly,
+                    },
+                    AuditAction::UpdateCompliance {
+                        compliance_contract: Some(compliance_registry.to_string()),
+                        mode: ComplianceMode::Disabled,
+                    },
+                ]
+            );
+        });
+}
+
+#[test]
+fn compliance_cannot_be_updated_without_campaign() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .instantiate_compliance_registry(vec![], vec![]);
+
+    let compliance_registry = suite.compliance_registry_addr.clone();
+
+    suite.manage_campaign(
+        alice,
+        update_compliance(
+            Some(compliance_registry.to_string()),
+            ComplianceMode::DenylistOnly,
+        ),
+        &[],
+        |result: Result<AppResponse, anyhow::Error>| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            assert!(matches!(err, ContractError::CampaignError { .. }));
+        },
+    );
+}
@@ -41,9 +41,12 @@ fn can_claim_dust_after_vesting_ends() {
                         start_time: current_time.seconds(),
                         end_time: current_time.plus_days(60).seconds(),
                         cliff_duration: None,
+                        early_exit_penalty: None,
                     }],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(90).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -99,6 +102,7 @@ fn can_claim_dust_after_vesting_ends() {
                     claimed: coins(16u128, "uom"),
                     pending: coins(17u128 - 16u128, "uom"),
                     available_to_claim: coins(17u128 - 16u128, "uom"),
+                    redistributed: vec![],
                 }
             );
         })
@@ -160,10 +164,13 @@ fn can_claim_dust_after_vesting_ends_2() {
                             start_time: current_time.seconds(),
                             end_time: current_time.plus_days(60).seconds(),
                             cliff_duration: None,
+                            early_exit_penalty: None,
                         },
                     ],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(90).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -221,6 +228,7 @@ fn can_claim_dust_after_vesting_ends_2() {
                     claimed: coins(10u128, "uom"),
                     pending: coins(17u128 - 10u128, "uom"),
                     available_to_claim: coins(17u128 - 10u128, "uom"),
+                    redistributed: vec![],
                 }
             );
         })
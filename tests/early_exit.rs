@@ -0,0 +1,372 @@
+use crate::suite::TestingSuite;
+use cosmwasm_std::{coin, Decimal, Decimal256, Uint128};
+use cw_multi_test::AppResponse;
+use mantra_claimdrop_std::error::ContractError;
+use mantra_claimdrop_std::msg::{
+    CampaignAction, CampaignParams, DistributionType, EarlyExitPenalty, PenaltyDestination,
+};
+mod suite;
+
+#[test]
+fn exit_early_sends_the_penalty_to_the_treasury() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+    let treasury = &suite.senders[3].clone();
+    let start_time = suite.get_time().plus_days(1);
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .add_allocations(
+            alice,
+            &vec![
+                (bob.to_string(), Uint128::new(1_000)),
+                (carol.to_string(), Uint128::new(1_000)),
+            ],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(CampaignParams {
+                    name: "Test Airdrop I".to_string(),
+                    description: "This is an airdrop".to_string(),
+                    ty: "airdrop".to_string(),
+                    reward_denom: "uom".to_string(),
+                    total_reward: coin(2_000, "uom"),
+                    distribution_type: vec![
+                        DistributionType::LumpSum {
+                            percentage: Decimal::percent(20),
+                            start_time: start_time.seconds(),
+                        },
+                        DistributionType::LinearVesting {
+                            percentage: Decimal::percent(80),
+                            start_time: start_time.seconds(),
+                            end_time: start_time.plus_days(10).seconds(),
+                            cliff_duration: None,
+                            early_exit_penalty: Some(EarlyExitPenalty {
+                                percentage: Decimal::percent(50),
+                                destination: PenaltyDestination::Treasury {
+                                    address: treasury.to_string(),
+                                },
+                            }),
+                        },
+                    ],
+                    start_time: start_time.seconds(),
+                    end_time: start_time.plus_days(10).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
+                }),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .top_up_campaign(
+            alice,
+            &[coin(2_000, "uom")],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .exit_early(bob, |result: Result<AppResponse, anyhow::Error>| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::CampaignError { .. } => {}
+                _ => panic!("Wrong error type, should return ContractError::CampaignError"),
+            }
+        })
+        .add_day()
+        .add_day()
+        .add_day()
+        .add_day()
+        // 3 days in, bob gets the lump sum, the vested 30% of the vesting slot and half of the
+        // remaining 70%
+        .exit_early(bob, |result: Result<AppResponse, anyhow::Error>| {
+            let response = result.unwrap();
+            assert!(response.events.iter().any(|event| event
+                .attributes
+                .iter()
+                .any(|attr| attr.key == "penalty" && attr.value == "280uom")));
+        })
+        .query_balance("uom", bob, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_000_720));
+        })
+        .query_balance("uom", treasury, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_000_280));
+        })
+        .exit_early(bob, |result: Result<AppResponse, anyhow::Error>| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::AlreadyExitedEarly { .. } => {}
+                _ => panic!("Wrong error type, should return ContractError::AlreadyExitedEarly"),
+            }
+        })
+        .add_week()
+        .add_week()
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::NothingToClaim => {}
+                    _ => panic!("Wrong error type, should return ContractError::NothingToClaim"),
+                }
+            },
+        )
+        // the penalty sent to the treasury doesn't change the allocations of other addresses
+        .claim(
+            carol,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_balance("uom", carol, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_001_000));
+        })
+        .query_campaign(|result| {
+            let campaign = result.unwrap();
+            assert_eq!(campaign.claimed, coin(1_720, "uom"));
+            assert_eq!(campaign.reward_per_share, Decimal256::zero());
+        });
+}
+
+#[test]
+fn exit_early_redistributes_the_penalty_including_rounding_dust() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+    let start_time = suite.get_time().plus_days(1);
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .add_allocations(
+            alice,
+            &vec![
+                (bob.to_string(), Uint128::new(1_001)),
+                (carol.to_string(), Uint128::new(2_002)),
+            ],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(CampaignParams {
+                    name: "Test Airdrop I".to_string(),
+                    description: "This is an airdrop".to_string(),
+                    ty: "airdrop".to_string(),
+                    reward_denom: "uom".to_string(),
+                    total_reward: coin(3_003, "uom"),
+                    distribution_type: vec![
+                        DistributionType::LumpSum {
+                            percentage: Decimal::percent(33),
+                            start_time: start_time.seconds(),
+                        },
+                        DistributionType::LinearVesting {
+                            percentage: Decimal::percent(67),
+                            start_time: start_time.seconds(),
+                            end_time: start_time.plus_days(10).seconds(),
+                            cliff_duration: None,
+                            early_exit_penalty: Some(EarlyExitPenalty {
+                                percentage: Decimal::percent(25),
+                                destination: PenaltyDestination::Redistribute {},
+                            }),
+                        },
+                    ],
+                    start_time: start_time.seconds(),
+                    end_time: start_time.plus_days(10).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
+                }),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .top_up_campaign(
+            alice,
+            &[coin(3_003, "uom")],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .add_day()
+        .add_day()
+        .add_day()
+        .add_day()
+        .add_day();
+
+    // 4 days in, bob's slots are 330 and 670, with 1 of dust that would only be compensated once
+    // vesting ends. 268 have vested, so 402 plus the dust are penalised: 101 are forfeited and
+    // bob gets 330 + 268 + 302
+    suite
+        .exit_early(bob, |result: Result<AppResponse, anyhow::Error>| {
+            let response = result.unwrap();
+            assert!(response.events.iter().any(|event| event
+                .attributes
+                .iter()
+                .any(|attr| attr.key == "redistributed" && attr.value == "101uom")));
+        })
+        .query_balance("uom", bob, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_000_900));
+        })
+        .query_campaign(|result| {
+            assert_eq!(
+                result.unwrap().reward_per_share,
+                Decimal256::from_ratio(101u128, 2_002u128)
+            );
+        })
+        // the redistributed penalty is floored, carol's allocation grows by 100
+        .query_rewards(carol, |result| {
+            let rewards = result.unwrap();
+            assert_eq!(rewards.pending, vec![coin(2_102, "uom")]);
+        })
+        .add_week();
+
+    // once vesting has ended, exiting is the same as claiming, including the rounding
+    // compensation: carol's slots are 693 and 1408, plus 1 of dust
+    suite
+        .exit_early(carol, |result: Result<AppResponse, anyhow::Error>| {
+            let response = result.unwrap();
+            assert!(response.events.iter().any(|event| event
+                .attributes
+                .iter()
+                .any(|attr| attr.key == "penalty" && attr.value == "0uom")));
+        })
+        .query_balance("uom", carol, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_002_102));
+        })
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::NothingToClaim => {}
+                    _ => panic!("Wrong error type, should return ContractError::NothingToClaim"),
+                }
+            },
+        )
+        .query_campaign(|result| {
+            assert_eq!(result.unwrap().claimed, coin(3_002, "uom"));
+        });
+}
+
+#[test]
+fn exit_early_requires_a_penalty() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let start_time = suite.get_time().plus_days(1);
+
+    let params = |early_exit_penalty: Option<EarlyExitPenalty>| CampaignParams {
+        name: "Test Airdrop I".to_string(),
+        description: "This is an airdrop".to_string(),
+        ty: "airdrop".to_string(),
+        reward_denom: "uom".to_string(),
+        total_reward: coin(1_000, "uom"),
+        distribution_type: vec![DistributionType::LinearVesting {
+            percentage: Decimal::one(),
+            start_time: start_time.seconds(),
+            end_time: start_time.plus_days(10).seconds(),
+            cliff_duration: None,
+            early_exit_penalty,
+        }],
+        start_time: start_time.seconds(),
+        end_time: start_time.plus_days(10).seconds(),
+        schedules: vec![],
+        require_finalized_allocations: false,
+    };
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .add_allocations(
+            alice,
+            &vec![(bob.to_string(), Uint128::new(1_000))],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(params(Some(EarlyExitPenalty {
+                    percentage: Decimal::percent(101),
+                    destination: PenaltyDestination::Redistribute {},
+                }))),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidCampaignParam { param, .. } => {
+                        assert_eq!(param, "early_exit_penalty")
+                    }
+                    _ => panic!(
+                        "Wrong error type, should return ContractError::InvalidCampaignParam"
+                    ),
+                }
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(params(Some(EarlyExitPenalty {
+                    percentage: Decimal::percent(10),
+                    destination: PenaltyDestination::Treasury {
+                        address: "invalid".to_string(),
+                    },
+                }))),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::Std(_) => {}
+                    _ => panic!("Wrong error type, should return ContractError::Std"),
+                }
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(params(None)),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .top_up_campaign(
+            alice,
+            &[coin(1_000, "uom")],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .add_day()
+        .add_day()
+        .exit_early(bob, |result: Result<AppResponse, anyhow::Error>| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::CampaignError { .. } => {}
+                _ => panic!("Wrong error type, should return ContractError::CampaignError"),
+            }
+        });
+}
@@ -0,0 +1,351 @@
+use crate::suite::TestingSuite;
+use cosmwasm_std::{coin, Decimal, Decimal256, Uint128};
+use cw_multi_test::AppResponse;
+use mantra_claimdrop_std::error::ContractError;
+use mantra_claimdrop_std::msg::{CampaignAction, CampaignParams, DistributionType, ForfeitureMode};
+mod suite;
+
+fn create_campaign(suite: &mut TestingSuite, allocations: Vec<(String, Uint128)>) {
+    let alice = &suite.senders[0].clone();
+    let start_time = suite.get_time().plus_days(1);
+    let total_reward = allocations
+        .iter()
+        .fold(Uint128::zero(), |total, (_, amount)| total + amount);
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .add_allocations(
+            alice,
+            &allocations,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(CampaignParams {
+                    name: "Test Airdrop I".to_string(),
+                    description: "This is an airdrop".to_string(),
+                    ty: "airdrop".to_string(),
+                    reward_denom: "uom".to_string(),
+                    total_reward: coin(total_reward.u128(), "uom"),
+                    distribution_type: vec![DistributionType::LinearVesting {
+                        percentage: Decimal::one(),
+                        start_time: start_time.seconds(),
+                        end_time: start_time.plus_days(10).seconds(),
+                        cliff_duration: None,
+                        early_exit_penalty: None,
+                    }],
+                    start_time: start_time.seconds(),
+                    end_time: start_time.plus_days(10).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
+                }),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .top_up_campaign(
+            alice,
+            &[coin(total_reward.u128(), "uom")],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        );
+}
+
+#[test]
+fn forfeited_allocations_are_redistributed() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+    let dave = &suite.senders[3].clone();
+
+    create_campaign(
+        &mut suite,
+        vec![
+            (bob.to_string(), Uint128::new(1_000)),
+            (carol.to_string(), Uint128::new(1_000)),
+            (dave.to_string(), Uint128::new(2_000)),
+        ],
+    );
+
+    suite
+        .manage_campaign(
+            bob,
+            CampaignAction::UpdateForfeitureMode {
+                mode: ForfeitureMode::Redistribute,
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::OwnershipError(_) => {}
+                    _ => panic!("Wrong error type, should return ContractError::OwnershipError"),
+                }
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::UpdateForfeitureMode {
+                mode: ForfeitureMode::Redistribute,
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .add_day()
+        .add_day()
+        .add_day()
+        .add_day()
+        .add_day()
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        // only blacklisted addresses can be forfeited before the campaign ends
+        .forfeit_allocations(
+            alice,
+            vec![bob.to_string()],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::CampaignError { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::CampaignError"),
+                }
+            },
+        )
+        .blacklist_address(
+            alice,
+            bob,
+            true,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .forfeit_allocations(
+            carol,
+            vec![bob.to_string()],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::OwnershipError(_) => {}
+                    _ => panic!("Wrong error type, should return ContractError::OwnershipError"),
+                }
+            },
+        )
+        // bob claimed 400, the remaining 600 are shared between carol and dave
+        .forfeit_allocations(
+            alice,
+            vec![bob.to_string()],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .forfeit_allocations(
+            alice,
+            vec![bob.to_string()],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::AllocationForfeited { .. } => {}
+                    _ => {
+                        panic!("Wrong error type, should return ContractError::AllocationForfeited")
+                    }
+                }
+            },
+        )
+        .query_campaign(|result| {
+            let campaign = result.unwrap();
+            assert_eq!(campaign.forfeited, Uint128::new(600));
+            assert_eq!(campaign.reward_per_share, Decimal256::percent(20));
+        })
+        .query_rewards(carol, |result| {
+            let rewards = result.unwrap();
+            assert_eq!(rewards.redistributed, vec![coin(200, "uom")]);
+            assert_eq!(rewards.pending, vec![coin(1_200, "uom")]);
+            assert_eq!(rewards.available_to_claim, vec![coin(480, "uom")]);
+        })
+        .query_rewards(bob, |result| {
+            let rewards = result.unwrap();
+            assert!(rewards.pending.is_empty());
+            assert!(rewards.available_to_claim.is_empty());
+        })
+        .blacklist_address(
+            alice,
+            bob,
+            false,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::AllocationForfeited { .. } => {}
+                    _ => {
+                        panic!("Wrong error type, should return ContractError::AllocationForfeited")
+                    }
+                }
+            },
+        )
+        .add_week()
+        // forfeited allocations have nothing left to claim in any slot
+        .query_rewards_breakdown(bob, |result| {
+            let breakdown = result.unwrap();
+            assert!(breakdown.rewards.available_to_claim.is_empty());
+            assert!(breakdown
+                .slots
+                .iter()
+                .all(|slot| slot.claimable.amount.is_zero()));
+            assert_eq!(breakdown.slots[0].claimed, coin(400, "uom"));
+        })
+        .claim(
+            carol,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_balance("uom", carol, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_001_200));
+        })
+        // dave left the allocation unclaimed after the campaign ended, it goes to carol
+        .forfeit_allocations(
+            alice,
+            vec![dave.to_string()],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .claim(
+            carol,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_balance("uom", carol, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_003_600));
+        })
+        .query_campaign(|result| {
+            let campaign = result.unwrap();
+            assert_eq!(campaign.claimed, coin(4_000, "uom"));
+            assert_eq!(campaign.forfeited, Uint128::new(3_000));
+        });
+}
+
+#[test]
+fn forfeited_allocations_are_returned_to_the_owner() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+
+    create_campaign(
+        &mut suite,
+        vec![
+            (bob.to_string(), Uint128::new(1_000)),
+            (carol.to_string(), Uint128::new(1_000)),
+        ],
+    );
+
+    suite
+        .add_day()
+        .blacklist_address(
+            alice,
+            bob,
+            true,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .forfeit_allocations(
+            alice,
+            vec![bob.to_string()],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_balance("uom", alice, |balance| {
+            assert_eq!(balance, Uint128::new(999_999_000));
+        })
+        .query_rewards(carol, |result| {
+            let rewards = result.unwrap();
+            assert!(rewards.redistributed.is_empty());
+            assert_eq!(rewards.pending, vec![coin(1_000, "uom")]);
+        })
+        .query_campaign(|result| {
+            let campaign = result.unwrap();
+            assert_eq!(campaign.forfeited, Uint128::new(1_000));
+            assert_eq!(campaign.reward_per_share, Decimal256::zero());
+        });
+}
+
+#[test]
+fn forfeitures_without_remaining_addresses_are_returned_to_the_owner() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+
+    create_campaign(
+        &mut suite,
+        vec![
+            (bob.to_string(), Uint128::new(1_000)),
+            (carol.to_string(), Uint128::new(1_000)),
+        ],
+    );
+
+    suite
+        .manage_campaign(
+            alice,
+            CampaignAction::UpdateForfeitureMode {
+                mode: ForfeitureMode::Redistribute,
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .add_week()
+        .add_week()
+        // nobody is left to share in the forfeited allocations
+        .forfeit_allocations(
+            alice,
+            vec![bob.to_string(), carol.to_string()],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let response = result.unwrap();
+                assert!(response.events.iter().any(|event| event
+                    .attributes
+                    .iter()
+                    .any(|attr| attr.key == "redistributed" && attr.value == "false")));
+            },
+        )
+        .query_balance("uom", alice, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_000_000));
+        })
+        .query_campaign(|result| {
+            let campaign = result.unwrap();
+            assert_eq!(campaign.forfeited, Uint128::new(2_000));
+            assert_eq!(campaign.reward_per_share, Decimal256::zero());
+        });
+}
@@ -0,0 +1,172 @@
+//! Benchmarks the storage gas of the claim path, using the KV store gas costs of the Cosmos SDK.
+//! Run with `cargo test --test gas_benchmarks -- --nocapture` to see the figures.
+
+use std::cell::Cell;
+use std::marker::PhantomData;
+
+use claimdrop_contract::contract::{execute, instantiate};
+use cosmwasm_std::testing::{
+    message_info, mock_dependencies_with_balance, mock_env, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{coin, coins, Decimal, Env, OwnedDeps, Record, Storage, Uint128};
+use mantra_claimdrop_std::msg::{
+    CampaignAction, CampaignParams, DistributionType, ExecuteMsg, InstantiateMsg,
+};
+
+// KV store gas costs, as defined by the Cosmos SDK's default KVGasConfig
+const READ_COST_FLAT: u64 = 1_000;
+const READ_COST_PER_BYTE: u64 = 3;
+const WRITE_COST_FLAT: u64 = 2_000;
+const WRITE_COST_PER_BYTE: u64 = 30;
+const DELETE_COST: u64 = 1_000;
+const ITER_NEXT_COST_FLAT: u64 = 30;
+
+/// A storage that meters the gas consumed by the operations performed on it.
+#[derive(Default)]
+struct MeteredStorage {
+    storage: MockStorage,
+    gas: Cell<u64>,
+}
+
+impl MeteredStorage {
+    fn consume(&self, gas: u64) {
+        self.gas.set(self.gas.get() + gas);
+    }
+
+    fn reset(&self) {
+        self.gas.set(0);
+    }
+}
+
+impl Storage for MeteredStorage {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        let value = self.storage.get(key);
+        let len = key.len() + value.as_ref().map_or(0, |value| value.len());
+        self.consume(READ_COST_FLAT + READ_COST_PER_BYTE * len as u64);
+        value
+    }
+
+    fn range<'a>(
+        &'a self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: cosmwasm_std::Order,
+    ) -> Box<dyn Iterator<Item = Record> + 'a> {
+        Box::new(
+            self.storage
+                .range(start, end, order)
+                .inspect(move |(key, value)| {
+                    let len = key.len() + value.len();
+                    self.consume(ITER_NEXT_COST_FLAT + READ_COST_PER_BYTE * len as u64);
+                }),
+        )
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) {
+        let len = key.len() + value.len();
+        self.consume(WRITE_COST_FLAT + WRITE_COST_PER_BYTE * len as u64);
+        self.storage.set(key, value);
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        self.consume(DELETE_COST);
+        self.storage.remove(key);
+    }
+}
+
+type MeteredDeps = OwnedDeps<MeteredStorage, MockApi, MockQuerier>;
+
+fn metered_dependencies() -> MeteredDeps {
+    let deps = mock_dependencies_with_balance(&coins(1_000_000_000, "uom"));
+
+    OwnedDeps {
+        storage: MeteredStorage::default(),
+        api: deps.api,
+        querier: deps.querier,
+        custom_query_type: PhantomData,
+    }
+}
+
+#[test]
+fn claim_storage_gas() {
+    let mut deps = metered_dependencies();
+    let mut env = mock_env();
+    let owner = deps.api.addr_make("owner");
+    let receiver = deps.api.addr_make("receiver");
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        message_info(&owner, &[]),
+        InstantiateMsg { owner: None },
+    )
+    .unwrap();
+
+    let execute_msg = |deps: &mut MeteredDeps, env: &Env, sender, msg| {
+        execute(deps.as_mut(), env.clone(), message_info(sender, &[]), msg).unwrap();
+    };
+
+    execute_msg(
+        &mut deps,
+        &env,
+        &owner,
+        ExecuteMsg::AddAllocations {
+            allocations: vec![(receiver.to_string(), Uint128::new(1_000_000)).into()],
+        },
+    );
+    execute_msg(
+        &mut deps,
+        &env,
+        &owner,
+        ExecuteMsg::ManageCampaign {
+            action: CampaignAction::CreateCampaign {
+                params: Box::new(CampaignParams {
+                    name: "Test Airdrop I".to_string(),
+                    description: "This is an airdrop".to_string(),
+                    ty: "airdrop".to_string(),
+                    reward_denom: "uom".to_string(),
+                    total_reward: coin(1_000_000, "uom"),
+                    distribution_type: vec![
+                        DistributionType::LumpSum {
+                            percentage: Decimal::percent(25),
+                            start_time: env.block.time.seconds(),
+                        },
+                        DistributionType::LinearVesting {
+                            percentage: Decimal::percent(75),
+                            start_time: env.block.time.seconds(),
+                            end_time: env.block.time.plus_days(10).seconds(),
+                            cliff_duration: None,
+                            early_exit_penalty: None,
+                        },
+                    ],
+                    start_time: env.block.time.seconds(),
+                    end_time: env.block.time.plus_days(10).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
+                }),
+            },
+        },
+    );
+
+    let claim = ExecuteMsg::Claim {
+        receiver: None,
+        amount: None,
+        ibc_destination: None,
+    };
+
+    // the first claim takes from both slots, the next ones only from the linear vesting slot
+    let mut gas_per_claim = vec![];
+    for _ in 0..3 {
+        deps.storage.reset();
+        execute_msg(&mut deps, &env, &receiver, claim.clone());
+        gas_per_claim.push(deps.storage.gas.get());
+        env.block.time = env.block.time.plus_days(1);
+    }
+
+    println!("claim | storage gas");
+    for (i, gas) in gas_per_claim.iter().enumerate() {
+        println!("{:>5} | {:>11}", i + 1, gas);
+    }
+
+    assert!(gas_per_claim.iter().all(|gas| *gas > 0));
+}
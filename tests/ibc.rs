@@ -0,0 +1,1225 @@
+use claimdrop_contract::contract::{execute, instantiate, query, reply};
+use claimdrop_contract::ibc::{
+    ibc_channel_close, ibc_channel_connect, ibc_channel_open, ibc_packet_receive,
+    ibc_source_callback, IBC_TRANSFER_REPLY_ID, IBC_TRANSFER_TIMEOUT_SECONDS,
+    MAX_IBC_TRANSFER_TIMEOUT_SECONDS,
+};
+use claimdrop_contract::state::{
+    restore_claim, ClaimRestoration, PendingIbcTransfer, CAMPAIGN, CLAIMS, CLAIM_APPROVALS,
+    VESTING_ANCHORS,
+};
+use cosmwasm_std::testing::{
+    message_info, mock_dependencies_with_balance, mock_env, mock_ibc_channel_close_init,
+    mock_ibc_channel_connect_ack, mock_ibc_channel_open_init, mock_ibc_channel_open_try,
+    mock_ibc_packet_recv, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    coin, coins, from_json, to_json_binary, to_json_string, Addr, BankMsg, Binary, CosmosMsg,
+    Decimal, Env, IbcAckCallbackMsg, IbcAcknowledgement, IbcBasicResponse, IbcCallbackRequest,
+    IbcEndpoint, IbcMsg, IbcOrder, IbcPacket, IbcReceiveResponse, IbcSourceCallbackMsg,
+    IbcSrcCallback, IbcTimeout, IbcTimeoutCallbackMsg, MsgResponse, Order, OwnedDeps, Reply,
+    Response, StdAck, StdResult, SubMsg, SubMsgResponse, SubMsgResult, Uint128,
+};
+use mantra_claimdrop_std::error::ContractError;
+use mantra_claimdrop_std::msg::{
+    CampaignAction, CampaignParams, ClaimHistoryResponse, ClaimedResponse, DistributionType,
+    ExecuteMsg, IbcChannelConfig, IbcChannelsResponse, IbcClaimAck, IbcClaimPacket, IbcClaimPayout,
+    IbcDestination, InstantiateMsg, QueryMsg, RewardsResponse, VestingAnchor, IBC_APP_VERSION,
+};
+
+type Deps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+const REMOTE_ADDRESS: &str = "osmo1remoteaddress";
+
+/// Sets up a started campaign where the remote address and alice can claim 1000 uom each
+fn setup() -> (Deps, Env) {
+    let env = mock_env();
+    setup_with(vec![DistributionType::LumpSum {
+        percentage: Decimal::one(),
+        start_time: env.block.time.seconds(),
+    }])
+}
+
+/// Sets up a started campaign with the given distribution types, where the remote address and
+/// alice are allocated 1000 uom each
+fn setup_with(distribution_type: Vec<DistributionType>) -> (Deps, Env) {
+    let mut deps = mock_dependencies_with_balance(&coins(2_000, "uom"));
+    let env = mock_env();
+    let owner = MockApi::default().addr_make("owner");
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        message_info(&owner, &[]),
+        InstantiateMsg { owner: None },
+    )
+    .unwrap();
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        message_info(&owner, &[]),
+        ExecuteMsg::AddAllocations {
+            allocations: vec![
+                (REMOTE_ADDRESS.to_string(), Uint128::new(1_000)).into(),
+                (
+                    MockApi::default().addr_make("alice").to_string(),
+                    Uint128::new(1_000),
+                )
+                    .into(),
+            ],
+        },
+    )
+    .unwrap();
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        message_info(&owner, &[]),
+        ExecuteMsg::ManageCampaign {
+            action: CampaignAction::CreateCampaign {
+                params: Box::new(CampaignParams {
+                    name: "Test Airdrop I".to_string(),
+                    description: "This is an airdrop".to_string(),
+                    ty: "airdrop".to_string(),
+                    reward_denom: "uom".to_string(),
+                    total_reward: coin(2_000, "uom"),
+                    distribution_type,
+                    start_time: env.block.time.seconds(),
+                    end_time: env.block.time.plus_days(10).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
+                }),
+            },
+        },
+    )
+    .unwrap();
+
+    (deps, env)
+}
+
+fn allow_channel(
+    deps: &mut Deps,
+    env: &Env,
+    channel_id: &str,
+    address_prefix: &str,
+    transfer_channel: Option<&str>,
+) {
+    let owner = MockApi::default().addr_make("owner");
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        message_info(&owner, &[]),
+        ExecuteMsg::ManageIbcChannel {
+            channel_id: channel_id.to_string(),
+            config: Some(IbcChannelConfig {
+                address_prefix: address_prefix.to_string(),
+                transfer_channel: transfer_channel.map(|channel| channel.to_string()),
+            }),
+        },
+    )
+    .unwrap();
+}
+
+fn claim_packet(amount: Option<u128>, payout: IbcClaimPayout) -> IbcClaimPacket {
+    IbcClaimPacket {
+        address: REMOTE_ADDRESS.to_string(),
+        amount: amount.map(Uint128::new),
+        payout,
+    }
+}
+
+/// Pays out the claim to the remote address over the transfer channel
+fn remote_payout() -> IbcClaimPayout {
+    IbcClaimPayout::Remote {
+        remote_address: REMOTE_ADDRESS.to_string(),
+    }
+}
+
+fn receive(
+    deps: &mut Deps,
+    env: &Env,
+    channel_id: &str,
+    packet: &IbcClaimPacket,
+) -> IbcReceiveResponse {
+    ibc_packet_receive(
+        deps.as_mut(),
+        env.clone(),
+        mock_ibc_packet_recv(channel_id, packet).unwrap(),
+    )
+    .unwrap()
+}
+
+fn ack(response: &IbcReceiveResponse) -> StdAck {
+    from_json(response.acknowledgement.as_ref().unwrap()).unwrap()
+}
+
+fn transfer_msg(
+    env: &Env,
+    channel_id: &str,
+    to_address: &str,
+    amount: u128,
+    timeout_seconds: u64,
+) -> CosmosMsg {
+    CosmosMsg::Ibc(IbcMsg::Transfer {
+        channel_id: channel_id.to_string(),
+        to_address: to_address.to_string(),
+        amount: coin(amount, "uom"),
+        timeout: IbcTimeout::with_timestamp(env.block.time.plus_seconds(timeout_seconds)),
+        memo: Some(
+            to_json_string(&IbcCallbackRequest::source(IbcSrcCallback {
+                address: env.contract.address.clone(),
+                gas_limit: None,
+            }))
+            .unwrap(),
+        ),
+    })
+}
+
+fn claim_over_ibc(deps: &mut Deps, env: &Env, timeout: Option<u64>) -> Response {
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        message_info(&MockApi::default().addr_make("alice"), &[]),
+        ExecuteMsg::Claim {
+            receiver: None,
+            amount: None,
+            ibc_destination: Some(IbcDestination {
+                channel_id: "channel-5".to_string(),
+                remote_address: "osmo1alice".to_string(),
+                timeout,
+            }),
+        },
+    )
+    .unwrap()
+}
+
+/// Replies to the transfer as the chain would, with the MsgTransferResponse of the packet sent
+#[allow(deprecated)]
+fn reply_to_transfer(deps: &mut Deps, env: &Env, transfer: &SubMsg, sequence: &[u8]) {
+    let mut value = vec![0x08];
+    value.extend_from_slice(sequence);
+
+    reply(
+        deps.as_mut(),
+        env.clone(),
+        Reply {
+            id: transfer.id,
+            payload: transfer.payload.clone(),
+            gas_used: 0,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None,
+                msg_responses: vec![MsgResponse {
+                    type_url: "/ibc.applications.transfer.v1.MsgTransferResponse".to_string(),
+                    value: Binary::from(value),
+                }],
+            }),
+        },
+    )
+    .unwrap();
+}
+
+fn transfer_packet(channel_id: &str, sequence: u64) -> IbcPacket {
+    IbcPacket::new(
+        Binary::default(),
+        IbcEndpoint {
+            port_id: "transfer".to_string(),
+            channel_id: channel_id.to_string(),
+        },
+        IbcEndpoint {
+            port_id: "transfer".to_string(),
+            channel_id: "channel-99".to_string(),
+        },
+        sequence,
+        IbcTimeout::with_timestamp(mock_env().block.time),
+    )
+}
+
+fn acknowledge(
+    deps: &mut Deps,
+    env: &Env,
+    channel_id: &str,
+    sequence: u64,
+    ack: StdAck,
+) -> IbcBasicResponse {
+    ibc_source_callback(
+        deps.as_mut(),
+        env.clone(),
+        IbcSourceCallbackMsg::Acknowledgement(IbcAckCallbackMsg::new(
+            IbcAcknowledgement::new(to_json_binary(&ack).unwrap()),
+            transfer_packet(channel_id, sequence),
+            Addr::unchecked("relayer"),
+        )),
+    )
+    .unwrap()
+}
+
+fn status(response: &IbcBasicResponse) -> &str {
+    response
+        .attributes
+        .iter()
+        .find(|attr| attr.key == "status")
+        .map(|attr| attr.value.as_str())
+        .unwrap()
+}
+
+fn query_rewards(deps: &Deps, env: &Env, receiver: &str) -> RewardsResponse {
+    from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Rewards {
+                receiver: receiver.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn channels_must_use_claimdrop_version() {
+    let mut deps = mock_dependencies_with_balance(&[]);
+    let env = mock_env();
+
+    let err = ibc_channel_open(
+        deps.as_mut(),
+        env.clone(),
+        mock_ibc_channel_open_init("channel-0", IbcOrder::Unordered, "ics20-1"),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidIbcChannel { .. }));
+
+    let err = ibc_channel_open(
+        deps.as_mut(),
+        env.clone(),
+        mock_ibc_channel_open_init("channel-0", IbcOrder::Ordered, IBC_APP_VERSION),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidIbcChannel { .. }));
+
+    let response = ibc_channel_open(
+        deps.as_mut(),
+        env.clone(),
+        mock_ibc_channel_open_try("channel-0", IbcOrder::Unordered, IBC_APP_VERSION),
+    )
+    .unwrap();
+    assert_eq!(response.unwrap().version, IBC_APP_VERSION);
+
+    ibc_channel_connect(
+        deps.as_mut(),
+        env,
+        mock_ibc_channel_connect_ack("channel-0", IbcOrder::Unordered, IBC_APP_VERSION),
+    )
+    .unwrap();
+}
+
+/// Builds a packet claiming the allocation of alice, paid out to an address on this chain
+fn local_claim_packet(amount: Option<u128>, receiver: &Addr) -> IbcClaimPacket {
+    IbcClaimPacket {
+        address: MockApi::default().addr_make("alice").to_string(),
+        amount: amount.map(Uint128::new),
+        payout: IbcClaimPayout::Local {
+            receiver: receiver.to_string(),
+        },
+    }
+}
+
+#[test]
+fn only_allowed_channels_can_claim() {
+    let (mut deps, env) = setup();
+    let packet = local_claim_packet(None, &MockApi::default().addr_make("alice"));
+
+    let response = receive(&mut deps, &env, "channel-0", &packet);
+    assert_eq!(
+        ack(&response),
+        StdAck::error(
+            ContractError::IbcChannelNotAllowed {
+                channel_id: "channel-0".to_string()
+            }
+            .to_string()
+        )
+    );
+    assert!(response.messages.is_empty());
+
+    // only the owner can allow channels
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        message_info(&MockApi::default().addr_make("someone"), &[]),
+        ExecuteMsg::ManageIbcChannel {
+            channel_id: "channel-0".to_string(),
+            config: Some(IbcChannelConfig {
+                address_prefix: "cosmwasm".to_string(),
+                transfer_channel: None,
+            }),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::OwnershipError(_)));
+
+    allow_channel(&mut deps, &env, "channel-0", "cosmwasm", None);
+
+    let channels: IbcChannelsResponse = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::IbcChannels {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        channels.channels,
+        vec![(
+            "channel-0".to_string(),
+            IbcChannelConfig {
+                address_prefix: "cosmwasm".to_string(),
+                transfer_channel: None
+            }
+        )]
+    );
+
+    let response = receive(&mut deps, &env, "channel-0", &packet);
+    assert_eq!(
+        ack(&response),
+        StdAck::success(
+            cosmwasm_std::to_json_binary(&IbcClaimAck {
+                amount: coin(1_000, "uom")
+            })
+            .unwrap()
+        )
+    );
+
+    // closing the channel disallows it
+    ibc_channel_close(
+        deps.as_mut(),
+        env.clone(),
+        mock_ibc_channel_close_init("channel-0", IbcOrder::Unordered, IBC_APP_VERSION),
+    )
+    .unwrap();
+
+    let channels: IbcChannelsResponse = from_json(
+        query(
+            deps.as_ref(),
+            env,
+            QueryMsg::IbcChannels {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(channels.channels.is_empty());
+}
+
+#[test]
+fn claims_can_be_paid_out_locally_or_remotely() {
+    let (mut deps, env) = setup();
+    let alice = MockApi::default().addr_make("alice");
+
+    allow_channel(&mut deps, &env, "channel-0", "cosmwasm", None);
+    allow_channel(&mut deps, &env, "channel-1", "osmo", Some("channel-7"));
+    allow_channel(&mut deps, &env, "channel-2", "osmo", None);
+
+    // the tokens are sent to the claimed address on this chain
+    let response = receive(
+        &mut deps,
+        &env,
+        "channel-0",
+        &local_claim_packet(Some(400), &alice),
+    );
+    assert!(ack(&response).is_success());
+    assert_eq!(
+        response.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: alice.to_string(),
+            amount: coins(400, "uom"),
+        })
+    );
+
+    // remote payouts need a transfer channel
+    let response = receive(
+        &mut deps,
+        &env,
+        "channel-2",
+        &claim_packet(None, remote_payout()),
+    );
+    assert!(!ack(&response).is_success());
+    assert!(response.messages.is_empty());
+
+    // the tokens are sent back to the remote address over ICS-20
+    let response = receive(
+        &mut deps,
+        &env,
+        "channel-1",
+        &claim_packet(None, remote_payout()),
+    );
+    assert!(ack(&response).is_success());
+    assert_eq!(response.messages[0].id, IBC_TRANSFER_REPLY_ID);
+    assert_eq!(
+        response.messages[0].msg,
+        transfer_msg(
+            &env,
+            "channel-7",
+            REMOTE_ADDRESS,
+            1_000,
+            IBC_TRANSFER_TIMEOUT_SECONDS
+        )
+    );
+
+    // there's nothing left to claim
+    let response = receive(
+        &mut deps,
+        &env,
+        "channel-1",
+        &claim_packet(None, remote_payout()),
+    );
+    assert_eq!(
+        ack(&response),
+        StdAck::error(ContractError::NothingToClaim.to_string())
+    );
+
+    let rewards: RewardsResponse = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Rewards {
+                receiver: REMOTE_ADDRESS.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(rewards.claimed, coins(1_000, "uom"));
+
+    let history: ClaimHistoryResponse = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ClaimHistory {
+                address: REMOTE_ADDRESS.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(history.history.len(), 1);
+    assert_eq!(history.history[0].sender, "their-port");
+    assert_eq!(history.history[0].destination, REMOTE_ADDRESS);
+
+    let history: ClaimHistoryResponse = from_json(
+        query(
+            deps.as_ref(),
+            env,
+            QueryMsg::ClaimHistory {
+                address: alice.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(history.history.len(), 1);
+    assert_eq!(history.history[0].destination, alice.to_string());
+}
+
+#[test]
+fn channels_only_claim_for_addresses_with_their_prefix() {
+    let (mut deps, env) = setup();
+    let owner = MockApi::default().addr_make("owner");
+    let alice = MockApi::default().addr_make("alice");
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        message_info(&owner, &[]),
+        ExecuteMsg::ManageIbcChannel {
+            channel_id: "channel-1".to_string(),
+            config: Some(IbcChannelConfig {
+                address_prefix: String::new(),
+                transfer_channel: Some("channel-7".to_string()),
+            }),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidIbcChannel { .. }));
+
+    allow_channel(&mut deps, &env, "channel-1", "osmo", Some("channel-7"));
+
+    // the allocations of this chain can't be claimed by a remote channel
+    let response = receive(
+        &mut deps,
+        &env,
+        "channel-1",
+        &IbcClaimPacket {
+            address: alice.to_string(),
+            amount: None,
+            payout: remote_payout(),
+        },
+    );
+    assert_eq!(
+        ack(&response),
+        StdAck::error(ContractError::Unauthorized.to_string())
+    );
+    assert!(response.messages.is_empty());
+
+    // nor the allocations of another remote chain
+    allow_channel(&mut deps, &env, "channel-2", "osmosis", Some("channel-7"));
+    let response = receive(
+        &mut deps,
+        &env,
+        "channel-2",
+        &claim_packet(None, remote_payout()),
+    );
+    assert_eq!(
+        ack(&response),
+        StdAck::error(ContractError::Unauthorized.to_string())
+    );
+
+    // the remote address receiving the tokens must be set
+    let response = receive(
+        &mut deps,
+        &env,
+        "channel-1",
+        &claim_packet(
+            None,
+            IbcClaimPayout::Remote {
+                remote_address: String::new(),
+            },
+        ),
+    );
+    assert!(!ack(&response).is_success());
+    assert!(response.messages.is_empty());
+
+    // the tokens can be sent to any address on the remote chain
+    let response = receive(
+        &mut deps,
+        &env,
+        "channel-1",
+        &claim_packet(
+            None,
+            IbcClaimPayout::Remote {
+                remote_address: "osmo1otheraddress".to_string(),
+            },
+        ),
+    );
+    assert!(ack(&response).is_success());
+    assert_eq!(
+        response.messages[0].msg,
+        transfer_msg(
+            &env,
+            "channel-7",
+            "osmo1otheraddress",
+            1_000,
+            IBC_TRANSFER_TIMEOUT_SECONDS
+        )
+    );
+
+    let rewards = query_rewards(&deps, &env, REMOTE_ADDRESS);
+    assert_eq!(rewards.claimed, coins(1_000, "uom"));
+    let rewards = query_rewards(&deps, &env, alice.as_str());
+    assert!(rewards.claimed.is_empty());
+}
+
+#[test]
+fn local_payouts_go_to_the_holder_or_an_approved_operator() {
+    let (mut deps, env) = setup();
+    let alice = MockApi::default().addr_make("alice");
+    let operator = MockApi::default().addr_make("operator");
+
+    allow_channel(&mut deps, &env, "channel-0", "cosmwasm", None);
+
+    // the channel can't claim for addresses of other chains
+    let response = receive(
+        &mut deps,
+        &env,
+        "channel-0",
+        &claim_packet(
+            None,
+            IbcClaimPayout::Local {
+                receiver: operator.to_string(),
+            },
+        ),
+    );
+    assert_eq!(
+        ack(&response),
+        StdAck::error(ContractError::Unauthorized.to_string())
+    );
+
+    // the operator wasn't approved by alice
+    let response = receive(
+        &mut deps,
+        &env,
+        "channel-0",
+        &local_claim_packet(None, &operator),
+    );
+    assert_eq!(
+        ack(&response),
+        StdAck::error(ContractError::Unauthorized.to_string())
+    );
+    assert!(response.messages.is_empty());
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        message_info(&alice, &[]),
+        ExecuteMsg::ApproveClaimer {
+            operator: operator.to_string(),
+            expires: None,
+            max_amount: Some(Uint128::new(300)),
+        },
+    )
+    .unwrap();
+
+    // blacklisted operators can't receive claims
+    let owner = MockApi::default().addr_make("owner");
+    let blacklist = |deps: &mut Deps, blacklist: bool| {
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&owner, &[]),
+            ExecuteMsg::BlacklistAddress {
+                address: operator.to_string(),
+                blacklist,
+                reason: None,
+                note: None,
+                expiration: None,
+                freeze_vesting: None,
+            },
+        )
+        .unwrap();
+    };
+    blacklist(&mut deps, true);
+
+    let response = receive(
+        &mut deps,
+        &env,
+        "channel-0",
+        &local_claim_packet(None, &operator),
+    );
+    assert_eq!(
+        ack(&response),
+        StdAck::error(ContractError::AddressBlacklisted.to_string())
+    );
+
+    blacklist(&mut deps, false);
+
+    // the approved operator receives up to the approved amount
+    let response = receive(
+        &mut deps,
+        &env,
+        "channel-0",
+        &local_claim_packet(None, &operator),
+    );
+    assert!(ack(&response).is_success());
+    assert_eq!(
+        response.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: operator.to_string(),
+            amount: coins(300, "uom"),
+        })
+    );
+
+    let response = receive(
+        &mut deps,
+        &env,
+        "channel-0",
+        &local_claim_packet(None, &operator),
+    );
+    assert!(!ack(&response).is_success());
+    assert!(response.messages.is_empty());
+
+    let rewards = query_rewards(&deps, &env, alice.as_str());
+    assert_eq!(rewards.claimed, coins(300, "uom"));
+    assert_eq!(rewards.available_to_claim, coins(700, "uom"));
+}
+
+#[test]
+fn invalid_packets_are_acknowledged_with_an_error() {
+    let (mut deps, env) = setup();
+    allow_channel(&mut deps, &env, "channel-0", "cosmwasm", None);
+
+    let response = ibc_packet_receive(
+        deps.as_mut(),
+        env.clone(),
+        mock_ibc_packet_recv("channel-0", &"not a claim").unwrap(),
+    )
+    .unwrap();
+    assert!(!ack(&response).is_success());
+
+    let response = receive(
+        &mut deps,
+        &env,
+        "channel-0",
+        &claim_packet(
+            None,
+            IbcClaimPayout::Local {
+                receiver: "invalid".to_string(),
+            },
+        ),
+    );
+    assert!(!ack(&response).is_success());
+
+    allow_channel(&mut deps, &env, "channel-1", "osmo", Some("channel-7"));
+
+    let mut packet = claim_packet(None, remote_payout());
+    packet.address = "osmo1unknown".to_string();
+    let response = receive(&mut deps, &env, "channel-1", &packet);
+    assert_eq!(
+        ack(&response),
+        StdAck::error(
+            ContractError::NoAllocationFound {
+                address: "osmo1unknown".to_string()
+            }
+            .to_string()
+        )
+    );
+}
+
+#[test]
+fn claims_can_be_transferred_to_remote_chains() {
+    let (mut deps, env) = setup();
+    let alice = MockApi::default().addr_make("alice");
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        message_info(&alice, &[]),
+        ExecuteMsg::Claim {
+            receiver: None,
+            amount: None,
+            ibc_destination: Some(IbcDestination {
+                channel_id: "channel-5".to_string(),
+                remote_address: "".to_string(),
+                timeout: None,
+            }),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidIbcTransfer { .. }));
+
+    for timeout in [0, MAX_IBC_TRANSFER_TIMEOUT_SECONDS + 1, u64::MAX] {
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&alice, &[]),
+            ExecuteMsg::Claim {
+                receiver: None,
+                amount: None,
+                ibc_destination: Some(IbcDestination {
+                    channel_id: "channel-5".to_string(),
+                    remote_address: "osmo1alice".to_string(),
+                    timeout: Some(timeout),
+                }),
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidIbcTransfer { .. }));
+    }
+
+    let response = claim_over_ibc(&mut deps, &env, Some(600));
+    assert_eq!(response.messages[0].id, IBC_TRANSFER_REPLY_ID);
+    assert_eq!(
+        response.messages[0].msg,
+        transfer_msg(&env, "channel-5", "osmo1alice", 1_000, 600)
+    );
+    reply_to_transfer(&mut deps, &env, &response.messages[0], &[0xac, 0x02]);
+
+    let rewards = query_rewards(&deps, &env, alice.as_str());
+    assert_eq!(rewards.claimed, coins(1_000, "uom"));
+    assert!(rewards.available_to_claim.is_empty());
+
+    // the transfer succeeded, the claim stays
+    acknowledge(
+        &mut deps,
+        &env,
+        "channel-5",
+        300,
+        StdAck::success(Binary::from(vec![1])),
+    );
+
+    let rewards = query_rewards(&deps, &env, alice.as_str());
+    assert_eq!(rewards.claimed, coins(1_000, "uom"));
+
+    let history: ClaimHistoryResponse = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ClaimHistory {
+                address: alice.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(history.history.len(), 1);
+    assert_eq!(history.history[0].destination, "osmo1alice");
+}
+
+#[test]
+fn failed_transfers_restore_the_claim() {
+    let (mut deps, env) = setup();
+    let alice = MockApi::default().addr_make("alice");
+
+    let response = claim_over_ibc(&mut deps, &env, None);
+    reply_to_transfer(&mut deps, &env, &response.messages[0], &[7]);
+
+    // the transfer failed on the remote chain, the tokens were refunded to the contract
+    acknowledge(
+        &mut deps,
+        &env,
+        "channel-5",
+        7,
+        StdAck::error("invalid receiver"),
+    );
+
+    let rewards = query_rewards(&deps, &env, alice.as_str());
+    assert!(rewards.claimed.is_empty());
+    assert_eq!(rewards.available_to_claim, coins(1_000, "uom"));
+
+    let claimed: ClaimedResponse = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Claimed {
+                address: None,
+                start_from: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(claimed.claimed.is_empty());
+
+    let history: ClaimHistoryResponse = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ClaimHistory {
+                address: alice.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(history.history.is_empty());
+
+    // the callback is only handled once
+    acknowledge(
+        &mut deps,
+        &env,
+        "channel-5",
+        7,
+        StdAck::error("invalid receiver"),
+    );
+    let rewards = query_rewards(&deps, &env, alice.as_str());
+    assert_eq!(rewards.available_to_claim, coins(1_000, "uom"));
+
+    // the claim can be made again, this time timing out
+    let response = claim_over_ibc(&mut deps, &env, None);
+    reply_to_transfer(&mut deps, &env, &response.messages[0], &[8]);
+
+    let response = ibc_source_callback(
+        deps.as_mut(),
+        env.clone(),
+        IbcSourceCallbackMsg::Timeout(IbcTimeoutCallbackMsg::new(
+            transfer_packet("channel-5", 8),
+            Addr::unchecked("relayer"),
+        )),
+    )
+    .unwrap();
+    assert!(response
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "status" && attr.value == "restored"));
+
+    let rewards = query_rewards(&deps, &env, alice.as_str());
+    assert!(rewards.claimed.is_empty());
+    assert_eq!(rewards.available_to_claim, coins(1_000, "uom"));
+}
+
+#[test]
+fn failed_transfers_follow_replaced_addresses() {
+    let (mut deps, env) = setup();
+    let alice = MockApi::default().addr_make("alice");
+    let new_alice = MockApi::default().addr_make("new_alice");
+
+    let response = claim_over_ibc(&mut deps, &env, None);
+    reply_to_transfer(&mut deps, &env, &response.messages[0], &[7]);
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        message_info(&MockApi::default().addr_make("owner"), &[]),
+        ExecuteMsg::ReplaceAddress {
+            old_address: alice.to_string(),
+            new_address: new_alice.to_string(),
+        },
+    )
+    .unwrap();
+
+    let response = acknowledge(&mut deps, &env, "channel-5", 7, StdAck::error("failed"));
+    assert_eq!(status(&response), "restored");
+
+    let rewards = query_rewards(&deps, &env, new_alice.as_str());
+    assert!(rewards.claimed.is_empty());
+    assert_eq!(rewards.available_to_claim, coins(1_000, "uom"));
+}
+
+#[test]
+fn failed_transfers_are_refunded_once_the_campaign_is_closed() {
+    let (mut deps, env) = setup();
+    let owner = MockApi::default().addr_make("owner");
+    let alice = MockApi::default().addr_make("alice");
+
+    let response = claim_over_ibc(&mut deps, &env, None);
+    reply_to_transfer(&mut deps, &env, &response.messages[0], &[7]);
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        message_info(&owner, &[]),
+        ExecuteMsg::ManageCampaign {
+            action: CampaignAction::CloseCampaign {},
+        },
+    )
+    .unwrap();
+
+    // the claim can't be made again, so the refunded tokens go to the owner
+    let response = acknowledge(&mut deps, &env, "channel-5", 7, StdAck::error("failed"));
+    assert_eq!(status(&response), "refunded");
+    assert_eq!(
+        response.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: owner.to_string(),
+            amount: coins(1_000, "uom"),
+        })
+    );
+
+    let rewards = query_rewards(&deps, &env, alice.as_str());
+    assert_eq!(rewards.claimed, coins(1_000, "uom"));
+}
+
+#[test]
+fn failed_transfers_are_kept_once_the_campaign_is_closed_without_owner() {
+    let (mut deps, env) = setup();
+    let owner = MockApi::default().addr_make("owner");
+
+    let response = claim_over_ibc(&mut deps, &env, None);
+    reply_to_transfer(&mut deps, &env, &response.messages[0], &[7]);
+
+    for msg in [
+        ExecuteMsg::ManageCampaign {
+            action: CampaignAction::CloseCampaign {},
+        },
+        ExecuteMsg::UpdateOwnership(cw_ownable::Action::RenounceOwnership {}),
+    ] {
+        execute(deps.as_mut(), env.clone(), message_info(&owner, &[]), msg).unwrap();
+    }
+
+    // there's no owner to refund, so the tokens stay in the contract
+    let response = acknowledge(&mut deps, &env, "channel-5", 7, StdAck::error("failed"));
+    assert_eq!(status(&response), "unrestorable");
+    assert!(response.messages.is_empty());
+}
+
+#[test]
+fn failed_transfers_clear_the_first_claim_anchor() {
+    let env = mock_env();
+    let (mut deps, env) = setup_with(vec![
+        DistributionType::LumpSum {
+            percentage: Decimal::percent(50),
+            start_time: env.block.time.seconds(),
+        },
+        DistributionType::RelativeLinearVesting {
+            percentage: Decimal::percent(50),
+            anchor: VestingAnchor::FirstClaim,
+            start_offset: 0,
+            duration: 86_400 * 10,
+            cliff_duration: None,
+        },
+    ]);
+    let alice = MockApi::default().addr_make("alice");
+
+    // the claim anchors the vesting of alice
+    let response = claim_over_ibc(&mut deps, &env, None);
+    reply_to_transfer(&mut deps, &env, &response.messages[0], &[7]);
+    let anchors = VESTING_ANCHORS
+        .load(deps.as_ref().storage, alice.as_str())
+        .unwrap();
+    assert_eq!(anchors.first_claimed_at, Some(env.block.time.seconds()));
+
+    let response = acknowledge(&mut deps, &env, "channel-5", 7, StdAck::error("failed"));
+    assert_eq!(status(&response), "restored");
+
+    // the vesting starts at the next claim instead
+    let anchors = VESTING_ANCHORS
+        .load(deps.as_ref().storage, alice.as_str())
+        .unwrap();
+    assert_eq!(anchors.first_claimed_at, None);
+
+    let mut env = env;
+    env.block.time = env.block.time.plus_days(5);
+    let rewards = query_rewards(&deps, &env, alice.as_str());
+    assert!(rewards.claimed.is_empty());
+    assert_eq!(rewards.available_to_claim, coins(500, "uom"));
+}
+
+#[test]
+fn restored_claims_give_the_amount_back_to_the_approval() {
+    let (mut deps, env) = setup();
+    let alice = MockApi::default().addr_make("alice");
+    let operator = MockApi::default().addr_make("operator");
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        message_info(&alice, &[]),
+        ExecuteMsg::ApproveClaimer {
+            operator: operator.to_string(),
+            expires: None,
+            max_amount: Some(Uint128::new(500)),
+        },
+    )
+    .unwrap();
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        message_info(&operator, &[]),
+        ExecuteMsg::Claim {
+            receiver: Some(alice.to_string()),
+            amount: Some(Uint128::new(300)),
+            ibc_destination: None,
+        },
+    )
+    .unwrap();
+
+    let approval = CLAIM_APPROVALS
+        .load(deps.as_ref().storage, (alice.as_str(), operator.as_str()))
+        .unwrap();
+    assert_eq!(approval.max_amount, Some(Uint128::new(200)));
+
+    let slots = CLAIMS
+        .prefix(alice.as_str())
+        .range(deps.as_ref().storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(slot, claim)| (slot, claim.amount)))
+        .collect::<StdResult<Vec<_>>>()
+        .unwrap();
+    let transfer = PendingIbcTransfer {
+        channel_id: "channel-5".to_string(),
+        address: alice.to_string(),
+        amount: coin(300, "uom"),
+        slots,
+        history_sequence: 1,
+        approved_operator: Some(operator.to_string()),
+        anchored_first_claim: false,
+    };
+    assert_eq!(
+        restore_claim(deps.as_mut().storage, &transfer).unwrap(),
+        ClaimRestoration::Restored
+    );
+
+    let approval = CLAIM_APPROVALS
+        .load(deps.as_ref().storage, (alice.as_str(), operator.as_str()))
+        .unwrap();
+    assert_eq!(approval.max_amount, Some(Uint128::new(500)));
+
+    let rewards = query_rewards(&deps, &env, alice.as_str());
+    assert!(rewards.claimed.is_empty());
+}
+
+#[test]
+fn failed_transfers_with_missing_claims_are_not_restored() {
+    let (mut deps, env) = setup();
+    let alice = MockApi::default().addr_make("alice");
+
+    let response = claim_over_ibc(&mut deps, &env, None);
+    reply_to_transfer(&mut deps, &env, &response.messages[0], &[7]);
+
+    // the claimed amounts are no longer recorded for alice
+    CLAIMS.remove(deps.as_mut().storage, (alice.as_str(), 0));
+
+    let response = acknowledge(&mut deps, &env, "channel-5", 7, StdAck::error("failed"));
+    assert_eq!(status(&response), "unrestorable");
+    assert!(response.messages.is_empty());
+
+    let campaign = CAMPAIGN.load(deps.as_ref().storage).unwrap();
+    assert_eq!(campaign.claimed, coin(1_000, "uom"));
+}
+
+#[test]
+fn failed_remote_payouts_restore_the_claim() {
+    let (mut deps, env) = setup();
+    allow_channel(&mut deps, &env, "channel-1", "osmo", Some("channel-7"));
+
+    let response = receive(
+        &mut deps,
+        &env,
+        "channel-1",
+        &claim_packet(None, remote_payout()),
+    );
+    assert!(ack(&response).is_success());
+    reply_to_transfer(&mut deps, &env, &response.messages[0], &[1]);
+
+    // transfers over other channels, or not sent by claims, are ignored
+    acknowledge(&mut deps, &env, "channel-5", 1, StdAck::error("failed"));
+    let rewards = query_rewards(&deps, &env, REMOTE_ADDRESS);
+    assert_eq!(rewards.claimed, coins(1_000, "uom"));
+
+    acknowledge(&mut deps, &env, "channel-7", 1, StdAck::error("failed"));
+    let rewards = query_rewards(&deps, &env, REMOTE_ADDRESS);
+    assert!(rewards.claimed.is_empty());
+    assert_eq!(rewards.available_to_claim, coins(1_000, "uom"));
+}
+
+#[test]
+fn approved_operators_cannot_transfer_claims() {
+    let (mut deps, env) = setup();
+    let alice = MockApi::default().addr_make("alice");
+    let operator = MockApi::default().addr_make("operator");
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        message_info(&alice, &[]),
+        ExecuteMsg::ApproveClaimer {
+            operator: operator.to_string(),
+            expires: None,
+            max_amount: None,
+        },
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        message_info(&operator, &[]),
+        ExecuteMsg::Claim {
+            receiver: Some(alice.to_string()),
+            amount: None,
+            ibc_destination: Some(IbcDestination {
+                channel_id: "channel-5".to_string(),
+                remote_address: "osmo1operator".to_string(),
+                timeout: None,
+            }),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized));
+}
@@ -46,6 +46,8 @@ fn create_multiple_campaigns_fails() {
                     }],
                     start_time: current_time.seconds() + 1,
                     end_time: current_time.seconds() + 172_800,
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -68,6 +70,8 @@ fn create_multiple_campaigns_fails() {
                     }],
                     start_time: current_time.seconds() + 1,
                     end_time: current_time.seconds() + 172_800,
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -109,6 +113,8 @@ fn create_multiple_campaigns_fails() {
                     }],
                     start_time: current_time.seconds() + 1,
                     end_time: current_time.seconds() + 172_800,
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -152,6 +158,8 @@ fn cant_create_campaign_if_not_owner() {
                     }],
                     start_time: current_time.seconds() + 1,
                     end_time: current_time.seconds() + 172_800,
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -196,6 +204,8 @@ fn validate_campaign_params() {
                     }],
                     start_time: current_time.seconds() + 1,
                     end_time: current_time.seconds() + 172_800,
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -224,6 +234,8 @@ fn validate_campaign_params() {
                 }],
                 start_time: current_time.seconds() + 1,
                 end_time: current_time.seconds() + 172_800,
+                schedules: vec![],
+                require_finalized_allocations: false,
             }),
         },
         &[], // No funds during campaign creation
@@ -253,6 +265,8 @@ fn validate_campaign_params() {
                     }],
                     start_time: current_time.seconds() + 1,
                     end_time: current_time.seconds() + 172_800,
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -282,6 +296,8 @@ fn validate_campaign_params() {
                     }],
                     start_time: current_time.seconds() + 1,
                     end_time: current_time.seconds() + 172_800,
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -312,6 +328,8 @@ fn validate_campaign_params() {
                     }],
                     start_time: current_time.seconds() + 1,
                     end_time: current_time.seconds() + 172_800,
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -342,6 +360,8 @@ fn validate_campaign_params() {
                     }],
                     start_time: current_time.seconds() + 1,
                     end_time: current_time.seconds() + 172_800,
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -372,6 +392,8 @@ fn validate_campaign_params() {
                     }],
                     start_time: current_time.seconds() + 172_800,
                     end_time: current_time.seconds() + 1,
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -401,6 +423,8 @@ fn validate_campaign_params() {
                     }],
                     start_time: current_time.seconds() - 100,
                     end_time: current_time.seconds() + 1,
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -428,6 +452,8 @@ fn validate_campaign_params() {
                     distribution_type: vec![],
                     start_time: current_time.seconds() + 1,
                     end_time: current_time.seconds() + 172_800,
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -466,6 +492,8 @@ fn validate_campaign_params() {
                 ],
                 start_time: current_time.seconds() + 1,
                 end_time: current_time.seconds() + 172_800,
+                schedules: vec![],
+                require_finalized_allocations: false,
             }),
         },
         &[], // No funds during campaign creation
@@ -495,6 +523,8 @@ fn validate_campaign_params() {
                     }],
                     start_time: current_time.seconds() + 1,
                     end_time: current_time.seconds() + 172_800,
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -524,6 +554,8 @@ fn validate_campaign_params() {
                     }],
                     start_time: current_time.seconds() + 1,
                     end_time: current_time.seconds() + 172_800,
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -553,6 +585,8 @@ fn validate_campaign_params() {
                     }],
                     start_time: current_time.seconds() + 1,
                     end_time: current_time.seconds() + 172_800,
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -579,6 +613,8 @@ fn validate_campaign_params() {
                     }],
                     start_time: current_time.seconds() + 1,
                     end_time: current_time.seconds() + 172_800,
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -605,6 +641,8 @@ fn validate_campaign_params() {
                     }],
                     start_time: current_time.seconds() + 1,
                     end_time: current_time.seconds() + 172_800,
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -638,7 +676,8 @@ fn validate_campaign_params() {
                     ],
                     start_time: current_time.seconds() + 1,
                     end_time: current_time.seconds() + 172_800,
-
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -665,10 +704,12 @@ fn validate_campaign_params() {
                         start_time: current_time.seconds() + 1,
                         end_time: current_time.seconds() + 172_800,
                         cliff_duration: Some(0u64),
+                        early_exit_penalty: None,
                     }],
                     start_time: current_time.seconds() + 1,
                     end_time: current_time.seconds() + 172_800,
-
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -697,10 +738,12 @@ fn validate_campaign_params() {
                         start_time: current_time.seconds(),
                         end_time: current_time.plus_days(7).seconds(),
                         cliff_duration: Some(7 * 86_400u64),
+                        early_exit_penalty: None,
                     }],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(7).seconds(),
-
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -731,6 +774,8 @@ fn validate_campaign_params() {
                     }],
                     start_time: current_time.seconds() + 1,
                     end_time: current_time.seconds() + 172_800,
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -760,6 +805,8 @@ fn validate_campaign_params() {
                     }],
                     start_time: current_time.seconds() + 1,
                     end_time: current_time.seconds() + 172_800,
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -789,7 +836,8 @@ fn validate_campaign_params() {
                     }],
                     start_time: current_time.seconds() + 1,
                     end_time: current_time.seconds() + 172_800,
-
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -827,9 +875,12 @@ fn cannot_start_distribution_in_past() {
                         start_time: current_time.minus_days(10).seconds(),
                         end_time: current_time.plus_days(60).seconds(),
                         cliff_duration: None,
+                        early_exit_penalty: None,
                     }],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(60).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[],
@@ -915,6 +966,8 @@ fn create_campaign_and_claim_single_distribution_type() {
                     }],
                     start_time: current_time.seconds() + 1,
                     end_time: current_time.seconds() + 172_800,
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -1063,6 +1116,8 @@ fn cant_claim_unfunded_campaign() {
                 }],
                 start_time: current_time.seconds() + 1,
                 end_time: current_time.seconds() + 172_800,
+                schedules: vec![],
+                require_finalized_allocations: false,
             }),
         },
         &[],
@@ -1157,6 +1212,8 @@ fn claim_ended_campaign() {
                     }],
                     start_time: current_time.seconds() + 1,
                     end_time: current_time.seconds() + 172_800,
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -1346,10 +1403,13 @@ fn query_claimed() {
                             start_time: current_time.plus_days(7).seconds(),
                             end_time: current_time.plus_days(14).seconds(),
                             cliff_duration: None,
+                            early_exit_penalty: None,
                         },
                     ],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(14).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -1617,10 +1677,13 @@ fn create_campaign_and_claim_multiple_distribution_types() {
                             start_time: current_time.plus_days(7).seconds(),
                             end_time: current_time.plus_days(14).seconds(),
                             cliff_duration: None,
+                            early_exit_penalty: None,
                         },
                     ],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(14).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -1659,6 +1722,7 @@ fn create_campaign_and_claim_multiple_distribution_types() {
                     claimed: coins(2_500u128, "uom"),
                     pending: coins(10_000u128 - 2_500u128, "uom"),
                     available_to_claim: vec![],
+                    redistributed: vec![],
                 }
             );
         })
@@ -1669,7 +1733,7 @@ fn create_campaign_and_claim_multiple_distribution_types() {
             |result: Result<AppResponse, anyhow::Error>| {
                 let err = result.unwrap_err().downcast::<ContractError>().unwrap();
                 match err {
-                    ContractError::NothingToClaim { .. } => {}
+                    ContractError::NothingToClaim => {}
                     _ => panic!("Wrong error type, should return ContractError::NothingToClaim"),
                 }
             },
@@ -1691,7 +1755,7 @@ fn create_campaign_and_claim_multiple_distribution_types() {
             |result: Result<AppResponse, anyhow::Error>| {
                 let err = result.unwrap_err().downcast::<ContractError>().unwrap();
                 match err {
-                    ContractError::NothingToClaim { .. } => {}
+                    ContractError::NothingToClaim => {}
                     _ => panic!("Wrong error type, should return ContractError::NothingToClaim"),
                 }
             },
@@ -1718,6 +1782,7 @@ fn create_campaign_and_claim_multiple_distribution_types() {
                     // Claimed: 3571. Pending: 10000 - 3571 = 6429
                     pending: coins(10_000u128 - 3_571u128, "uom"),
                     available_to_claim: coins(4_286u128, "uom"), // 4 days * (7500/7)
+                    redistributed: vec![],
                 }
             );
         })
@@ -1737,6 +1802,7 @@ fn create_campaign_and_claim_multiple_distribution_types() {
                     claimed: coins(7_857u128, "uom"),
                     pending: coins(10_000u128 - 7_857u128, "uom"), // 2143
                     available_to_claim: vec![], // All currently vested is claimed.
+                    redistributed: vec![],
                 }
             );
         })
@@ -1760,7 +1826,7 @@ fn create_campaign_and_claim_multiple_distribution_types() {
             |result: Result<AppResponse, anyhow::Error>| {
                 let err = result.unwrap_err().downcast::<ContractError>().unwrap();
                 match err {
-                    ContractError::NothingToClaim { .. } => {}
+                    ContractError::NothingToClaim => {}
                     _ => panic!("Wrong error type, should return ContractError::NothingToClaim"),
                 }
             },
@@ -1777,6 +1843,7 @@ fn create_campaign_and_claim_multiple_distribution_types() {
                     claimed: vec![],
                     pending: coins(35_000u128, "uom"),
                     available_to_claim: coins(35_000u128, "uom"), // All available as campaign ended
+                    redistributed: vec![],
                 }
             );
         })
@@ -1795,6 +1862,7 @@ fn create_campaign_and_claim_multiple_distribution_types() {
                     claimed: coins(35_000u128, "uom"),
                     pending: vec![],
                     available_to_claim: vec![],
+                    redistributed: vec![],
                 }
             );
         })
@@ -1839,9 +1907,12 @@ fn claim_campaign_with_cliff() {
                         start_time: current_time.seconds(),
                         end_time: current_time.plus_days(1460).seconds(), // 4 years
                         cliff_duration: Some(86_400 * 365),               // 1 year cliff
+                        early_exit_penalty: None,
                     }],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(1460).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -1865,7 +1936,7 @@ fn claim_campaign_with_cliff() {
         |result: Result<AppResponse, anyhow::Error>| {
             let err = result.unwrap_err().downcast::<ContractError>().unwrap();
             match err {
-                ContractError::NothingToClaim { .. } => {}
+                ContractError::NothingToClaim => {}
                 _ => panic!("Wrong error type, should return ContractError::NothingToClaim"),
             }
         },
@@ -1885,7 +1956,7 @@ fn claim_campaign_with_cliff() {
         |result: Result<AppResponse, anyhow::Error>| {
             let err = result.unwrap_err().downcast::<ContractError>().unwrap();
             match err {
-                ContractError::NothingToClaim { .. } => {}
+                ContractError::NothingToClaim => {}
                 _ => panic!("Wrong error type, should return ContractError::NothingToClaim"),
             }
         },
@@ -1914,6 +1985,7 @@ fn claim_campaign_with_cliff() {
                     claimed: coins(10_000 / 4, "uom"),
                     pending: coins(10_000u128 - (10_000 / 4), "uom"),
                     available_to_claim: vec![],
+                    redistributed: vec![],
                 }
             );
         });
@@ -1949,6 +2021,7 @@ fn claim_campaign_with_cliff() {
                     claimed: coins((10_000 / 4) * 2, "uom"),
                     pending: coins(10_000u128 - ((10_000 / 4) * 2), "uom"),
                     available_to_claim: coins((10_000 / 4) * 2, "uom"),
+                    redistributed: vec![],
                 }
             );
         })
@@ -1971,6 +2044,7 @@ fn claim_campaign_with_cliff() {
                     claimed: coins(10_000u128, "uom"),
                     pending: vec![],
                     available_to_claim: vec![],
+                    redistributed: vec![],
                 }
             );
         });
@@ -2012,6 +2086,7 @@ fn claim_campaign_with_vesting_cliff_and_lump_sum() {
                             start_time: current_time.seconds(),
                             end_time: current_time.plus_days(1460).seconds(), // 4 years
                             cliff_duration: Some(86_400 * 365),               // 1 year cliff
+                            early_exit_penalty: None,
                         },
                         DistributionType::LumpSum {
                             percentage: Decimal::percent(50),
@@ -2020,6 +2095,8 @@ fn claim_campaign_with_vesting_cliff_and_lump_sum() {
                     ],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(1460).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -2068,7 +2145,7 @@ fn claim_campaign_with_vesting_cliff_and_lump_sum() {
         |result: Result<AppResponse, anyhow::Error>| {
             let err = result.unwrap_err().downcast::<ContractError>().unwrap();
             match err {
-                ContractError::NothingToClaim { .. } => {}
+                ContractError::NothingToClaim => {}
                 _ => panic!("Wrong error type, should return ContractError::NothingToClaim"),
             }
         },
@@ -2100,6 +2177,7 @@ fn claim_campaign_with_vesting_cliff_and_lump_sum() {
                     claimed: coins(5_000u128 + 5_000u128 / 4, "uom"),
                     pending: coins(10_000u128 - (5_000u128 + 5_000u128 / 4), "uom"),
                     available_to_claim: vec![],
+                    redistributed: vec![],
                 }
             );
         });
@@ -2138,6 +2216,7 @@ fn claim_campaign_with_vesting_cliff_and_lump_sum() {
                     claimed: coins(5_000u128 + (2 * 5_000u128 / 4), "uom"),
                     pending: coins(10_000u128 - (5_000u128 + (2 * 5_000u128 / 4)), "uom"),
                     available_to_claim: coins(2 * 5_000u128 / 4, "uom"),
+                    redistributed: vec![],
                 }
             );
         })
@@ -2160,6 +2239,7 @@ fn claim_campaign_with_vesting_cliff_and_lump_sum() {
                     claimed: coins(10_000u128, "uom"),
                     pending: vec![],
                     available_to_claim: vec![],
+                    redistributed: vec![],
                 }
             );
         });
@@ -2200,6 +2280,7 @@ fn claim_campaign_with_vesting_cliff_in_future_and_lump_sum() {
                             start_time: current_time.plus_days(30).seconds(),
                             end_time: current_time.plus_days(60).seconds(), // 30 days duration
                             cliff_duration: Some(86_400 * 7),               // 7 days cliff
+                            early_exit_penalty: None,
                         },
                         DistributionType::LumpSum {
                             percentage: Decimal::percent(50),
@@ -2208,6 +2289,8 @@ fn claim_campaign_with_vesting_cliff_in_future_and_lump_sum() {
                     ],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(90).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -2256,7 +2339,7 @@ fn claim_campaign_with_vesting_cliff_in_future_and_lump_sum() {
         |result: Result<AppResponse, anyhow::Error>| {
             let err = result.unwrap_err().downcast::<ContractError>().unwrap();
             match err {
-                ContractError::NothingToClaim { .. } => {}
+                ContractError::NothingToClaim => {}
                 _ => panic!("Wrong error type, should return ContractError::NothingToClaim"),
             }
         },
@@ -2272,7 +2355,7 @@ fn claim_campaign_with_vesting_cliff_in_future_and_lump_sum() {
         |result: Result<AppResponse, anyhow::Error>| {
             let err = result.unwrap_err().downcast::<ContractError>().unwrap();
             match err {
-                ContractError::NothingToClaim { .. } => {}
+                ContractError::NothingToClaim => {}
                 _ => panic!("Wrong error type, should return ContractError::NothingToClaim"),
             }
         },
@@ -2290,6 +2373,7 @@ fn claim_campaign_with_vesting_cliff_in_future_and_lump_sum() {
                 claimed: coins(5_000u128, "uom"),
                 pending: coins(10_000u128 - 5_000u128, "uom"),
                 available_to_claim: vec![],
+                redistributed: vec![],
             }
         );
     });
@@ -2304,6 +2388,7 @@ fn claim_campaign_with_vesting_cliff_in_future_and_lump_sum() {
                 claimed: coins(5_000u128, "uom"),
                 pending: coins(10_000u128 - 5_000u128, "uom"),
                 available_to_claim: coins(7 * 5_000u128 / 30u128, "uom"), // 7 days (cliff) out of 30 day vesting period of 5000 tokens = 1166
+                redistributed: vec![],
             }
         );
     });
@@ -2352,6 +2437,7 @@ fn claim_campaign_with_vesting_cliff_in_future_and_lump_sum() {
                     claimed: coins(previous_total_claimed, "uom"),
                     pending: coins(10_000u128 - previous_total_claimed, "uom"),
                     available_to_claim: coins(remaining_to_claim_from_vesting, "uom"),
+                    redistributed: vec![],
                 }
             );
         })
@@ -2374,6 +2460,7 @@ fn claim_campaign_with_vesting_cliff_in_future_and_lump_sum() {
                     claimed: coins(10_000u128, "uom"),
                     pending: vec![],
                     available_to_claim: vec![],
+                    redistributed: vec![],
                 }
             );
         });
@@ -2424,9 +2511,12 @@ fn topup_campaigns_with_and_without_cliff() {
                         start_time: current_time.seconds(),
                         end_time: current_time.plus_days(30).seconds(), // a month
                         cliff_duration: Some(86_400 * 7),               // 7 days cliff
+                        early_exit_penalty: None,
                     }],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(30).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation // Initial funding less than total_reward
@@ -2552,9 +2642,12 @@ fn topup_campaigns_with_and_without_cliff() {
                         start_time: current_time_after_first_campaign.seconds(),
                         end_time: current_time_after_first_campaign.plus_days(30).seconds(), // a month
                         cliff_duration: None, // no cliff
+                        early_exit_penalty: None,
                     }],
                     start_time: current_time_after_first_campaign.seconds(),
                     end_time: current_time_after_first_campaign.plus_days(30).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation // Initial funding
@@ -2626,10 +2719,13 @@ fn query_rewards() {
                             start_time: current_time.plus_days(7).seconds(),
                             end_time: current_time.plus_days(14).seconds(),
                             cliff_duration: None,
+                            early_exit_penalty: None,
                         },
                     ],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(14).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -2661,6 +2757,7 @@ fn query_rewards() {
                     claimed: coins(2_500u128, "uom"),
                     pending: coins(10_000u128 - 2_500u128, "uom"), // 7500 pending from vesting
                     available_to_claim: vec![],                    // Vesting not started yet
+                    redistributed: vec![],
                 }
             );
         })
@@ -2716,6 +2813,8 @@ fn query_rewards_single_user() {
                     }],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(14).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation // Fund with exact user allocation for simplicity here
@@ -2798,10 +2897,13 @@ fn query_rewards_fails_when_campaign_has_not_started() {
                             start_time: current_time.plus_days(8).seconds(),
                             end_time: current_time.plus_days(15).seconds(),
                             cliff_duration: None,
+                            early_exit_penalty: None,
                         },
                     ],
                     start_time: current_time.plus_days(1).seconds(), // Campaign starts in 1 day
                     end_time: current_time.plus_days(15).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -2880,10 +2982,13 @@ fn close_campaigns() {
                             start_time: current_time.plus_days(7).seconds(),
                             end_time: current_time.plus_days(14).seconds(),
                             cliff_duration: None,
+                            early_exit_penalty: None,
                         },
                     ],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(14).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -2979,10 +3084,13 @@ fn close_campaigns() {
                             start_time: current_time.plus_days(7).seconds(),
                             end_time: current_time.plus_days(14).seconds(),
                             cliff_duration: None,
+                            early_exit_penalty: None,
                         },
                     ],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(14).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -3097,10 +3205,13 @@ fn can_query_claims_after_campaign_is_closed() {
                             start_time: current_time.plus_days(7).seconds(),
                             end_time: current_time.plus_days(14).seconds(),
                             cliff_duration: None,
+                            early_exit_penalty: None,
                         },
                     ],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(14).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -3166,6 +3277,7 @@ fn can_query_claims_after_campaign_is_closed() {
                 claimed: coins(2_500u128, "uom"),
                 pending: coins(7500, "uom"),
                 available_to_claim: coins(1071, "uom"), // 7500 / 7 days for 1 day
+                redistributed: vec![],
             }
         );
     });
@@ -3254,10 +3366,13 @@ fn renouncing_contract_owner_makes_prevents_creating_campaigns() {
                             start_time: current_time.plus_days(7).seconds(),
                             end_time: current_time.plus_days(14).seconds(),
                             cliff_duration: None,
+                            early_exit_penalty: None,
                         },
                     ],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(14).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -3348,6 +3463,8 @@ fn renouncing_contract_owner_makes_prevents_creating_campaigns() {
                     distribution_type: vec![ /* ... */ ],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(14).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -3402,9 +3519,12 @@ fn can_claim_dust_without_new_claims() {
                         start_time: current_time.seconds(),
                         end_time: current_time.plus_days(60).seconds(),
                         cliff_duration: None,
+                        early_exit_penalty: None,
                     }],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(60).seconds(), // Campaign ends when vesting ends
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -3497,9 +3617,12 @@ fn cant_end_distribution_type_after_campaign() {
                         start_time: current_time.seconds(),
                         end_time: current_time.plus_days(30).seconds(), // Dist ends after campaign
                         cliff_duration: None,
+                        early_exit_penalty: None,
                     }],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(7).seconds(), // Campaign ends before dist
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -3528,9 +3651,12 @@ fn cant_end_distribution_type_after_campaign() {
                         start_time: current_time.seconds(),
                         end_time: current_time.plus_days(7).seconds(),
                         cliff_duration: None,
+                        early_exit_penalty: None,
                     }],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(7).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -3557,9 +3683,12 @@ fn cant_end_distribution_type_after_campaign() {
                         start_time: current_time.seconds(),
                         end_time: current_time.plus_days(6).seconds(), // Dist ends before campaign here, which is fine.
                         cliff_duration: None,
+                        early_exit_penalty: None,
                     }],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(7).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -3624,10 +3753,13 @@ fn test_add_allocations() {
                             start_time: current_time.plus_days(8).seconds(),
                             end_time: current_time.plus_days(15).seconds(),
                             cliff_duration: None,
+                            early_exit_penalty: None,
                         },
                     ],
                     start_time: current_time.plus_days(1).seconds(),
                     end_time: current_time.plus_days(15).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -3985,6 +4117,8 @@ fn test_cannot_add_allocations_after_campaign_start() {
                     }],
                     start_time: current_time.plus_days(1).seconds(), // Campaign starts in 1 day
                     end_time: current_time.plus_days(14).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -4075,6 +4209,8 @@ fn test_replace_address() {
                     }],
                     start_time: current_time.plus_days(1).seconds(),
                     end_time: current_time.plus_days(14).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -4177,6 +4313,8 @@ fn test_replace_address() {
                     ],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(14).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -4315,6 +4453,8 @@ fn test_remove_address() {
                     }],
                     start_time: current_time.plus_days(1).seconds(),
                     end_time: current_time.plus_days(14).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -4464,6 +4604,8 @@ fn test_replace_placeholder_address() {
                     }],
                     start_time: current_time.plus_days(1).seconds(),
                     end_time: current_time.plus_days(14).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -4594,6 +4736,8 @@ fn test_cant_replace_address_with_existing_allocation() {
                     }],
                     start_time: current_time.plus_days(1).seconds(),
                     end_time: current_time.plus_days(14).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -4683,6 +4827,8 @@ fn test_blacklist_address() {
                     }],
                     start_time: current_time.plus_days(1).seconds(),
                     end_time: current_time.plus_days(14).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -4717,7 +4863,7 @@ fn test_blacklist_address() {
         )
         .query_is_blacklisted(carol, |result| {
             let blacklist_status = result.unwrap();
-            assert_eq!(blacklist_status.is_blacklisted, false);
+            assert!(!blacklist_status.is_blacklisted);
         })
         .blacklist_address(
             // Owner succeeds
@@ -4738,11 +4884,11 @@ fn test_blacklist_address() {
         )
         .query_is_blacklisted(carol, |result| {
             let blacklist_status = result.unwrap();
-            assert_eq!(blacklist_status.is_blacklisted, true);
+            assert!(blacklist_status.is_blacklisted);
         })
         .query_is_blacklisted(placeholder, |result| {
             let blacklist_status = result.unwrap();
-            assert_eq!(blacklist_status.is_blacklisted, true);
+            assert!(blacklist_status.is_blacklisted);
         });
 
     suite.add_day(); // Advance 1 day, campaign starts
@@ -4826,10 +4972,13 @@ fn test_claim_more_than_currently_available_fails() {
                             start_time: current_time.plus_days(7).seconds(),
                             end_time: current_time.plus_days(14).seconds(),
                             cliff_duration: Some(3 * 86_400u64),
+                            early_exit_penalty: None,
                         },
                     ],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(14).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -4846,7 +4995,7 @@ fn test_claim_more_than_currently_available_fails() {
 
     // At this point, only lump_sum_share is available. Vesting hasn't started/cliffed.
     suite.claim(
-        &alice,
+        alice,
         None,
         Some(excessive_amount),
         |result: Result<AppResponse, anyhow::Error>| {
@@ -4905,10 +5054,13 @@ fn test_partial_claim_lump_sum() {
                             start_time: current_time.plus_days(7).seconds(),
                             end_time: current_time.plus_days(14).seconds(),
                             cliff_duration: Some(3 * 86_400u64),
+                            early_exit_penalty: None,
                         },
                     ],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(14).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -4940,7 +5092,8 @@ fn test_partial_claim_lump_sum() {
             RewardsResponse {
                 claimed: vec![],
                 pending: vec![coin(Uint128::new(10_000).u128(), "uom")],
-                available_to_claim: vec![coin(alice_lump_sum_share.u128(), "uom")]
+                available_to_claim: vec![coin(alice_lump_sum_share.u128(), "uom")],
+                redistributed: vec![],
             }
         );
     });
@@ -4975,7 +5128,8 @@ fn test_partial_claim_lump_sum() {
             RewardsResponse {
                 claimed: vec![coin(partial_claim_amount.u128(), "uom")],
                 pending: vec![coin(total_pending.u128(), "uom")],
-                available_to_claim: vec![coin(remaining_lump_sum.u128(), "uom")]
+                available_to_claim: vec![coin(remaining_lump_sum.u128(), "uom")],
+                redistributed: vec![],
             }
         );
     });
@@ -5005,7 +5159,8 @@ fn test_partial_claim_lump_sum() {
             RewardsResponse {
                 claimed: vec![coin(alice_lump_sum_share.u128(), "uom")],
                 pending: vec![coin(total_pending_after_full_lump.u128(), "uom")],
-                available_to_claim: vec![]
+                available_to_claim: vec![],
+                redistributed: vec![],
             }
         );
     });
@@ -5057,10 +5212,13 @@ fn test_partial_claim_lumpsum_and_linear_vesting() {
                             start_time: current_time.seconds(),
                             end_time: current_time.plus_days(5).seconds(),
                             cliff_duration: None,
+                            early_exit_penalty: None,
                         },
                     ],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(5).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -5175,7 +5333,8 @@ fn test_partial_claim_lumpsum_and_linear_vesting() {
                     Uint128::new(10_000 - total_claimed_after_partial_vesting.u128()).u128(),
                     reward_denom
                 )],
-                available_to_claim: vec![coin(remaining_vested_available.u128(), reward_denom)]
+                available_to_claim: vec![coin(remaining_vested_available.u128(), reward_denom)],
+                redistributed: vec![],
             }
         );
     });
@@ -5232,7 +5391,8 @@ fn test_partial_claim_lumpsum_and_linear_vesting() {
                 RewardsResponse {
                     claimed: vec![coin(Uint128::new(10_000).u128(), reward_denom)],
                     pending: vec![],
-                    available_to_claim: vec![]
+                    available_to_claim: vec![],
+                    redistributed: vec![],
                 }
             );
         });
@@ -5284,10 +5444,13 @@ fn test_claim_zero_amount_fails() {
                             start_time: current_time.seconds(),
                             end_time: current_time.plus_days(5).seconds(),
                             cliff_duration: None,
+                            early_exit_penalty: None,
                         },
                     ],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(5).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -5361,10 +5524,13 @@ fn test_claim_full_amount_when_none_specified_after_partial_claims() {
                             start_time: current_time.seconds(),
                             end_time: current_time.plus_days(5).seconds(),
                             cliff_duration: None,
+                            early_exit_penalty: None,
                         },
                     ],
                     start_time: current_time.seconds(),
                     end_time: current_time.plus_days(5).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
@@ -5444,6 +5610,7 @@ fn test_claim_full_amount_when_none_specified_after_partial_claims() {
                     reward_denom
                 )],
                 available_to_claim: vec![],
+                redistributed: vec![],
             }
         );
     });
@@ -5511,7 +5678,8 @@ fn test_claim_full_amount_when_none_specified_after_partial_claims() {
                 RewardsResponse {
                     claimed: vec![coin(Uint128::new(10_000).u128(), reward_denom)],
                     pending: vec![],
-                    available_to_claim: vec![]
+                    available_to_claim: vec![],
+                    redistributed: vec![],
                 }
             );
         });
@@ -5561,6 +5729,8 @@ fn test_claim_authorization() {
                     }],
                     start_time: current_time.seconds() + 1,
                     end_time: current_time.seconds() + 172_800,
+                    schedules: vec![],
+                    require_finalized_allocations: false,
                 }),
             },
             &[], // No funds during campaign creation
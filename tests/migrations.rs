@@ -0,0 +1,292 @@
+use std::collections::HashMap;
+
+use claimdrop_contract::contract::{execute, migrate, query, sudo};
+use claimdrop_contract::state::{
+    get_claims_for_address, Claim, DistributionSlot, ALLOCATIONS, TOTAL_ALLOCATED,
+};
+use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env, MockApi};
+use cosmwasm_std::{from_json, Deps, Env, Response, Uint128};
+use cw_storage_plus::Map;
+use mantra_claimdrop_std::error::ContractError;
+use mantra_claimdrop_std::msg::{
+    AuditAction, AuditLogResponse, ExecuteMsg, MigrateMsg, MigrationProgress,
+    MigrationStatusResponse, QueryMsg, SudoMsg,
+};
+
+const CONTRACT_NAME: &str = "mantra_claimdrop-contract";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+fn query_migration_status(deps: Deps, env: &Env) -> MigrationStatusResponse {
+    from_json(query(deps, env.clone(), QueryMsg::MigrationStatus {}).unwrap()).unwrap()
+}
+
+fn attribute<'a>(response: &'a Response, key: &str) -> &'a str {
+    response
+        .attributes
+        .iter()
+        .find(|attribute| attribute.key == key)
+        .map(|attribute| attribute.value.as_str())
+        .unwrap()
+}
+
+/// Sorts the given addresses as they are stored
+fn sorted(mut addresses: Vec<String>) -> Vec<String> {
+    addresses.sort();
+    addresses
+}
+
+#[test]
+fn migration_runs_in_batches() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let api = MockApi::default();
+    let owner = api.addr_make("owner");
+
+    cw2::set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "2.0.0").unwrap();
+    cw_ownable::initialize_owner(deps.as_mut().storage, &api, Some(owner.as_str())).unwrap();
+
+    let blacklisted = sorted(
+        (0..5)
+            .map(|i| api.addr_make(&format!("blacklisted{i}")).to_string())
+            .collect(),
+    );
+    let legacy_blacklist: Map<&str, bool> = Map::new("blacklist");
+    for address in blacklisted.iter() {
+        legacy_blacklist
+            .save(deps.as_mut().storage, address, &true)
+            .unwrap();
+    }
+
+    let claimers = sorted(
+        (0..3)
+            .map(|i| api.addr_make(&format!("claimer{i}")).to_string())
+            .collect(),
+    );
+    let claims: HashMap<DistributionSlot, Claim> =
+        HashMap::from([(0, (Uint128::new(100), env.block.time.seconds()))]);
+    let legacy_claims: Map<String, HashMap<DistributionSlot, Claim>> = Map::new("claims");
+    for address in claimers.iter() {
+        legacy_claims
+            .save(deps.as_mut().storage, address.clone(), &claims)
+            .unwrap();
+    }
+    for address in claimers.iter() {
+        ALLOCATIONS
+            .save(deps.as_mut().storage, address, &Uint128::new(1_000))
+            .unwrap();
+    }
+
+    let response = migrate(
+        deps.as_mut(),
+        env.clone(),
+        MigrateMsg {
+            batch_size: Some(3),
+        },
+    )
+    .unwrap();
+    assert_eq!(attribute(&response, "from_version"), "2.0.0");
+    assert_eq!(attribute(&response, "to_version"), CONTRACT_VERSION);
+    assert_eq!(attribute(&response, "processed"), "3");
+    assert_eq!(attribute(&response, "completed"), "false");
+
+    let status = query_migration_status(deps.as_ref(), &env);
+    assert_eq!(status.version, CONTRACT_VERSION);
+    assert_eq!(
+        status.migration,
+        Some(MigrationProgress {
+            from_version: "2.0.0".to_string(),
+            to_version: CONTRACT_VERSION.to_string(),
+            pending_steps: vec![
+                "blacklist_entries".to_string(),
+                "slot_claims".to_string(),
+                "allocation_totals".to_string(),
+            ],
+            cursor: Some(blacklisted[2].clone()),
+            processed: 3,
+        })
+    );
+
+    // the contract can't be used while the migration is in progress
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        message_info(&api.addr_make(&claimers[0]), &[]),
+        ExecuteMsg::Claim {
+            receiver: None,
+            amount: None,
+            ibc_destination: None,
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::MigrationInProgress));
+
+    // governance can't close the campaign either, but it can still pause the contract and reset
+    // its owner
+    let err = sudo(
+        deps.as_mut(),
+        env.clone(),
+        SudoMsg::ForceCloseCampaign {
+            refund_recipient: owner.to_string(),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::MigrationInProgress));
+
+    sudo(deps.as_mut(), env.clone(), SudoMsg::Pause {}).unwrap();
+    sudo(deps.as_mut(), env.clone(), SudoMsg::Resume {}).unwrap();
+    sudo(
+        deps.as_mut(),
+        env.clone(),
+        SudoMsg::ResetOwner {
+            owner: owner.to_string(),
+        },
+    )
+    .unwrap();
+
+    // nor can the data being migrated be queried
+    let err = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::Claimed {
+            address: None,
+            start_from: None,
+            limit: None,
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::MigrationInProgress));
+
+    // only authorized wallets can continue the migration
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        message_info(&api.addr_make("someone"), &[]),
+        ExecuteMsg::ContinueMigration {
+            batch_size: Some(3),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::OwnershipError(_)));
+
+    let response = execute(
+        deps.as_mut(),
+        env.clone(),
+        message_info(&owner, &[]),
+        ExecuteMsg::ContinueMigration {
+            batch_size: Some(3),
+        },
+    )
+    .unwrap();
+    assert_eq!(attribute(&response, "processed"), "3");
+    assert_eq!(attribute(&response, "completed"), "false");
+
+    let migration = query_migration_status(deps.as_ref(), &env)
+        .migration
+        .unwrap();
+    assert_eq!(
+        migration.pending_steps,
+        vec!["slot_claims".to_string(), "allocation_totals".to_string()]
+    );
+    assert_eq!(migration.cursor, Some(claimers[0].clone()));
+    assert_eq!(migration.processed, 1);
+
+    let response = execute(
+        deps.as_mut(),
+        env.clone(),
+        message_info(&owner, &[]),
+        ExecuteMsg::ContinueMigration { batch_size: None },
+    )
+    .unwrap();
+    assert_eq!(attribute(&response, "processed"), "5");
+    assert_eq!(attribute(&response, "completed"), "true");
+    assert_eq!(query_migration_status(deps.as_ref(), &env).migration, None);
+
+    let audit_log: AuditLogResponse = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::AuditLog {
+                action: Some("continue_migration".to_string()),
+                subject: None,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        audit_log
+            .entries
+            .iter()
+            .map(|entry| entry.action.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            AuditAction::ContinueMigration { processed: 3 },
+            AuditAction::ContinueMigration { processed: 5 },
+        ]
+    );
+    assert_eq!(
+        TOTAL_ALLOCATED.load(deps.as_ref().storage).unwrap(),
+        Uint128::new(3_000)
+    );
+
+    for address in claimers.iter() {
+        assert_eq!(
+            get_claims_for_address(deps.as_ref(), address.clone()).unwrap(),
+            claims
+        );
+    }
+
+    let err = execute(
+        deps.as_mut(),
+        env,
+        message_info(&owner, &[]),
+        ExecuteMsg::ContinueMigration { batch_size: None },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::NoMigrationInProgress));
+}
+
+#[test]
+fn migration_only_runs_steps_of_newer_versions() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    cw2::set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "2.1.0").unwrap();
+
+    let response = migrate(
+        deps.as_mut(),
+        env.clone(),
+        MigrateMsg {
+            batch_size: Some(1),
+        },
+    )
+    .unwrap();
+    assert_eq!(attribute(&response, "processed"), "0");
+    assert_eq!(attribute(&response, "completed"), "true");
+
+    let status = query_migration_status(deps.as_ref(), &env);
+    assert_eq!(status.version, CONTRACT_VERSION);
+    assert_eq!(status.migration, None);
+
+    let err = migrate(deps.as_mut(), env, MigrateMsg { batch_size: None }).unwrap_err();
+    assert!(matches!(err, ContractError::MigrateInvalidVersion { .. }));
+}
+
+#[test]
+fn migration_rejects_empty_batches() {
+    let mut deps = mock_dependencies();
+
+    cw2::set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "2.0.0").unwrap();
+
+    let err = migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            batch_size: Some(0),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidInput { .. }));
+}
@@ -0,0 +1,282 @@
+use crate::suite::TestingSuite;
+use cosmwasm_std::{coin, Decimal, Uint128};
+use cw_multi_test::AppResponse;
+use mantra_claimdrop_std::error::ContractError;
+use mantra_claimdrop_std::msg::{
+    AllocationRow, CampaignAction, CampaignParams, DistributionType, Schedule,
+};
+mod suite;
+
+fn campaign_params(start_time: u64, distribution_type: Vec<DistributionType>) -> CampaignParams {
+    CampaignParams {
+        name: "Test Airdrop I".to_string(),
+        description: "This is an airdrop".to_string(),
+        ty: "airdrop".to_string(),
+        reward_denom: "uom".to_string(),
+        total_reward: coin(2_000, "uom"),
+        distribution_type,
+        start_time,
+        end_time: start_time + 86_400 * 30,
+        schedules: vec![Schedule {
+            id: "team".to_string(),
+            distribution_type: vec![DistributionType::Milestone {
+                id: "listing".to_string(),
+                percentage: Decimal::one(),
+                vesting_duration: None,
+            }],
+        }],
+        require_finalized_allocations: false,
+    }
+}
+
+#[test]
+fn milestones_unlock_when_triggered() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+    let start_time = suite.get_time().plus_days(1).seconds();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .add_allocations(
+            alice,
+            &vec![(bob.to_string(), Uint128::new(1_000))],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(campaign_params(
+                    start_time,
+                    vec![
+                        DistributionType::LumpSum {
+                            percentage: Decimal::percent(50),
+                            start_time,
+                        },
+                        DistributionType::Milestone {
+                            id: "listing".to_string(),
+                            percentage: Decimal::percent(50),
+                            vesting_duration: Some(86_400 * 10),
+                        },
+                    ],
+                )),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .add_allocation_rows(
+            alice,
+            vec![AllocationRow::Scheduled(
+                carol.to_string(),
+                Uint128::new(1_000),
+                "team".to_string(),
+            )],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .top_up_campaign(
+            alice,
+            &[coin(2_000, "uom")],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .add_day()
+        .add_day()
+        // the milestone is locked, bob only gets the lump sum
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_balance("uom", bob, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_000_500));
+        })
+        .query_rewards_breakdown(bob, |result| {
+            let breakdown = result.unwrap();
+            assert_eq!(breakdown.slots[1].vested, coin(0, "uom"));
+            assert_eq!(breakdown.slots[1].start_time, None);
+            assert_eq!(breakdown.slots[1].end_time, None);
+        })
+        .claim(
+            carol,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::NothingToClaim => {}
+                    _ => panic!("Wrong error type, should return ContractError::NothingToClaim"),
+                }
+            },
+        )
+        .trigger_milestone(
+            bob,
+            "listing",
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::OwnershipError(_) => {}
+                    _ => panic!("Wrong error type, should return ContractError::OwnershipError"),
+                }
+            },
+        )
+        .trigger_milestone(
+            alice,
+            "launch",
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidInput { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::InvalidInput"),
+                }
+            },
+        )
+        .trigger_milestone(
+            alice,
+            "listing",
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .trigger_milestone(
+            alice,
+            "listing",
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::CampaignError { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::CampaignError"),
+                }
+            },
+        )
+        // the team schedule releases the milestone in a lump sum
+        .claim(
+            carol,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_balance("uom", carol, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_001_000));
+        })
+        // while the default distribution vests it linearly from the trigger
+        .add_day()
+        .add_day()
+        .add_day()
+        .add_day()
+        .add_day()
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_balance("uom", bob, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_000_750));
+        })
+        .add_week()
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_balance("uom", bob, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_001_000));
+        })
+        .query_rewards_breakdown(bob, |result| {
+            let breakdown = result.unwrap();
+            assert!(breakdown.slots[1].start_time.is_some());
+            assert!(breakdown.slots[1].end_time > breakdown.slots[1].start_time);
+        })
+        .query_campaign(|result| {
+            let campaign = result.unwrap();
+            assert_eq!(campaign.milestones.len(), 1);
+            assert_eq!(campaign.milestones[0].id, "listing");
+        });
+}
+
+#[test]
+fn cannot_create_invalid_milestones() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let start_time = suite.get_time().plus_days(1).seconds();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(campaign_params(
+                    start_time,
+                    vec![
+                        DistributionType::Milestone {
+                            id: "listing".to_string(),
+                            percentage: Decimal::percent(50),
+                            vesting_duration: None,
+                        },
+                        DistributionType::Milestone {
+                            id: "listing".to_string(),
+                            percentage: Decimal::percent(50),
+                            vesting_duration: Some(86_400),
+                        },
+                    ],
+                )),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidCampaignParam { param, .. } => {
+                        assert_eq!(param, "milestone");
+                    }
+                    _ => panic!(
+                        "Wrong error type, should return ContractError::InvalidCampaignParam"
+                    ),
+                }
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(campaign_params(
+                    start_time,
+                    vec![DistributionType::Milestone {
+                        id: "listing".to_string(),
+                        percentage: Decimal::one(),
+                        vesting_duration: Some(0),
+                    }],
+                )),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidCampaignParam { param, .. } => {
+                        assert_eq!(param, "vesting_duration");
+                    }
+                    _ => panic!(
+                        "Wrong error type, should return ContractError::InvalidCampaignParam"
+                    ),
+                }
+            },
+        );
+}
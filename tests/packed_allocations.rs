@@ -0,0 +1,172 @@
+use std::cell::RefCell;
+
+use crate::suite::TestingSuite;
+use cosmwasm_std::{coin, Api, Binary, CanonicalAddr, Uint128};
+use cw_multi_test::{AppResponse, MockApiBech32};
+use mantra_claimdrop_std::error::ContractError;
+use mantra_claimdrop_std::msg::{AllocationsResponse, ExportStateResponse};
+mod suite;
+
+enum PackedAddress {
+    Canonical([u8; 20]),
+    Text(String),
+}
+
+fn encode_varint(mut value: u128, data: &mut Vec<u8>) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            data.push(byte);
+            return;
+        }
+        data.push(byte | 0x80);
+    }
+}
+
+fn pack(allocations: &[(PackedAddress, u128)]) -> Binary {
+    let mut data = vec![];
+
+    for (address, amount) in allocations {
+        match address {
+            PackedAddress::Canonical(canonical) => {
+                encode_varint(0, &mut data);
+                data.extend_from_slice(canonical);
+            }
+            PackedAddress::Text(address) => {
+                encode_varint(address.len() as u128, &mut data);
+                data.extend_from_slice(address.as_bytes());
+            }
+        }
+        encode_varint(*amount, &mut data);
+    }
+
+    Binary::from(data)
+}
+
+/// Returns the allocations and the exported state of the contract
+fn snapshot(suite: &mut TestingSuite) -> (AllocationsResponse, ExportStateResponse) {
+    let allocations = RefCell::new(None);
+    let state = RefCell::new(None);
+
+    suite
+        .query_allocations(None, None, Some(100), |result| {
+            *allocations.borrow_mut() = Some(result.unwrap());
+        })
+        .query_export_state(None, Some(100), |result| {
+            *state.borrow_mut() = Some(result.unwrap());
+        });
+
+    (
+        allocations.into_inner().unwrap(),
+        state.into_inner().unwrap(),
+    )
+}
+
+#[test]
+fn packed_allocations_match_json_allocations() {
+    let canonical = [7u8; 20];
+    let canonical_address = MockApiBech32::new("mantra")
+        .addr_humanize(&CanonicalAddr::from(canonical.to_vec()))
+        .unwrap();
+
+    let mut json_suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+    let alice = &json_suite.senders[0].clone();
+    let bob = &json_suite.senders[1].clone();
+    let carol = &json_suite.senders[2].clone();
+
+    json_suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .add_allocations(
+            alice,
+            &vec![
+                (bob.to_string(), Uint128::new(1_000)),
+                (carol.to_string(), Uint128::new(u64::MAX as u128 * 1_000)),
+                (canonical_address.to_string(), Uint128::new(1)),
+                ("vitalik.eth".to_string(), Uint128::new(128)),
+            ],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        );
+
+    let mut packed_suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    packed_suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .add_allocations_packed(
+            alice,
+            pack(&[
+                (PackedAddress::Text(bob.to_string()), 1_000),
+                (
+                    PackedAddress::Text(carol.to_string()),
+                    u64::MAX as u128 * 1_000,
+                ),
+                (PackedAddress::Canonical(canonical), 1),
+                (PackedAddress::Text("vitalik.eth".to_string()), 128),
+            ]),
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        );
+
+    let (json_allocations, json_state) = snapshot(&mut json_suite);
+    let (packed_allocations, packed_state) = snapshot(&mut packed_suite);
+
+    assert_eq!(json_allocations.allocations.len(), 4);
+    assert_eq!(json_allocations, packed_allocations);
+    assert_eq!(json_state, packed_state);
+}
+
+#[test]
+fn cannot_add_malformed_packed_allocations() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+
+    let valid = pack(&[(PackedAddress::Text(bob.to_string()), 1_000)]);
+    // the amount is missing
+    let truncated = Binary::from(&valid[..valid.len() - 2]);
+    // the amount doesn't fit in 128 bits
+    let mut overflowing = pack(&[(PackedAddress::Text(bob.to_string()), 0)]).to_vec();
+    overflowing.pop();
+    overflowing.extend_from_slice(&[0xff; 18]);
+    overflowing.push(0x7f);
+
+    suite.instantiate_claimdrop_contract(Some(alice.to_string()));
+
+    for data in [Binary::default(), truncated, Binary::from(overflowing)] {
+        suite.add_allocations_packed(alice, data, |result: Result<AppResponse, anyhow::Error>| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::InvalidInput { .. } => {}
+                _ => panic!("Wrong error type, should return ContractError::InvalidInput"),
+            }
+        });
+    }
+
+    suite
+        .add_allocations_packed(
+            bob,
+            valid.clone(),
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::OwnershipError(_) => {}
+                    _ => panic!("Wrong error type, should return ContractError::OwnershipError"),
+                }
+            },
+        )
+        .add_allocations_packed(
+            alice,
+            valid,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_allocations(Some(bob), None, None, |result| {
+            let allocations = result.unwrap().allocations;
+            assert_eq!(allocations.len(), 1);
+            assert_eq!(allocations[0].1.amount, Uint128::new(1_000));
+        });
+}
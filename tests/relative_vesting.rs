@@ -0,0 +1,408 @@
+use crate::suite::TestingSuite;
+use cosmwasm_std::{coin, Decimal, Uint128};
+use cw_multi_test::AppResponse;
+use mantra_claimdrop_std::error::ContractError;
+use mantra_claimdrop_std::msg::{
+    CampaignAction, CampaignParams, DistributionType, ForfeitureMode, VestingAnchor,
+};
+mod suite;
+
+fn campaign_params(start_time: u64, distribution_type: Vec<DistributionType>) -> CampaignParams {
+    CampaignParams {
+        name: "Test Airdrop I".to_string(),
+        description: "This is an airdrop".to_string(),
+        ty: "airdrop".to_string(),
+        reward_denom: "uom".to_string(),
+        total_reward: coin(2_000, "uom"),
+        distribution_type,
+        start_time,
+        end_time: start_time + 86_400 * 30,
+        schedules: vec![],
+        require_finalized_allocations: false,
+    }
+}
+
+#[test]
+fn vesting_relative_to_registration() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+    let start_time = suite.get_time().plus_days(1).seconds();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .add_allocations(
+            alice,
+            &vec![(bob.to_string(), Uint128::new(1_000))],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(campaign_params(
+                    start_time,
+                    vec![DistributionType::RelativeLinearVesting {
+                        percentage: Decimal::one(),
+                        anchor: VestingAnchor::Registration,
+                        start_offset: 0,
+                        duration: 86_400 * 10,
+                        cliff_duration: None,
+                    }],
+                )),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .top_up_campaign(
+            alice,
+            &[coin(2_000, "uom")],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .add_day()
+        .add_day()
+        .add_day()
+        .add_day()
+        .add_day()
+        .add_day()
+        // bob was added before the campaign started, so it vests from the start of the campaign
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_balance("uom", bob, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_000_500));
+        })
+        // carol joins the running campaign, and vests from now on
+        .add_allocations(
+            alice,
+            &vec![(carol.to_string(), Uint128::new(1_000))],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_rewards(carol, |result| {
+            let rewards = result.unwrap();
+            assert_eq!(rewards.pending, vec![coin(1_000, "uom")]);
+            assert!(rewards.available_to_claim.is_empty());
+        })
+        .add_day()
+        .add_day()
+        .add_day()
+        .add_day()
+        .add_day()
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .claim(
+            carol,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_balance("uom", bob, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_001_000));
+        })
+        .query_balance("uom", carol, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_000_500));
+        })
+        .query_export_state(None, None, |result| {
+            let entries = result.unwrap().entries;
+            let carol_entry = entries
+                .iter()
+                .find(|entry| entry.address == carol.as_str())
+                .unwrap();
+            assert_eq!(carol_entry.registered_at, Some(start_time + 86_400 * 5));
+        });
+}
+
+#[test]
+fn vesting_relative_to_first_claim() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let start_time = suite.get_time().plus_days(1).seconds();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .add_allocations(
+            alice,
+            &vec![(bob.to_string(), Uint128::new(1_000))],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(campaign_params(
+                    start_time,
+                    vec![DistributionType::RelativeLinearVesting {
+                        percentage: Decimal::one(),
+                        anchor: VestingAnchor::FirstClaim,
+                        start_offset: 86_400,
+                        duration: 86_400 * 4,
+                        cliff_duration: None,
+                    }],
+                )),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .top_up_campaign(
+            alice,
+            &[coin(2_000, "uom")],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .add_day()
+        .add_day()
+        .add_day()
+        // nothing has vested yet, but the first claim starts the vesting clock of bob
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let response = result.unwrap();
+                assert!(response.events.iter().any(|event| event
+                    .attributes
+                    .iter()
+                    .any(|attr| attr.key == "claimed_amount" && attr.value == "0uom")));
+            },
+        )
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::NothingToClaim => {}
+                    _ => panic!("Wrong error type, should return ContractError::NothingToClaim"),
+                }
+            },
+        )
+        .add_day()
+        .add_day()
+        .add_day()
+        // one day of offset, then half of the vesting period
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_balance("uom", bob, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_000_500));
+        })
+        .add_week()
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_balance("uom", bob, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_001_000));
+        })
+        .query_export_state(None, None, |result| {
+            let entries = result.unwrap().entries;
+            assert_eq!(entries[0].first_claimed_at, Some(start_time + 86_400 * 2));
+        });
+}
+
+#[test]
+fn cannot_create_relative_vesting_without_duration() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let start_time = suite.get_time().plus_days(1).seconds();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(campaign_params(
+                    start_time,
+                    vec![DistributionType::RelativeLinearVesting {
+                        percentage: Decimal::one(),
+                        anchor: VestingAnchor::Registration,
+                        start_offset: 0,
+                        duration: 0,
+                        cliff_duration: None,
+                    }],
+                )),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidCampaignParam { param, .. } => {
+                        assert_eq!(param, "duration");
+                    }
+                    _ => panic!(
+                        "Wrong error type, should return ContractError::InvalidCampaignParam"
+                    ),
+                }
+            },
+        );
+}
+
+#[test]
+fn late_allocations_only_share_in_later_forfeitures() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+    let dave = &suite.senders[3].clone();
+    let start_time = suite.get_time().plus_days(1).seconds();
+
+    suite.instantiate_claimdrop_contract(Some(alice.to_string()));
+    let contract = suite.claimdrop_contract_addr.clone();
+
+    suite
+        .add_allocations(
+            alice,
+            &vec![
+                (bob.to_string(), Uint128::new(1_000)),
+                (carol.to_string(), Uint128::new(1_000)),
+            ],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(campaign_params(
+                    start_time,
+                    vec![DistributionType::RelativeLinearVesting {
+                        percentage: Decimal::one(),
+                        anchor: VestingAnchor::Registration,
+                        start_offset: 0,
+                        duration: 86_400 * 10,
+                        cliff_duration: None,
+                    }],
+                )),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .top_up_campaign(
+            alice,
+            &[coin(2_000, "uom")],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::UpdateForfeitureMode {
+                mode: ForfeitureMode::Redistribute,
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .add_day()
+        .add_day()
+        .blacklist_address(
+            alice,
+            carol,
+            true,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        // the allocation of carol goes to bob, the only address left
+        .forfeit_allocations(
+            alice,
+            vec![carol.to_string()],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        // dave joins after the forfeiture, so it doesn't share in it
+        .add_allocations(
+            alice,
+            &vec![(dave.to_string(), Uint128::new(1_000))],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .top_up_campaign(
+            alice,
+            &[coin(1_000, "uom")],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_rewards(dave, |result| {
+            let rewards = result.unwrap();
+            assert!(rewards.redistributed.is_empty());
+            assert_eq!(rewards.pending, vec![coin(1_000, "uom")]);
+        })
+        .query_rewards(bob, |result| {
+            assert_eq!(result.unwrap().pending, vec![coin(2_000, "uom")]);
+        })
+        .add_week()
+        .add_week()
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .claim(
+            dave,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_balance("uom", bob, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_002_000));
+        })
+        .query_balance("uom", dave, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_001_000));
+        })
+        // the claims add up to the funds of the campaign
+        .query_balance("uom", &contract, |balance| {
+            assert_eq!(balance, Uint128::zero());
+        });
+}
@@ -0,0 +1,142 @@
+use crate::suite::TestingSuite;
+use cosmwasm_std::{coin, coins, Decimal, Uint128};
+use cw_multi_test::AppResponse;
+use mantra_claimdrop_std::msg::{CampaignAction, CampaignParams, DistributionType};
+mod suite;
+
+#[test]
+fn rewards_breakdown_per_distribution_slot() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let current_time = &suite.get_time();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .add_allocations(
+            alice,
+            &vec![(bob.to_string(), Uint128::new(1_000))],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(CampaignParams {
+                    name: "Test Airdrop I".to_string(),
+                    description: "This is an airdrop".to_string(),
+                    ty: "airdrop".to_string(),
+                    reward_denom: "uom".to_string(),
+                    total_reward: coin(1_000, "uom"),
+                    distribution_type: vec![
+                        DistributionType::LumpSum {
+                            percentage: Decimal::percent(25),
+                            start_time: current_time.seconds(),
+                        },
+                        DistributionType::LinearVesting {
+                            percentage: Decimal::percent(75),
+                            start_time: current_time.seconds(),
+                            end_time: current_time.plus_days(10).seconds(),
+                            cliff_duration: Some(86_400 * 2),
+                            early_exit_penalty: None,
+                        },
+                    ],
+                    start_time: current_time.seconds(),
+                    end_time: current_time.plus_days(10).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
+                }),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .top_up_campaign(
+            alice,
+            &coins(1_000, "uom"),
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_rewards_breakdown(bob, |result| {
+            let response = result.unwrap();
+            assert_eq!(response.rewards.available_to_claim, coins(250, "uom"));
+            assert_eq!(response.slots.len(), 2);
+
+            let lump_sum = &response.slots[0];
+            assert_eq!(lump_sum.slot, 0);
+            assert_eq!(lump_sum.entitlement, coin(250, "uom"));
+            assert_eq!(lump_sum.vested, coin(250, "uom"));
+            assert_eq!(lump_sum.claimed, coin(0, "uom"));
+            assert_eq!(lump_sum.claimable, coin(250, "uom"));
+            assert_eq!(lump_sum.start_time, Some(current_time.seconds()));
+            assert_eq!(lump_sum.end_time, Some(current_time.seconds()));
+            assert_eq!(lump_sum.cliff_end_time, None);
+
+            let vesting = &response.slots[1];
+            assert_eq!(vesting.slot, 1);
+            assert_eq!(vesting.entitlement, coin(750, "uom"));
+            assert_eq!(vesting.vested, coin(0, "uom"));
+            assert_eq!(vesting.claimed, coin(0, "uom"));
+            assert_eq!(vesting.claimable, coin(0, "uom"));
+            assert_eq!(vesting.start_time, Some(current_time.seconds()));
+            assert_eq!(vesting.end_time, Some(current_time.plus_days(10).seconds()));
+            assert_eq!(
+                vesting.cliff_end_time,
+                Some(current_time.plus_days(2).seconds())
+            );
+        })
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        );
+
+    for _ in 0..5 {
+        suite.add_day();
+    }
+
+    suite
+        .claim(
+            bob,
+            None,
+            Some(Uint128::new(100)),
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_rewards_breakdown(bob, |result| {
+            let response = result.unwrap();
+            assert_eq!(response.rewards.claimed, coins(350, "uom"));
+            assert_eq!(response.rewards.available_to_claim, coins(275, "uom"));
+
+            let lump_sum = &response.slots[0];
+            assert_eq!(lump_sum.claimed, coin(250, "uom"));
+            assert_eq!(lump_sum.claimable, coin(0, "uom"));
+
+            let vesting = &response.slots[1];
+            assert_eq!(vesting.vested, coin(375, "uom"));
+            assert_eq!(vesting.claimed, coin(100, "uom"));
+            assert_eq!(vesting.claimable, coin(275, "uom"));
+        })
+        .manage_campaign(
+            alice,
+            CampaignAction::CloseCampaign {},
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_rewards_breakdown(bob, |result| {
+            let response = result.unwrap();
+            assert!(response.rewards.available_to_claim.is_empty());
+            assert_eq!(response.slots[1].vested, coin(375, "uom"));
+            assert_eq!(response.slots[1].claimable, coin(0, "uom"));
+        });
+}
@@ -0,0 +1,294 @@
+use crate::suite::TestingSuite;
+use cosmwasm_std::{coin, Decimal, Uint128};
+use cw_multi_test::AppResponse;
+use mantra_claimdrop_std::error::ContractError;
+use mantra_claimdrop_std::msg::{
+    AllocationRow, CampaignAction, CampaignParams, DistributionType, Schedule,
+};
+mod suite;
+
+fn campaign_params(start_time: u64, schedules: Vec<Schedule>) -> CampaignParams {
+    CampaignParams {
+        name: "Test Airdrop I".to_string(),
+        description: "This is an airdrop".to_string(),
+        ty: "airdrop".to_string(),
+        reward_denom: "uom".to_string(),
+        total_reward: coin(3_000, "uom"),
+        distribution_type: vec![DistributionType::LumpSum {
+            percentage: Decimal::one(),
+            start_time,
+        }],
+        start_time,
+        end_time: start_time + 86_400 * 10,
+        schedules,
+        require_finalized_allocations: false,
+    }
+}
+
+fn schedules(start_time: u64) -> Vec<Schedule> {
+    vec![
+        Schedule {
+            id: "team".to_string(),
+            distribution_type: vec![DistributionType::LinearVesting {
+                percentage: Decimal::one(),
+                start_time,
+                end_time: start_time + 86_400 * 10,
+                cliff_duration: Some(86_400 * 5),
+                early_exit_penalty: None,
+            }],
+        },
+        Schedule {
+            id: "community".to_string(),
+            distribution_type: vec![
+                DistributionType::LumpSum {
+                    percentage: Decimal::percent(25),
+                    start_time,
+                },
+                DistributionType::LinearVesting {
+                    percentage: Decimal::percent(75),
+                    start_time,
+                    end_time: start_time + 86_400 * 10,
+                    cliff_duration: None,
+                    early_exit_penalty: None,
+                },
+            ],
+        },
+    ]
+}
+
+#[test]
+fn claims_follow_the_schedule_of_each_address() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+    let dave = &suite.senders[3].clone();
+    let start_time = suite.get_time().plus_days(1).seconds();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .add_allocations(
+            alice,
+            &vec![(bob.to_string(), Uint128::new(1_000))],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(campaign_params(start_time, schedules(start_time))),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .add_allocation_rows(
+            alice,
+            vec![
+                AllocationRow::Scheduled(
+                    carol.to_string(),
+                    Uint128::new(1_000),
+                    "team".to_string(),
+                ),
+                AllocationRow::Scheduled(
+                    dave.to_string(),
+                    Uint128::new(1_000),
+                    "community".to_string(),
+                ),
+            ],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .top_up_campaign(
+            alice,
+            &[coin(3_000, "uom")],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .add_day()
+        .add_day()
+        .add_day()
+        .add_day()
+        // 3 days in, bob gets the default lump sum
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_balance("uom", bob, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_001_000));
+        })
+        // carol is still in the cliff of the team schedule
+        .claim(
+            carol,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::NothingToClaim => {}
+                    _ => panic!("Wrong error type, should return ContractError::NothingToClaim"),
+                }
+            },
+        )
+        .query_rewards(carol, |result| {
+            let rewards = result.unwrap();
+            assert_eq!(rewards.pending, vec![coin(1_000, "uom")]);
+            assert!(rewards.available_to_claim.is_empty());
+        })
+        // dave gets the lump sum plus 30% of the vesting slot of the community schedule
+        .query_rewards(dave, |result| {
+            let rewards = result.unwrap();
+            assert_eq!(rewards.available_to_claim, vec![coin(475, "uom")]);
+        })
+        .claim(
+            dave,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_balance("uom", dave, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_000_475));
+        })
+        .add_week()
+        .add_week()
+        .claim(
+            carol,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .claim(
+            dave,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_balance("uom", carol, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_001_000));
+        })
+        .query_balance("uom", dave, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_001_000));
+        })
+        .query_campaign(|result| {
+            let campaign = result.unwrap();
+            assert_eq!(campaign.claimed, coin(3_000, "uom"));
+        });
+}
+
+#[test]
+fn cannot_use_undefined_schedules() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+    let start_time = suite.get_time().plus_days(1).seconds();
+
+    let mut duplicated = schedules(start_time);
+    duplicated[1].id = "team".to_string();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        // the schedules live on the campaign, so they can't be used before it's created
+        .add_allocation_rows(
+            alice,
+            vec![AllocationRow::Scheduled(
+                bob.to_string(),
+                Uint128::new(1_000),
+                "team".to_string(),
+            )],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::CampaignError { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::CampaignError"),
+                }
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(campaign_params(start_time, duplicated)),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidCampaignParam { param, .. } => {
+                        assert_eq!(param, "schedules");
+                    }
+                    _ => panic!(
+                        "Wrong error type, should return ContractError::InvalidCampaignParam"
+                    ),
+                }
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(campaign_params(start_time, schedules(start_time))),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .add_allocation_rows(
+            alice,
+            vec![AllocationRow::Scheduled(
+                bob.to_string(),
+                Uint128::new(1_000),
+                "advisors".to_string(),
+            )],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidInput { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::InvalidInput"),
+                }
+            },
+        )
+        .add_allocation_rows(
+            alice,
+            vec![
+                AllocationRow::Default(bob.to_string(), Uint128::new(1_000)),
+                AllocationRow::Scheduled(
+                    carol.to_string(),
+                    Uint128::new(1_000),
+                    "team".to_string(),
+                ),
+            ],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_export_state(None, None, |result| {
+            let mut entries = result.unwrap().entries;
+            entries.sort_by(|a, b| a.address.cmp(&b.address));
+            let schedule_of = |address: &str| {
+                entries
+                    .iter()
+                    .find(|entry| entry.address == address)
+                    .unwrap()
+                    .schedule
+                    .clone()
+            };
+            assert_eq!(schedule_of(bob.as_str()), None);
+            assert_eq!(schedule_of(carol.as_str()), Some("team".to_string()));
+        });
+}
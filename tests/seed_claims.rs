@@ -0,0 +1,259 @@
+use crate::suite::TestingSuite;
+use cosmwasm_std::{coin, coins, Decimal, Uint128};
+use cw_multi_test::AppResponse;
+use mantra_claimdrop_std::error::ContractError;
+use mantra_claimdrop_std::msg::{CampaignAction, CampaignParams, DistributionType};
+mod suite;
+
+#[test]
+fn seeded_claims_are_subtracted_from_claimable_amount() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+    let start_time = suite.get_time().plus_days(1);
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .add_allocations(
+            alice,
+            &vec![
+                (bob.to_string(), Uint128::new(1_000)),
+                (carol.to_string(), Uint128::new(1_000)),
+            ],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .seed_claims(
+            alice,
+            vec![(bob.to_string(), vec![(0, Uint128::new(400))])],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::CampaignError { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::CampaignError"),
+                }
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(CampaignParams {
+                    name: "Test Airdrop I".to_string(),
+                    description: "This is an airdrop".to_string(),
+                    ty: "airdrop".to_string(),
+                    reward_denom: "uom".to_string(),
+                    total_reward: coin(2_000, "uom"),
+                    distribution_type: vec![
+                        DistributionType::LumpSum {
+                            percentage: Decimal::percent(50),
+                            start_time: start_time.seconds(),
+                        },
+                        DistributionType::LinearVesting {
+                            percentage: Decimal::percent(50),
+                            start_time: start_time.seconds(),
+                            end_time: start_time.plus_days(10).seconds(),
+                            cliff_duration: None,
+                            early_exit_penalty: None,
+                        },
+                    ],
+                    start_time: start_time.seconds(),
+                    end_time: start_time.plus_days(10).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
+                }),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .seed_claims(
+            carol,
+            vec![(bob.to_string(), vec![(0, Uint128::new(400))])],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::OwnershipError(_) => {}
+                    _ => panic!("Wrong error type, should return ContractError::OwnershipError"),
+                }
+            },
+        )
+        .seed_claims(
+            alice,
+            vec![(bob.to_string(), vec![(2, Uint128::new(400))])],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidInput { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::InvalidInput"),
+                }
+            },
+        )
+        .seed_claims(
+            alice,
+            vec![(alice.to_string(), vec![(0, Uint128::new(400))])],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::NoAllocationFound { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::NoAllocationFound"),
+                }
+            },
+        )
+        .seed_claims(
+            alice,
+            vec![(
+                bob.to_string(),
+                vec![(0, Uint128::new(600)), (1, Uint128::new(401))],
+            )],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::ExceededMaxClaimAmount => {}
+                    _ => panic!(
+                        "Wrong error type, should return ContractError::ExceededMaxClaimAmount"
+                    ),
+                }
+            },
+        )
+        // each slot can't exceed the entitlement of its distribution, even if the total doesn't
+        .seed_claims(
+            alice,
+            vec![(
+                bob.to_string(),
+                vec![(0, Uint128::new(501)), (1, Uint128::new(100))],
+            )],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::ExceededMaxClaimAmount => {}
+                    _ => panic!(
+                        "Wrong error type, should return ContractError::ExceededMaxClaimAmount"
+                    ),
+                }
+            },
+        )
+        .seed_claims(
+            alice,
+            vec![
+                (
+                    bob.to_string(),
+                    vec![(0, Uint128::new(500)), (1, Uint128::new(100))],
+                ),
+                (carol.to_string(), vec![(1, Uint128::new(200))]),
+            ],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_campaign(|result| {
+            assert_eq!(result.unwrap().claimed, coin(800, "uom"));
+        })
+        // seeding a slot again replaces the previous amount
+        .seed_claims(
+            alice,
+            vec![(bob.to_string(), vec![(0, Uint128::new(400))])],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_campaign(|result| {
+            assert_eq!(result.unwrap().claimed, coin(700, "uom"));
+        })
+        // the total seeded claims can't exceed the allocation
+        .seed_claims(
+            alice,
+            vec![(
+                carol.to_string(),
+                vec![(0, Uint128::new(500)), (1, Uint128::new(501))],
+            )],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::ExceededMaxClaimAmount => {}
+                    _ => panic!(
+                        "Wrong error type, should return ContractError::ExceededMaxClaimAmount"
+                    ),
+                }
+            },
+        )
+        .query_claimed(Some(bob), None, None, |result| {
+            let claimed = result.unwrap();
+            assert_eq!(claimed.claimed, vec![(bob.to_string(), coin(500, "uom"))]);
+            assert_eq!(
+                claimed.claimed_by_slot,
+                vec![(
+                    bob.to_string(),
+                    vec![(0, coin(400, "uom")), (1, coin(100, "uom"))]
+                )]
+            );
+        })
+        .top_up_campaign(
+            alice,
+            &coins(1_300, "uom"),
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .add_day()
+        .seed_claims(
+            alice,
+            vec![(carol.to_string(), vec![(0, Uint128::new(100))])],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::CampaignError { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::CampaignError"),
+                }
+            },
+        )
+        // the lump sum is 500, of which 400 were distributed already
+        .query_rewards(bob, |result| {
+            let rewards = result.unwrap();
+            assert_eq!(rewards.claimed, coins(500, "uom"));
+            assert_eq!(rewards.available_to_claim, coins(100, "uom"));
+        })
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .add_week()
+        .add_week()
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .claim(
+            carol,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_rewards(bob, |result| {
+            let rewards = result.unwrap();
+            assert_eq!(rewards.claimed, coins(1_000, "uom"));
+            assert!(rewards.pending.is_empty());
+        })
+        .query_balance("uom", bob, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_000_000 + 500));
+        })
+        .query_balance("uom", carol, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_000_000 + 800));
+        })
+        .query_campaign(|result| {
+            assert_eq!(result.unwrap().claimed, coin(2_000, "uom"));
+        });
+}
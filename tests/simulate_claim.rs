@@ -0,0 +1,250 @@
+use crate::suite::TestingSuite;
+use cosmwasm_std::{coin, coins, Decimal, Uint128};
+use cw_multi_test::AppResponse;
+use mantra_claimdrop_std::msg::{
+    CampaignAction, CampaignParams, ClaimFailureReason, DistributionType, SimulateClaimResponse,
+};
+mod suite;
+
+fn failure(reason: ClaimFailureReason) -> SimulateClaimResponse {
+    SimulateClaimResponse::Failure { reason }
+}
+
+#[test]
+fn simulate_claim_reports_failure_reasons() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+    let dan = &suite.senders[3].clone();
+    let current_time = &suite.get_time();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .query_simulate_claim(bob, None, None, |result| {
+            assert_eq!(
+                result.unwrap(),
+                failure(ClaimFailureReason::NoActiveCampaign)
+            );
+        })
+        .add_allocations(
+            alice,
+            &vec![
+                (bob.to_string(), Uint128::new(1_000)),
+                (carol.to_string(), Uint128::new(1_000)),
+            ],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(CampaignParams {
+                    name: "Test Airdrop I".to_string(),
+                    description: "This is an airdrop".to_string(),
+                    ty: "airdrop".to_string(),
+                    reward_denom: "uom".to_string(),
+                    total_reward: coin(2_000, "uom"),
+                    distribution_type: vec![
+                        DistributionType::LumpSum {
+                            percentage: Decimal::percent(25),
+                            start_time: current_time.plus_days(1).seconds(),
+                        },
+                        DistributionType::LinearVesting {
+                            percentage: Decimal::percent(75),
+                            start_time: current_time.plus_days(1).seconds(),
+                            end_time: current_time.plus_days(11).seconds(),
+                            cliff_duration: None,
+                            early_exit_penalty: None,
+                        },
+                    ],
+                    start_time: current_time.plus_days(1).seconds(),
+                    end_time: current_time.plus_days(11).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
+                }),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_simulate_claim(bob, None, None, |result| {
+            assert_eq!(result.unwrap(), failure(ClaimFailureReason::NotStarted));
+        });
+
+    suite.add_day();
+
+    suite
+        .query_simulate_claim(bob, None, None, |result| {
+            assert_eq!(
+                result.unwrap(),
+                failure(ClaimFailureReason::InsufficientFunds {
+                    available: coin(0, "uom")
+                })
+            );
+        })
+        .top_up_campaign(
+            alice,
+            &coins(2_000, "uom"),
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_simulate_claim(bob, Some(carol), None, |result| {
+            assert_eq!(result.unwrap(), failure(ClaimFailureReason::Unauthorized));
+        })
+        .query_simulate_claim(dan, None, None, |result| {
+            assert_eq!(result.unwrap(), failure(ClaimFailureReason::NoAllocation));
+        })
+        .query_simulate_claim(bob, None, Some(Uint128::zero()), |result| {
+            assert_eq!(
+                result.unwrap(),
+                failure(ClaimFailureReason::InvalidAmount {
+                    reason: "amount must be greater than zero".to_string()
+                })
+            );
+        })
+        .query_simulate_claim(bob, None, Some(Uint128::new(251)), |result| {
+            assert_eq!(
+                result.unwrap(),
+                failure(ClaimFailureReason::InvalidAmount {
+                    reason: "requested amount 251 exceeds available claimable amount 250"
+                        .to_string()
+                })
+            );
+        })
+        .blacklist_address(
+            alice,
+            carol,
+            true,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_simulate_claim(alice, Some(carol), None, |result| {
+            assert_eq!(result.unwrap(), failure(ClaimFailureReason::Blacklisted));
+        })
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_simulate_claim(bob, None, None, |result| {
+            assert_eq!(result.unwrap(), failure(ClaimFailureReason::NothingToClaim));
+        })
+        .manage_campaign(
+            alice,
+            CampaignAction::CloseCampaign {},
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_simulate_claim(bob, None, None, |result| {
+            assert_eq!(result.unwrap(), failure(ClaimFailureReason::Closed));
+        });
+}
+
+#[test]
+fn simulate_claim_matches_claim() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let current_time = &suite.get_time();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .add_allocations(
+            alice,
+            &vec![(bob.to_string(), Uint128::new(1_000))],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(CampaignParams {
+                    name: "Test Airdrop I".to_string(),
+                    description: "This is an airdrop".to_string(),
+                    ty: "airdrop".to_string(),
+                    reward_denom: "uom".to_string(),
+                    total_reward: coin(1_000, "uom"),
+                    distribution_type: vec![
+                        DistributionType::LumpSum {
+                            percentage: Decimal::percent(25),
+                            start_time: current_time.seconds(),
+                        },
+                        DistributionType::LinearVesting {
+                            percentage: Decimal::percent(75),
+                            start_time: current_time.seconds(),
+                            end_time: current_time.plus_days(10).seconds(),
+                            cliff_duration: None,
+                            early_exit_penalty: None,
+                        },
+                    ],
+                    start_time: current_time.seconds(),
+                    end_time: current_time.plus_days(10).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
+                }),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .top_up_campaign(
+            alice,
+            &coins(1_000, "uom"),
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        );
+
+    for _ in 0..5 {
+        suite.add_day();
+    }
+
+    suite
+        .query_simulate_claim(alice, Some(bob), Some(Uint128::new(300)), |result| {
+            assert_eq!(
+                result.unwrap(),
+                SimulateClaimResponse::Success {
+                    amount: coin(300, "uom"),
+                    slots: vec![(0, Uint128::new(250)), (1, Uint128::new(50))],
+                }
+            );
+        })
+        .query_simulate_claim(bob, None, None, |result| {
+            assert_eq!(
+                result.unwrap(),
+                SimulateClaimResponse::Success {
+                    amount: coin(625, "uom"),
+                    slots: vec![(0, Uint128::new(250)), (1, Uint128::new(375))],
+                }
+            );
+        })
+        .claim(
+            alice,
+            Some(bob.to_string()),
+            Some(Uint128::new(300)),
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_claim_history(bob, None, None, |result| {
+            let history = result.unwrap().history;
+            assert_eq!(
+                history[0].slots,
+                vec![(0, Uint128::new(250)), (1, Uint128::new(50))]
+            );
+        });
+}
@@ -0,0 +1,446 @@
+use std::cell::RefCell;
+
+use crate::suite::TestingSuite;
+use cosmwasm_std::{coin, coins, Decimal, Timestamp, Uint128};
+use cw_multi_test::AppResponse;
+use mantra_claimdrop_std::error::ContractError;
+use mantra_claimdrop_std::msg::{
+    CampaignAction, CampaignParams, DistributionType, SlotClaimEntry, StateEntry,
+};
+mod suite;
+
+fn create_campaign(start_time: Timestamp, total_reward: u128) -> CampaignAction {
+    CampaignAction::CreateCampaign {
+        params: Box::new(CampaignParams {
+            name: "Test Airdrop I".to_string(),
+            description: "This is an airdrop".to_string(),
+            ty: "airdrop".to_string(),
+            reward_denom: "uom".to_string(),
+            total_reward: coin(total_reward, "uom"),
+            distribution_type: vec![DistributionType::LinearVesting {
+                percentage: Decimal::one(),
+                start_time: start_time.seconds(),
+                end_time: start_time.plus_days(10).seconds(),
+                cliff_duration: None,
+                early_exit_penalty: None,
+            }],
+            start_time: start_time.seconds(),
+            end_time: start_time.plus_days(10).seconds(),
+            schedules: vec![],
+            require_finalized_allocations: false,
+        }),
+    }
+}
+
+fn entry(address: &str, allocation: u128, claims: Vec<(u64, u128)>) -> StateEntry {
+    StateEntry {
+        address: address.to_string(),
+        allocation: Uint128::new(allocation),
+        claims: claims
+            .into_iter()
+            .map(|(slot, amount)| SlotClaimEntry {
+                slot,
+                amount: Uint128::new(amount),
+                timestamp: 1_571_797_419,
+            })
+            .collect(),
+        blacklist: None,
+        schedule: None,
+        registered_at: None,
+        first_claimed_at: None,
+    }
+}
+
+#[test]
+fn state_can_be_handed_over_to_a_new_contract() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+    let dan = &suite.senders[3].clone();
+    let current_time = &suite.get_time();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .add_allocations(
+            alice,
+            &vec![
+                (bob.to_string(), Uint128::new(1_000)),
+                (carol.to_string(), Uint128::new(1_000)),
+                (dan.to_string(), Uint128::new(500)),
+            ],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .manage_campaign(
+            alice,
+            create_campaign(*current_time, 2_500),
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .top_up_campaign(
+            alice,
+            &coins(2_500, "uom"),
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .add_day()
+        .add_day()
+        .add_day()
+        .add_day()
+        .add_day()
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .claim(
+            carol,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .blacklist_address(
+            alice,
+            carol,
+            true,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        );
+
+    // page through the state of the campaign
+    let exported = RefCell::new(vec![]);
+    let mut addresses = [bob.clone(), carol.clone(), dan.clone()];
+    addresses.sort();
+
+    suite
+        .query_export_state(None, Some(2), |result| {
+            exported.borrow_mut().extend(result.unwrap().entries);
+        })
+        .query_export_state(Some(&addresses[1]), Some(2), |result| {
+            exported.borrow_mut().extend(result.unwrap().entries);
+        })
+        .query_export_state(Some(&addresses[2]), Some(2), |result| {
+            assert!(result.unwrap().entries.is_empty());
+        });
+
+    let exported = exported.into_inner();
+    assert_eq!(
+        exported
+            .iter()
+            .map(|entry| entry.address.clone())
+            .collect::<Vec<String>>(),
+        addresses
+            .iter()
+            .map(|address| address.to_string())
+            .collect::<Vec<String>>()
+    );
+
+    let bob_entry = exported
+        .iter()
+        .find(|entry| entry.address == bob.as_str())
+        .unwrap();
+    assert_eq!(bob_entry.allocation, Uint128::new(1_000));
+    assert_eq!(bob_entry.claims.len(), 1);
+    assert_eq!(bob_entry.claims[0].slot, 0);
+    assert_eq!(bob_entry.claims[0].amount, Uint128::new(500));
+    assert_eq!(bob_entry.blacklist, None);
+
+    let carol_entry = exported
+        .iter()
+        .find(|entry| entry.address == carol.as_str())
+        .unwrap();
+    assert_eq!(carol_entry.claims[0].amount, Uint128::new(500));
+    assert_eq!(
+        carol_entry.blacklist.as_ref().unwrap().blacklisted_by,
+        alice.to_string()
+    );
+
+    let dan_entry = exported
+        .iter()
+        .find(|entry| entry.address == dan.as_str())
+        .unwrap();
+    assert_eq!(dan_entry.allocation, Uint128::new(500));
+    assert!(dan_entry.claims.is_empty());
+
+    // deploy a new contract with a corrected campaign, and restore the state on it
+    let new_start_time = suite.get_time().plus_days(1);
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .import_state(
+            alice,
+            &exported,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::CampaignError { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::CampaignError"),
+                }
+            },
+        )
+        .manage_campaign(
+            alice,
+            create_campaign(new_start_time, 2_500),
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .import_state(
+            bob,
+            &exported,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::OwnershipError(_) => {}
+                    _ => panic!("Wrong error type, should return ContractError::OwnershipError"),
+                }
+            },
+        )
+        .import_state(
+            alice,
+            &exported,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let response = result.unwrap();
+                assert!(response.events.iter().any(|event| event
+                    .attributes
+                    .iter()
+                    .any(|attribute| attribute.key == "claimed" && attribute.value == "1000uom")));
+            },
+        )
+        .import_state(
+            alice,
+            &exported[..1],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::AllocationAlreadyExists { .. } => {}
+                    _ => panic!(
+                        "Wrong error type, should return ContractError::AllocationAlreadyExists"
+                    ),
+                }
+            },
+        )
+        .top_up_campaign(
+            alice,
+            &coins(1_500, "uom"),
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_campaign(|result| {
+            assert_eq!(result.unwrap().claimed, coin(1_000, "uom"));
+        })
+        .query_is_blacklisted(carol, |result| {
+            assert!(result.unwrap().is_blacklisted);
+        })
+        .query_export_state(None, None, |result| {
+            assert_eq!(result.unwrap().entries, exported);
+        });
+
+    // users keep their progress on the new campaign
+    suite
+        .add_day()
+        .add_day()
+        .add_day()
+        .add_day()
+        .add_day()
+        .add_day()
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::NothingToClaim => {}
+                    _ => panic!("Wrong error type, should return ContractError::NothingToClaim"),
+                }
+            },
+        )
+        .add_week()
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .claim(
+            dan,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_rewards(bob, |result| {
+            let rewards = result.unwrap();
+            assert_eq!(rewards.claimed, coins(1_000, "uom"));
+            assert!(rewards.pending.is_empty());
+        })
+        .query_rewards(dan, |result| {
+            assert_eq!(result.unwrap().claimed, coins(500, "uom"));
+        })
+        .query_campaign(|result| {
+            // carol is still blacklisted
+            assert_eq!(result.unwrap().claimed, coin(2_000, "uom"));
+        });
+}
+
+#[test]
+fn cannot_import_invalid_state() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let start_time = suite.get_time().plus_days(1);
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .manage_campaign(
+            alice,
+            create_campaign(start_time, 1_000),
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .import_state(
+            alice,
+            &[entry(bob.as_str(), 100, vec![(0, 101)])],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::ExceededMaxClaimAmount => {}
+                    _ => panic!(
+                        "Wrong error type, should return ContractError::ExceededMaxClaimAmount"
+                    ),
+                }
+            },
+        )
+        .import_state(
+            alice,
+            &[entry(bob.as_str(), 100, vec![(1, 50)])],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidInput { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::InvalidInput"),
+                }
+            },
+        )
+        .import_state(
+            alice,
+            &[entry(bob.as_str(), 100, vec![(0, 10), (0, 10)])],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidInput { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::InvalidInput"),
+                }
+            },
+        )
+        .add_day()
+        .import_state(
+            alice,
+            &[entry(bob.as_str(), 100, vec![])],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::CampaignError { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::CampaignError"),
+                }
+            },
+        )
+        .query_export_state(None, None, |result| {
+            assert!(result.unwrap().entries.is_empty());
+        });
+}
+
+#[test]
+fn cannot_import_more_than_the_slot_entitlement() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let carol = &suite.senders[2].clone();
+    let start_time = suite.get_time().plus_days(1);
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(CampaignParams {
+                    name: "Test Airdrop I".to_string(),
+                    description: "This is an airdrop".to_string(),
+                    ty: "airdrop".to_string(),
+                    reward_denom: "uom".to_string(),
+                    total_reward: coin(2_000, "uom"),
+                    distribution_type: vec![
+                        DistributionType::LumpSum {
+                            percentage: Decimal::percent(30),
+                            start_time: start_time.seconds(),
+                        },
+                        DistributionType::LinearVesting {
+                            percentage: Decimal::percent(70),
+                            start_time: start_time.seconds(),
+                            end_time: start_time.plus_days(10).seconds(),
+                            cliff_duration: None,
+                            early_exit_penalty: None,
+                        },
+                    ],
+                    start_time: start_time.seconds(),
+                    end_time: start_time.plus_days(10).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
+                }),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        // the lump sum entitles bob to 30, even though the total is within the allocation
+        .import_state(
+            alice,
+            &[entry(bob.as_str(), 100, vec![(0, 31)])],
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::ExceededMaxClaimAmount => {}
+                    _ => panic!(
+                        "Wrong error type, should return ContractError::ExceededMaxClaimAmount"
+                    ),
+                }
+            },
+        )
+        // the dust left by rounding the entitlements down can be held by any slot
+        .import_state(
+            alice,
+            &[
+                entry(bob.as_str(), 100, vec![(0, 30), (1, 70)]),
+                entry(carol.as_str(), 15, vec![(0, 5)]),
+            ],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_campaign(|result| {
+            assert_eq!(result.unwrap().claimed, coin(105, "uom"));
+        });
+}
@@ -0,0 +1,359 @@
+use crate::suite::TestingSuite;
+use cosmwasm_std::{coin, coins, Addr, Decimal, Uint128};
+use cw_multi_test::AppResponse;
+use mantra_claimdrop_std::error::ContractError;
+use mantra_claimdrop_std::msg::{
+    AuditAction, BlacklistReason, CampaignAction, CampaignParams, ClaimFailureReason,
+    DistributionType, SimulateClaimResponse, SudoMsg,
+};
+mod suite;
+
+/// Sets up a started campaign where bob and carol can claim 100 uom each
+fn setup() -> (TestingSuite, Addr, Addr, Addr, Addr) {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = suite.senders[0].clone();
+    let bob = suite.senders[1].clone();
+    let carol = suite.senders[2].clone();
+    let dan = suite.senders[3].clone();
+    let current_time = suite.get_time();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .add_allocations(
+            &alice,
+            &vec![
+                (bob.to_string(), Uint128::new(100)),
+                (carol.to_string(), Uint128::new(100)),
+            ],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .manage_campaign(
+            &alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(CampaignParams {
+                    name: "Test Airdrop I".to_string(),
+                    description: "This is an airdrop".to_string(),
+                    ty: "airdrop".to_string(),
+                    reward_denom: "uom".to_string(),
+                    total_reward: coin(200, "uom"),
+                    distribution_type: vec![DistributionType::LumpSum {
+                        percentage: Decimal::one(),
+                        start_time: current_time.seconds(),
+                    }],
+                    start_time: current_time.seconds(),
+                    end_time: current_time.plus_days(10).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
+                }),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .top_up_campaign(
+            &alice,
+            &coins(200, "uom"),
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        );
+
+    (suite, alice, bob, carol, dan)
+}
+
+#[test]
+fn governance_can_force_close_campaign() {
+    let (mut suite, alice, bob, _carol, dan) = setup();
+
+    suite
+        .claim(
+            &bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .sudo(
+            SudoMsg::ForceCloseCampaign {
+                refund_recipient: "invalid".to_string(),
+            },
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap_err();
+            },
+        )
+        .sudo(
+            SudoMsg::ForceCloseCampaign {
+                refund_recipient: dan.to_string(),
+            },
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_balance("uom", &dan, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_000_000 + 100));
+        })
+        .query_balance("uom", &alice, |balance| {
+            assert_eq!(balance, Uint128::new(1_000_000_000 - 200));
+        })
+        .query_campaign(|result| {
+            assert!(result.unwrap().closed.is_some());
+        })
+        .query_audit_log(Some("close_campaign"), None, None, None, |result| {
+            let entries = result.unwrap().entries;
+            assert_eq!(entries.len(), 1);
+            assert_eq!(entries[0].sender, "governance");
+        })
+        .sudo(
+            SudoMsg::ForceCloseCampaign {
+                refund_recipient: dan.to_string(),
+            },
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::CampaignError { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::CampaignError"),
+                }
+            },
+        );
+}
+
+#[test]
+fn governance_can_reset_owner() {
+    let (mut suite, alice, bob, carol, dan) = setup();
+
+    suite
+        .update_ownership(
+            &alice,
+            cw_ownable::Action::TransferOwnership {
+                new_owner: carol.to_string(),
+                expiry: None,
+            },
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .sudo(
+            SudoMsg::ResetOwner {
+                owner: dan.to_string(),
+            },
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        ._query_ownership(|result| {
+            let ownership = result.unwrap();
+            assert_eq!(ownership.owner, Some(dan.to_string()));
+            assert_eq!(ownership.pending_owner, None);
+        })
+        // the pending transfer was cancelled
+        .update_ownership(
+            &carol,
+            cw_ownable::Action::AcceptOwnership,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap_err();
+            },
+        )
+        // the previous owner can't manage the contract anymore
+        .blacklist_address(
+            &alice,
+            &bob,
+            true,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::OwnershipError(_) => {}
+                    _ => panic!("Wrong error type, should return ContractError::OwnershipError"),
+                }
+            },
+        )
+        .blacklist_address(
+            &dan,
+            &bob,
+            true,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_audit_log(Some("reset_owner"), None, None, None, |result| {
+            let entries = result.unwrap().entries;
+            assert_eq!(entries.len(), 1);
+            assert_eq!(entries[0].sender, "governance");
+            assert_eq!(entries[0].action, AuditAction::ResetOwner);
+            assert_eq!(entries[0].subject, Some(dan.to_string()));
+        });
+}
+
+#[test]
+fn governance_can_pause_and_resume_contract() {
+    let (mut suite, alice, bob, carol, _dan) = setup();
+
+    suite
+        .sudo(
+            SudoMsg::Resume {},
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::ContractNotPaused => {}
+                    _ => panic!("Wrong error type, should return ContractError::ContractNotPaused"),
+                }
+            },
+        )
+        .sudo(
+            SudoMsg::Pause {},
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_is_paused(|result| {
+            assert!(result.unwrap().is_paused);
+        })
+        .sudo(
+            SudoMsg::Pause {},
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::ContractPaused => {}
+                    _ => panic!("Wrong error type, should return ContractError::ContractPaused"),
+                }
+            },
+        )
+        .claim(
+            &bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::ContractPaused => {}
+                    _ => panic!("Wrong error type, should return ContractError::ContractPaused"),
+                }
+            },
+        )
+        .blacklist_address(
+            &alice,
+            &carol,
+            true,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::ContractPaused => {}
+                    _ => panic!("Wrong error type, should return ContractError::ContractPaused"),
+                }
+            },
+        )
+        .query_simulate_claim(&bob, None, None, |result| {
+            assert_eq!(
+                result.unwrap(),
+                SimulateClaimResponse::Failure {
+                    reason: ClaimFailureReason::Paused,
+                }
+            );
+        })
+        .sudo(
+            SudoMsg::Resume {},
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_is_paused(|result| {
+            assert!(!result.unwrap().is_paused);
+        })
+        .claim(
+            &bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_audit_log(None, None, None, None, |result| {
+            let entries = result.unwrap().entries;
+            let actions = entries
+                .iter()
+                .filter(|entry| entry.sender == "governance")
+                .map(|entry| entry.action.clone())
+                .collect::<Vec<AuditAction>>();
+            assert_eq!(actions, vec![AuditAction::Pause, AuditAction::Resume]);
+        });
+}
+
+#[test]
+fn governance_can_blacklist_addresses() {
+    let (mut suite, _alice, bob, carol, _dan) = setup();
+
+    suite
+        .sudo(
+            SudoMsg::Pause {},
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        // blacklisting works while the contract is paused
+        .sudo(
+            SudoMsg::BlacklistAddresses {
+                addresses: vec![bob.to_string(), carol.to_string()],
+                blacklist: true,
+                reason: Some(BlacklistReason::Compromised),
+                note: Some("owner key compromised".to_string()),
+                expiration: None,
+                freeze_vesting: None,
+            },
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_blacklist(None, None, |result| {
+            let entries = result.unwrap().entries;
+            assert_eq!(entries.len(), 2);
+            for (_, entry) in entries {
+                assert_eq!(entry.reason, BlacklistReason::Compromised);
+                assert_eq!(entry.blacklisted_by, "governance");
+            }
+        })
+        .sudo(
+            SudoMsg::BlacklistAddresses {
+                addresses: vec![carol.to_string()],
+                blacklist: false,
+                reason: None,
+                note: None,
+                expiration: None,
+                freeze_vesting: None,
+            },
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .sudo(
+            SudoMsg::Resume {},
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .claim(
+            &bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::AddressBlacklisted => {}
+                    _ => {
+                        panic!("Wrong error type, should return ContractError::AddressBlacklisted")
+                    }
+                }
+            },
+        )
+        .claim(
+            &carol,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        );
+}
@@ -1,12 +1,20 @@
-use cosmwasm_std::{coin, Addr, Coin, Empty, StdResult, Timestamp, Uint128};
+#![allow(dead_code)]
+
+use cosmwasm_std::{
+    coin, Addr, Binary, Coin, Decimal, Empty, HexBinary, StdResult, Timestamp, Uint128,
+};
 use cw_multi_test::{
     App, AppBuilder, AppResponse, BankKeeper, Contract, ContractWrapper, Executor, MockApiBech32,
     WasmKeeper,
 };
+use cw_utils::Expiration;
 use mantra_claimdrop_std::msg::{
-    AllocationsResponse, AuthorizedResponse, AuthorizedWalletsResponse, BlacklistResponse,
-    CampaignAction, CampaignResponse, ClaimedResponse, ExecuteMsg, InstantiateMsg, QueryMsg,
-    RewardsResponse,
+    AllocationChecksumResponse, AllocationRow, AllocationsResponse, AuditLogResponse,
+    AuthorizedResponse, AuthorizedWalletsResponse, AutoClaimResponse, BlacklistReason,
+    BlacklistResponse, BlacklistedAddressesResponse, CampaignAction, CampaignResponse,
+    ClaimApprovalsResponse, ClaimHistoryResponse, ClaimedResponse, ExecuteMsg, ExportStateResponse,
+    InstantiateMsg, PausedResponse, QueryMsg, RewardsAtResponse, RewardsBreakdownResponse,
+    RewardsResponse, SimulateClaimResponse, StateEntry, SudoMsg, VestingScheduleResponse,
 };
 
 type MantraApp = App<BankKeeper, MockApiBech32>;
@@ -17,7 +25,70 @@ pub fn claimdrop_contract() -> Box<dyn Contract<Empty>> {
         claimdrop_contract::contract::instantiate,
         claimdrop_contract::contract::query,
     )
-    .with_migrate(claimdrop_contract::contract::migrate);
+    .with_migrate(claimdrop_contract::contract::migrate)
+    .with_sudo(claimdrop_contract::contract::sudo);
+
+    Box::new(contract)
+}
+
+/// A compliance registry keeping a list of allowed and denied addresses, for testing purposes.
+mod compliance_registry {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{
+        to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+    };
+    use cw_storage_plus::Map;
+    use mantra_claimdrop_std::msg::{ComplianceQueryMsg, ComplianceStatus, IsAllowedResponse};
+
+    const STATUSES: Map<&str, ComplianceStatus> = Map::new("statuses");
+
+    #[cw_serde]
+    pub struct InstantiateMsg {
+        pub allowed: Vec<String>,
+        pub denied: Vec<String>,
+    }
+
+    pub fn instantiate(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: InstantiateMsg,
+    ) -> StdResult<Response> {
+        for address in msg.allowed.iter() {
+            STATUSES.save(deps.storage, address, &ComplianceStatus::Allowed)?;
+        }
+        for address in msg.denied.iter() {
+            STATUSES.save(deps.storage, address, &ComplianceStatus::Denied)?;
+        }
+        Ok(Response::default())
+    }
+
+    pub fn execute(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: InstantiateMsg,
+    ) -> StdResult<Response> {
+        Ok(Response::default())
+    }
+
+    pub fn query(deps: Deps, _env: Env, msg: ComplianceQueryMsg) -> StdResult<Binary> {
+        match msg {
+            ComplianceQueryMsg::IsAllowed { address } => to_json_binary(&IsAllowedResponse {
+                status: STATUSES
+                    .may_load(deps.storage, &address)?
+                    .unwrap_or(ComplianceStatus::Unlisted),
+            }),
+        }
+    }
+}
+
+pub fn compliance_registry_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        compliance_registry::execute,
+        compliance_registry::instantiate,
+        compliance_registry::query,
+    );
 
     Box::new(contract)
 }
@@ -26,6 +97,7 @@ pub struct TestingSuite {
     app: MantraApp,
     pub senders: Vec<Addr>,
     pub claimdrop_contract_addr: Addr,
+    pub compliance_registry_addr: Addr,
 }
 
 // helpers
@@ -40,6 +112,11 @@ impl TestingSuite {
         self.app.block_info().time
     }
 
+    #[track_caller]
+    pub fn get_block_height(&mut self) -> u64 {
+        self.app.block_info().height
+    }
+
     #[track_caller]
     pub fn add_day(&mut self) -> &mut Self {
         let mut block_info = self.app.block_info();
@@ -49,6 +126,15 @@ impl TestingSuite {
         self
     }
 
+    #[track_caller]
+    pub fn add_seconds(&mut self, seconds: u64) -> &mut Self {
+        let mut block_info = self.app.block_info();
+        block_info.time = block_info.time.plus_seconds(seconds);
+        self.app.set_block(block_info);
+
+        self
+    }
+
     #[track_caller]
     pub fn add_week(&mut self) -> &mut Self {
         let mut block_info = self.app.block_info();
@@ -99,6 +185,7 @@ impl TestingSuite {
             app,
             senders,
             claimdrop_contract_addr: Addr::unchecked(""),
+            compliance_registry_addr: Addr::unchecked(""),
         }
     }
 
@@ -125,6 +212,34 @@ impl TestingSuite {
     }
 }
 
+impl TestingSuite {
+    #[track_caller]
+    pub fn instantiate_compliance_registry(
+        &mut self,
+        allowed: Vec<String>,
+        denied: Vec<String>,
+    ) -> &mut Self {
+        let msg = compliance_registry::InstantiateMsg { allowed, denied };
+
+        let compliance_registry_code_id = self.app.store_code(compliance_registry_contract());
+        let admin = self.admin();
+
+        self.compliance_registry_addr = self
+            .app
+            .instantiate_contract(
+                compliance_registry_code_id,
+                admin,
+                &msg,
+                &[],
+                "compliance-registry",
+                None,
+            )
+            .unwrap();
+
+        self
+    }
+}
+
 pub trait ResultHandler {
     fn handle_result(&self, result: Result<AppResponse, anyhow::Error>);
 }
@@ -192,7 +307,16 @@ impl TestingSuite {
         amount: Option<Uint128>,
         result: impl ResultHandler,
     ) -> &mut Self {
-        self.execute_contract(sender, ExecuteMsg::Claim { receiver, amount }, &[], result)
+        self.execute_contract(
+            sender,
+            ExecuteMsg::Claim {
+                receiver,
+                amount,
+                ibc_destination: None,
+            },
+            &[],
+            result,
+        )
     }
 
     #[track_caller]
@@ -206,6 +330,7 @@ impl TestingSuite {
     }
 
     #[track_caller]
+    #[allow(clippy::ptr_arg)]
     pub fn add_allocations(
         &mut self,
         sender: &Addr,
@@ -215,7 +340,198 @@ impl TestingSuite {
         self.execute_contract(
             sender,
             ExecuteMsg::AddAllocations {
-                allocations: allocations.clone(),
+                allocations: allocations.iter().cloned().map(Into::into).collect(),
+            },
+            &[],
+            result,
+        )
+    }
+
+    #[track_caller]
+    pub fn add_allocations_packed(
+        &mut self,
+        sender: &Addr,
+        data: Binary,
+        result: impl ResultHandler,
+    ) -> &mut Self {
+        self.execute_contract(
+            sender,
+            ExecuteMsg::AddAllocationsPacked { data },
+            &[],
+            result,
+        )
+    }
+
+    #[track_caller]
+    pub fn add_allocation_rows(
+        &mut self,
+        sender: &Addr,
+        allocations: Vec<AllocationRow>,
+        result: impl ResultHandler,
+    ) -> &mut Self {
+        self.execute_contract(
+            sender,
+            ExecuteMsg::AddAllocations { allocations },
+            &[],
+            result,
+        )
+    }
+
+    #[track_caller]
+    pub fn seed_claims(
+        &mut self,
+        sender: &Addr,
+        claims: Vec<(String, Vec<(u64, Uint128)>)>,
+        result: impl ResultHandler,
+    ) -> &mut Self {
+        self.execute_contract(sender, ExecuteMsg::SeedClaims { claims }, &[], result)
+    }
+
+    #[track_caller]
+    pub fn approve_claimer(
+        &mut self,
+        sender: &Addr,
+        operator: &Addr,
+        expires: Option<Expiration>,
+        max_amount: Option<Uint128>,
+        result: impl ResultHandler,
+    ) -> &mut Self {
+        self.execute_contract(
+            sender,
+            ExecuteMsg::ApproveClaimer {
+                operator: operator.to_string(),
+                expires,
+                max_amount,
+            },
+            &[],
+            result,
+        )
+    }
+
+    #[track_caller]
+    pub fn revoke_claimer(
+        &mut self,
+        sender: &Addr,
+        operator: &Addr,
+        result: impl ResultHandler,
+    ) -> &mut Self {
+        self.execute_contract(
+            sender,
+            ExecuteMsg::RevokeClaimer {
+                operator: operator.to_string(),
+            },
+            &[],
+            result,
+        )
+    }
+
+    #[track_caller]
+    pub fn auto_claim(
+        &mut self,
+        sender: &Addr,
+        enabled: bool,
+        result: impl ResultHandler,
+    ) -> &mut Self {
+        self.execute_contract(sender, ExecuteMsg::AutoClaim { enabled }, &[], result)
+    }
+
+    #[track_caller]
+    pub fn crank(
+        &mut self,
+        sender: &Addr,
+        limit: Option<u32>,
+        result: impl ResultHandler,
+    ) -> &mut Self {
+        self.execute_contract(sender, ExecuteMsg::Crank { limit }, &[], result)
+    }
+
+    #[track_caller]
+    pub fn update_crank_tip(
+        &mut self,
+        sender: &Addr,
+        tip: Decimal,
+        result: impl ResultHandler,
+    ) -> &mut Self {
+        self.execute_contract(sender, ExecuteMsg::UpdateCrankTip { tip }, &[], result)
+    }
+
+    #[track_caller]
+    pub fn fund_crank_tips(
+        &mut self,
+        sender: &Addr,
+        funds: &[Coin],
+        result: impl ResultHandler,
+    ) -> &mut Self {
+        self.execute_contract(sender, ExecuteMsg::FundCrankTips {}, funds, result)
+    }
+
+    #[track_caller]
+    pub fn forfeit_allocations(
+        &mut self,
+        sender: &Addr,
+        addresses: Vec<String>,
+        result: impl ResultHandler,
+    ) -> &mut Self {
+        self.execute_contract(
+            sender,
+            ExecuteMsg::ForfeitAllocations { addresses },
+            &[],
+            result,
+        )
+    }
+
+    #[track_caller]
+    pub fn exit_early(&mut self, sender: &Addr, result: impl ResultHandler) -> &mut Self {
+        self.execute_contract(sender, ExecuteMsg::ExitEarly {}, &[], result)
+    }
+
+    #[track_caller]
+    pub fn trigger_milestone(
+        &mut self,
+        sender: &Addr,
+        id: &str,
+        result: impl ResultHandler,
+    ) -> &mut Self {
+        self.execute_contract(
+            sender,
+            ExecuteMsg::TriggerMilestone { id: id.to_string() },
+            &[],
+            result,
+        )
+    }
+
+    #[track_caller]
+    pub fn finalize_allocations(
+        &mut self,
+        sender: &Addr,
+        expected_hash: HexBinary,
+        expected_count: u64,
+        expected_total: Uint128,
+        result: impl ResultHandler,
+    ) -> &mut Self {
+        self.execute_contract(
+            sender,
+            ExecuteMsg::FinalizeAllocations {
+                expected_hash,
+                expected_count,
+                expected_total,
+            },
+            &[],
+            result,
+        )
+    }
+
+    #[track_caller]
+    pub fn import_state(
+        &mut self,
+        sender: &Addr,
+        entries: &[StateEntry],
+        result: impl ResultHandler,
+    ) -> &mut Self {
+        self.execute_contract(
+            sender,
+            ExecuteMsg::ImportState {
+                entries: entries.to_vec(),
             },
             &[],
             result,
@@ -271,6 +587,81 @@ impl TestingSuite {
             ExecuteMsg::BlacklistAddress {
                 address: address.to_string(),
                 blacklist,
+                reason: None,
+                note: None,
+                expiration: None,
+                freeze_vesting: None,
+            },
+            &[],
+            result,
+        )
+    }
+
+    #[track_caller]
+    pub fn blacklist_address_with_reason(
+        &mut self,
+        sender: &Addr,
+        address: &str,
+        reason: BlacklistReason,
+        result: impl ResultHandler,
+    ) -> &mut Self {
+        self.execute_contract(
+            sender,
+            ExecuteMsg::BlacklistAddress {
+                address: address.to_string(),
+                blacklist: true,
+                reason: Some(reason),
+                note: None,
+                expiration: None,
+                freeze_vesting: None,
+            },
+            &[],
+            result,
+        )
+    }
+
+    #[track_caller]
+    pub fn blacklist_addresses(
+        &mut self,
+        sender: &Addr,
+        addresses: Vec<String>,
+        blacklist: bool,
+        result: impl ResultHandler,
+    ) -> &mut Self {
+        self.execute_contract(
+            sender,
+            ExecuteMsg::BlacklistAddresses {
+                addresses,
+                blacklist,
+                reason: Some(BlacklistReason::Compromised),
+                note: None,
+                expiration: None,
+                freeze_vesting: None,
+            },
+            &[],
+            result,
+        )
+    }
+
+    #[track_caller]
+    pub fn blacklist_address_until(
+        &mut self,
+        sender: &Addr,
+        address: &Addr,
+        note: Option<&str>,
+        expiration: Expiration,
+        freeze_vesting: bool,
+        result: impl ResultHandler,
+    ) -> &mut Self {
+        self.execute_contract(
+            sender,
+            ExecuteMsg::BlacklistAddress {
+                address: address.to_string(),
+                blacklist: true,
+                reason: Some(BlacklistReason::Investigation),
+                note: note.map(|note| note.to_string()),
+                expiration: Some(expiration),
+                freeze_vesting: Some(freeze_vesting),
             },
             &[],
             result,
@@ -298,6 +689,19 @@ impl TestingSuite {
     }
 }
 
+// sudo msg
+impl TestingSuite {
+    #[track_caller]
+    pub fn sudo(&mut self, msg: SudoMsg, result: impl ResultHandler) -> &mut Self {
+        result.handle_result(
+            self.app
+                .wasm_sudo(self.claimdrop_contract_addr.clone(), &msg),
+        );
+
+        self
+    }
+}
+
 // queries
 impl TestingSuite {
     fn query_contract<T>(&mut self, msg: QueryMsg, result: impl Fn(StdResult<T>)) -> &mut Self
@@ -333,6 +737,104 @@ impl TestingSuite {
         )
     }
 
+    #[track_caller]
+    pub fn query_claim_approvals(
+        &mut self,
+        owner: &Addr,
+        start_after: Option<&Addr>,
+        limit: Option<u32>,
+        result: impl Fn(StdResult<ClaimApprovalsResponse>),
+    ) -> &mut Self {
+        self.query_contract(
+            QueryMsg::ClaimApprovals {
+                owner: owner.to_string(),
+                start_after: start_after.map(|addr| addr.to_string()),
+                limit,
+            },
+            result,
+        )
+    }
+
+    #[track_caller]
+    pub fn query_auto_claim(
+        &mut self,
+        start_after: Option<&Addr>,
+        limit: Option<u32>,
+        result: impl Fn(StdResult<AutoClaimResponse>),
+    ) -> &mut Self {
+        self.query_contract(
+            QueryMsg::AutoClaim {
+                start_after: start_after.map(|addr| addr.to_string()),
+                limit,
+            },
+            result,
+        )
+    }
+
+    #[track_caller]
+    pub fn query_rewards_breakdown(
+        &mut self,
+        receiver: &Addr,
+        result: impl Fn(StdResult<RewardsBreakdownResponse>),
+    ) -> &mut Self {
+        self.query_contract(
+            QueryMsg::RewardsBreakdown {
+                receiver: receiver.to_string(),
+            },
+            result,
+        )
+    }
+
+    #[track_caller]
+    pub fn query_rewards_at(
+        &mut self,
+        address: &Addr,
+        timestamp: u64,
+        result: impl Fn(StdResult<RewardsAtResponse>),
+    ) -> &mut Self {
+        self.query_contract(
+            QueryMsg::RewardsAt {
+                address: address.to_string(),
+                timestamp,
+            },
+            result,
+        )
+    }
+
+    #[track_caller]
+    pub fn query_vesting_schedule(
+        &mut self,
+        address: &Addr,
+        points: Vec<u64>,
+        result: impl Fn(StdResult<VestingScheduleResponse>),
+    ) -> &mut Self {
+        self.query_contract(
+            QueryMsg::VestingSchedule {
+                address: address.to_string(),
+                points,
+            },
+            result,
+        )
+    }
+
+    #[track_caller]
+    pub fn query_simulate_claim(
+        &mut self,
+        sender: &Addr,
+        receiver: Option<&Addr>,
+        amount: Option<Uint128>,
+        result: impl Fn(StdResult<SimulateClaimResponse>),
+    ) -> &mut Self {
+        self.query_contract(
+            QueryMsg::SimulateClaim {
+                sender: sender.to_string(),
+                receiver: receiver.map(|addr| addr.to_string()),
+                amount,
+            },
+            result,
+        )
+    }
+
     #[track_caller]
     pub fn query_claimed(
         &mut self,
@@ -386,6 +888,16 @@ impl TestingSuite {
         )
     }
 
+    #[track_caller]
+    pub fn query_blacklist(
+        &mut self,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        result: impl Fn(StdResult<BlacklistedAddressesResponse>),
+    ) -> &mut Self {
+        self.query_contract(QueryMsg::Blacklist { start_after, limit }, result)
+    }
+
     #[track_caller]
     pub fn _query_ownership(
         &mut self,
@@ -424,4 +936,71 @@ impl TestingSuite {
     ) -> &mut Self {
         self.query_contract(QueryMsg::AuthorizedWallets { start_after, limit }, result)
     }
+
+    #[track_caller]
+    pub fn query_audit_log(
+        &mut self,
+        action: Option<&str>,
+        subject: Option<&Addr>,
+        start_after: Option<u64>,
+        limit: Option<u16>,
+        result: impl Fn(StdResult<AuditLogResponse>),
+    ) -> &mut Self {
+        self.query_contract(
+            QueryMsg::AuditLog {
+                action: action.map(|action| action.to_string()),
+                subject: subject.map(|addr| addr.to_string()),
+                start_after,
+                limit,
+            },
+            result,
+        )
+    }
+
+    #[track_caller]
+    pub fn query_claim_history(
+        &mut self,
+        address: &Addr,
+        start_after: Option<u64>,
+        limit: Option<u16>,
+        result: impl Fn(StdResult<ClaimHistoryResponse>),
+    ) -> &mut Self {
+        self.query_contract(
+            QueryMsg::ClaimHistory {
+                address: address.to_string(),
+                start_after,
+                limit,
+            },
+            result,
+        )
+    }
+
+    #[track_caller]
+    pub fn query_export_state(
+        &mut self,
+        start_after: Option<&Addr>,
+        limit: Option<u16>,
+        result: impl Fn(StdResult<ExportStateResponse>),
+    ) -> &mut Self {
+        self.query_contract(
+            QueryMsg::ExportState {
+                start_after: start_after.map(|addr| addr.to_string()),
+                limit,
+            },
+            result,
+        )
+    }
+
+    #[track_caller]
+    pub fn query_allocation_checksum(
+        &mut self,
+        result: impl Fn(StdResult<AllocationChecksumResponse>),
+    ) -> &mut Self {
+        self.query_contract(QueryMsg::AllocationChecksum {}, result)
+    }
+
+    #[track_caller]
+    pub fn query_is_paused(&mut self, result: impl Fn(StdResult<PausedResponse>)) -> &mut Self {
+        self.query_contract(QueryMsg::IsPaused {}, result)
+    }
 }
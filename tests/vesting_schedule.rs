@@ -0,0 +1,184 @@
+use crate::suite::TestingSuite;
+use claimdrop_contract::queries::MAX_VESTING_SCHEDULE_POINTS;
+use cosmwasm_std::{coin, coins, Decimal, Uint128};
+use cw_multi_test::AppResponse;
+use mantra_claimdrop_std::msg::{CampaignAction, CampaignParams, DistributionType};
+mod suite;
+
+#[test]
+fn projected_rewards_follow_vesting_schedule() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let current_time = &suite.get_time();
+    let start = current_time.plus_days(1);
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .add_allocations(
+            alice,
+            &vec![(bob.to_string(), Uint128::new(1_001))],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(CampaignParams {
+                    name: "Test Airdrop I".to_string(),
+                    description: "This is an airdrop".to_string(),
+                    ty: "airdrop".to_string(),
+                    reward_denom: "uom".to_string(),
+                    total_reward: coin(1_001, "uom"),
+                    distribution_type: vec![DistributionType::LinearVesting {
+                        percentage: Decimal::percent(100),
+                        start_time: start.seconds(),
+                        end_time: start.plus_days(10).seconds(),
+                        cliff_duration: None,
+                        early_exit_penalty: None,
+                    }],
+                    start_time: start.seconds(),
+                    end_time: start.plus_days(10).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
+                }),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .top_up_campaign(
+            alice,
+            &coins(1_001, "uom"),
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        // the campaign has not started yet, but the schedule can already be projected
+        .query_vesting_schedule(
+            bob,
+            vec![
+                current_time.seconds(),
+                start.seconds(),
+                start.plus_days(5).seconds(),
+                start.plus_days(10).seconds(),
+                start.plus_days(20).seconds(),
+            ],
+            |result| {
+                let schedule = result.unwrap().schedule;
+                let vested: Vec<u128> = schedule.iter().map(|p| p.vested.amount.u128()).collect();
+                let claimable: Vec<u128> =
+                    schedule.iter().map(|p| p.claimable.amount.u128()).collect();
+                // 1001 * 0.5 = 500.5, floored to 500. The dust is released when the vesting ends.
+                assert_eq!(vested, vec![0, 0, 500, 1_001, 1_001]);
+                assert_eq!(claimable, vec![0, 0, 500, 1_001, 1_001]);
+                assert!(schedule.iter().all(|p| p.claimed == coin(0, "uom")));
+            },
+        );
+
+    for _ in 0..6 {
+        suite.add_day();
+    }
+
+    suite
+        .claim(
+            bob,
+            None,
+            None,
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_rewards_at(bob, start.plus_days(5).seconds(), |result| {
+            let rewards = result.unwrap();
+            assert_eq!(rewards.timestamp, start.plus_days(5).seconds());
+            assert_eq!(rewards.vested, coin(500, "uom"));
+            assert_eq!(rewards.claimed, coin(500, "uom"));
+            assert_eq!(rewards.claimable, coin(0, "uom"));
+        })
+        .query_rewards_at(bob, start.plus_days(8).seconds(), |result| {
+            let rewards = result.unwrap();
+            assert_eq!(rewards.vested, coin(800, "uom"));
+            assert_eq!(rewards.claimed, coin(500, "uom"));
+            assert_eq!(rewards.claimable, coin(300, "uom"));
+        })
+        .query_rewards_at(bob, start.plus_days(10).seconds(), |result| {
+            let rewards = result.unwrap();
+            assert_eq!(rewards.vested, coin(1_001, "uom"));
+            assert_eq!(rewards.claimable, coin(501, "uom"));
+        });
+}
+
+#[test]
+fn vesting_schedule_validates_points() {
+    let mut suite = TestingSuite::default_with_balances(vec![coin(1_000_000_000, "uom")]);
+
+    let alice = &suite.senders[0].clone();
+    let bob = &suite.senders[1].clone();
+    let current_time = &suite.get_time();
+
+    suite
+        .instantiate_claimdrop_contract(Some(alice.to_string()))
+        .add_allocations(
+            alice,
+            &vec![(bob.to_string(), Uint128::new(1_000))],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .manage_campaign(
+            alice,
+            CampaignAction::CreateCampaign {
+                params: Box::new(CampaignParams {
+                    name: "Test Airdrop I".to_string(),
+                    description: "This is an airdrop".to_string(),
+                    ty: "airdrop".to_string(),
+                    reward_denom: "uom".to_string(),
+                    total_reward: coin(1_000, "uom"),
+                    distribution_type: vec![DistributionType::LumpSum {
+                        percentage: Decimal::percent(100),
+                        start_time: current_time.seconds(),
+                    }],
+                    start_time: current_time.seconds(),
+                    end_time: current_time.plus_days(10).seconds(),
+                    schedules: vec![],
+                    require_finalized_allocations: false,
+                }),
+            },
+            &[],
+            |result: Result<AppResponse, anyhow::Error>| {
+                result.unwrap();
+            },
+        )
+        .query_vesting_schedule(bob, vec![], |result| {
+            assert!(result
+                .unwrap_err()
+                .to_string()
+                .contains("points must contain"));
+        })
+        .query_vesting_schedule(
+            bob,
+            vec![current_time.seconds(); MAX_VESTING_SCHEDULE_POINTS + 1],
+            |result| {
+                assert!(result
+                    .unwrap_err()
+                    .to_string()
+                    .contains("points must contain"));
+            },
+        )
+        .query_vesting_schedule(alice, vec![current_time.seconds()], |result| {
+            assert!(result
+                .unwrap_err()
+                .to_string()
+                .contains("No allocation found"));
+        })
+        .query_vesting_schedule(bob, vec![current_time.seconds(), u64::MAX], |result| {
+            assert!(result.unwrap_err().to_string().contains("out of range"));
+        })
+        .query_rewards_at(bob, u64::MAX / 1_000_000_000 + 1, |result| {
+            assert!(result.unwrap_err().to_string().contains("out of range"));
+        });
+}